@@ -0,0 +1,95 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use nabla_game;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::math::fraction::Fraction;
+use nabla_game::math::integral::integral;
+
+const ITERATIONS: u32 = 200;
+
+/// Basis with owned operands like before interning, used as the baseline for clones and equality
+#[derive(Clone, PartialEq)]
+enum OwnedBasis {
+    Leaf(BasisLeaf),
+    Node(Fraction, BasisOperator, Vec<OwnedBasis>),
+}
+
+impl From<&Basis> for OwnedBasis {
+    fn from(basis: &Basis) -> Self {
+        match basis {
+            Basis::BasisLeaf(leaf) => OwnedBasis::Leaf(leaf.clone()),
+            Basis::BasisNode(node) => OwnedBasis::Node(
+                node.coefficient,
+                node.operator.clone(),
+                node.operands.iter().map(OwnedBasis::from).collect(),
+            ),
+        }
+    }
+}
+
+/// times `ITERATIONS` runs of `f` and prints the mean duration
+fn bench<F, T>(name: &str, f: F) -> Duration
+where
+    F: Fn() -> T,
+{
+    // warm up
+    black_box(f());
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    let mean = start.elapsed() / ITERATIONS;
+    println!("{:<32} {:>12?} / iter", name, mean);
+    mean
+}
+
+// repeated integration by parts, every step clones and recombines the previous subtrees
+fn main() {
+    let x = Basis::x();
+    let log_x = LogBasisNode(&x);
+    let e_x = EBasisNode(&x);
+    let sin_x = SinBasisNode(&x);
+    let cos_x = CosBasisNode(&x);
+
+    let mut total = Duration::default();
+    total += bench("I(x^3 * e^x)", || {
+        integral(&((x.clone() ^ 3) * e_x.clone()))
+    });
+    total += bench("I(x^3 * cos(x))", || {
+        integral(&((x.clone() ^ 3) * cos_x.clone()))
+    });
+    total += bench("I(x * log(x))", || integral(&(x.clone() * log_x.clone())));
+    total += bench("I(log(x) / x^2)", || {
+        integral(&(log_x.clone() / (x.clone() ^ 2)))
+    });
+    total += bench("I(I(x^2 * sin(x)))", || {
+        integral(&integral(&((x.clone() ^ 2) * sin_x.clone())))
+    });
+    println!("{:<32} {:>12?}", "total", total);
+
+    // every integration by parts step clones its operands and compares the results against the
+    // table, compare both against the owned representation
+    let results = vec![
+        integral(&((x.clone() ^ 3) * e_x.clone())),
+        integral(&((x.clone() ^ 3) * cos_x.clone())),
+        integral(&integral(&((x.clone() ^ 2) * sin_x.clone()))),
+    ];
+    let copies = results.clone();
+    let owned = results.iter().map(OwnedBasis::from).collect::<Vec<_>>();
+    let owned_copies = owned.clone();
+
+    println!();
+    let shared = bench("clone, interned", || results.clone());
+    let baseline = bench("clone, owned", || owned.clone());
+    println!("{:<32} {:>11.1}x", "clone speedup", ratio(baseline, shared));
+    let shared = bench("eq, interned", || results == copies);
+    let baseline = bench("eq, owned", || owned == owned_copies);
+    println!("{:<32} {:>11.1}x", "eq speedup", ratio(baseline, shared));
+}
+
+/// how many times faster `shared` is than `baseline`
+fn ratio(baseline: Duration, shared: Duration) -> f64 {
+    baseline.as_secs_f64() / shared.as_secs_f64().max(f64::EPSILON)
+}
@@ -1,6 +1,8 @@
 // std imports
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 // outer crate imports
+use crate::math::coefficient::{Coefficient, Constant};
 use crate::math::fraction::Fraction;
 use crate::math::logarithm::logarithm;
 // local imports
@@ -53,13 +55,14 @@ pub fn AddBasisNode(operands: Vec<Basis>) -> Basis {
     });
 
     // recombine <base, coefficient> hash back into polynomial
-    let final_operands = operand_hash.iter().fold(vec![], |mut acc, (k, v)| {
+    let mut final_operands = operand_hash.iter().fold(vec![], |mut acc, (k, v)| {
         if k.is_num(0) || *v == 0 {
             return acc;
         }
         acc.push(k.with_frac(*v));
         acc
     });
+    final_operands.sort_by(canonical_order);
 
     // x + -x = 0
     if final_operands.len() == 0 {
@@ -73,10 +76,100 @@ pub fn AddBasisNode(operands: Vec<Basis>) -> Basis {
     Basis::BasisNode(BasisNode {
         coefficient: Fraction::from(1),
         operator: BasisOperator::Add,
-        operands: final_operands,
+        operands: final_operands.into(),
     })
 }
 
+/// total order of Add and Mult operands, hash maps give no order and equal nodes have to intern the same
+/// powers of variables by descending degree, then other functions, then numbers
+pub(crate) fn canonical_order(a: &Basis, b: &Basis) -> Ordering {
+    operand_rank(a)
+        .cmp(&operand_rank(b))
+        .then_with(|| variable(a).cmp(&variable(b)))
+        .then_with(|| degree(b).partial_cmp(&degree(a)).unwrap_or(Ordering::Equal))
+        .then_with(|| match (a, b) {
+            (Basis::BasisNode(a_node), Basis::BasisNode(b_node)) => a_node
+                .operands
+                .len()
+                .cmp(&b_node.operands.len())
+                .then_with(|| {
+                    a_node
+                        .operands
+                        .iter()
+                        .zip(b_node.operands.iter())
+                        .map(|(a_op, b_op)| canonical_order(a_op, b_op))
+                        .find(|order| *order != Ordering::Equal)
+                        .unwrap_or(Ordering::Equal)
+                }),
+            _ => Ordering::Equal,
+        })
+        .then_with(|| {
+            b.coefficient()
+                .partial_cmp(&a.coefficient())
+                .unwrap_or(Ordering::Equal)
+        })
+}
+
+/// group of an operand in `canonical_order`
+fn operand_rank(basis: &Basis) -> u32 {
+    match basis {
+        Basis::BasisLeaf(BasisLeaf { element, .. }) => match element {
+            BasisElement::X => 0,
+            // any char can be a variable, their symbols are compared in `canonical_order`
+            BasisElement::Var(_) => 1,
+            BasisElement::Num => 1000,
+            BasisElement::Inf => 1001,
+        },
+        Basis::BasisNode(BasisNode {
+            operator, operands, ..
+        }) => match operator {
+            BasisOperator::Pow(_) if matches!(operands[0], Basis::BasisLeaf(_)) => {
+                operand_rank(&operands[0])
+            }
+            BasisOperator::Mult => 200,
+            BasisOperator::Div => 201,
+            BasisOperator::Pow(_) => 202,
+            BasisOperator::E => 203,
+            BasisOperator::Log => 204,
+            BasisOperator::Cos => 205,
+            BasisOperator::Sin => 206,
+            BasisOperator::Acos => 207,
+            BasisOperator::Asin => 208,
+            BasisOperator::Inv => 209,
+            BasisOperator::Int => 210,
+            BasisOperator::Add => 211,
+            BasisOperator::Minus => 212,
+        },
+    }
+}
+
+/// symbol of a variable other than x or of its power, in `canonical_order`
+fn variable(basis: &Basis) -> Option<char> {
+    match basis {
+        Basis::BasisLeaf(BasisLeaf {
+            element: BasisElement::Var(var),
+            ..
+        }) => Some(*var),
+        Basis::BasisNode(BasisNode {
+            operator: BasisOperator::Pow(_),
+            operands,
+            ..
+        }) => variable(&operands[0]),
+        _ => None,
+    }
+}
+
+/// exponent of an operand in `canonical_order`, 1 for leaves
+fn degree(basis: &Basis) -> Fraction {
+    match basis {
+        Basis::BasisNode(BasisNode {
+            operator: BasisOperator::Pow(n),
+            ..
+        }) => *n,
+        _ => Fraction::from(1),
+    }
+}
+
 /// handles Minus BasisNodes, wrapper for AddBasisNode
 #[allow(non_snake_case)]
 pub fn MinusBasisNode(operands: Vec<Basis>) -> Basis {
@@ -138,7 +231,7 @@ fn build_numerator_denominator(
             }) = op
             {
                 final_coefficient *= *mult_coefficient;
-                acc.extend(mult_operands.clone());
+                acc.extend(mult_operands.iter().cloned());
             }
             // if numerator has Div nodes
             else if let Basis::BasisNode(BasisNode {
@@ -155,7 +248,7 @@ fn build_numerator_denominator(
                 }) = &div_operands[0]
                 {
                     final_coefficient *= *div_numerator_coefficient;
-                    acc.extend(div_numerator_operands.clone());
+                    acc.extend(div_numerator_operands.iter().cloned());
                 } else {
                     acc.push(div_operands[0].clone());
                 }
@@ -167,7 +260,7 @@ fn build_numerator_denominator(
                 }) = &div_operands[1]
                 {
                     final_coefficient /= *div_denominator_coefficient;
-                    denominator.extend(div_denominator_operands.clone());
+                    denominator.extend(div_denominator_operands.iter().cloned());
                 } else {
                     denominator.push(div_operands[1].clone());
                 }
@@ -194,7 +287,7 @@ fn build_numerator_denominator(
         }) = op
         {
             final_coefficient /= *mult_coefficient;
-            denominator.extend(mult_operands.clone());
+            denominator.extend(mult_operands.iter().cloned());
         }
         // if denominator has Div nodes
         else if let Basis::BasisNode(BasisNode {
@@ -211,7 +304,7 @@ fn build_numerator_denominator(
             }) = &div_operands[0]
             {
                 final_coefficient /= *div_numerator_coefficient;
-                denominator.extend(div_numerator_operands.clone());
+                denominator.extend(div_numerator_operands.iter().cloned());
             } else {
                 denominator.push(div_operands[0].clone());
             }
@@ -223,7 +316,7 @@ fn build_numerator_denominator(
             }) = &div_operands[1]
             {
                 final_coefficient *= *div_denominator_coefficient;
-                numerator.extend(div_denominator_operands.clone());
+                numerator.extend(div_denominator_operands.iter().cloned());
             } else {
                 numerator.push(div_operands[1].clone());
             }
@@ -243,10 +336,28 @@ fn build_numerator_denominator(
     (final_coefficient, numerator, denominator)
 }
 
+/// factors of the Basis of a product of constants, ie. √2e^2 → [√2, e^2]
+fn constant_factors(constants: BTreeMap<Constant, Fraction>) -> Vec<Basis> {
+    let constant = Coefficient {
+        rational: Fraction::from(1),
+        constants,
+    };
+    match constant.to_basis() {
+        Basis::BasisNode(BasisNode {
+            operator: BasisOperator::Mult,
+            operands,
+            ..
+        }) => operands.iter().cloned().collect(),
+        basis if basis.is_num(1) => vec![],
+        basis => vec![basis],
+    }
+}
+
 /// handles multiplication edge case logic, combines final numerator and denominator
 fn assemble_mult(coefficient: Fraction, numerator: Vec<Basis>, denominator: Vec<Basis>) -> Basis {
     let mut final_coefficient = coefficient;
-    let mut coefficient_hash: HashMap<Fraction, Fraction> = HashMap::new();
+    // symbolic constants are collected separately, ie. √2, e^2, log(2)
+    let mut constant = Coefficient::from(1);
 
     // 0 * n = 0
     if numerator
@@ -281,32 +392,16 @@ fn assemble_mult(coefficient: Fraction, numerator: Vec<Basis>, denominator: Vec<
     let mut denominator_hash: HashMap<Basis, (i32, i32)> = HashMap::new();
     // collect numerator
     numerator.iter().for_each(|factor| {
-        final_coefficient *= factor.coefficient();
-        // move numerics to numeric hash
-        if factor.is_numeric() && !factor.is_node(BasisOperator::Log) {
-            if let Basis::BasisNode(BasisNode {
-                operator: BasisOperator::Pow(pow),
-                operands,
-                ..
-            }) = factor
-            {
-                *coefficient_hash
-                    .entry(Fraction::from((1, pow.d))) // use lowest root and scale up, ie. n^(3/2) * n^(1/2) = (n^3 * n)^(1/2)
-                    .or_insert(Fraction::from(1)) *= operands[0].coefficient() ^ pow.n;
-            } else if let Basis::BasisNode(BasisNode {
-                operator: BasisOperator::E,
-                operands,
-                ..
-            }) = factor
-            {
-                *coefficient_hash
-                    .entry(Fraction { n: 0, d: 0 }) // (0,0) denotes e base
-                    .or_insert(Fraction::from(0)) += operands[0].coefficient();
-            }
+        // move constants to constant coefficient, products that overflow are left unevaluated
+        if let Some(product) = Coefficient::from_basis(factor)
+            .and_then(|factor_constant| constant.clone().checked_mul(factor_constant))
+        {
+            constant = product;
             return;
         }
+        final_coefficient *= factor.coefficient();
         // add base, exponent to numerator hash
-        let element = get_base(factor);
+        let element = get_base(factor).filter(|_| !factor.is_numeric());
         if element.is_some() {
             let (base, n, d) = element.unwrap();
             let leaf = base.with_coefficient(1);
@@ -323,32 +418,16 @@ fn assemble_mult(coefficient: Fraction, numerator: Vec<Basis>, denominator: Vec<
     });
     // divide from numerator and collect denominator
     denominator.iter().for_each(|factor| {
-        final_coefficient /= factor.coefficient();
-        // move numerics to numeric hash
-        if factor.is_numeric() && !factor.is_node(BasisOperator::Log) {
-            if let Basis::BasisNode(BasisNode {
-                operator: BasisOperator::Pow(pow),
-                operands,
-                ..
-            }) = factor
-            {
-                *coefficient_hash
-                    .entry(Fraction::from((1, pow.d))) // use lowest root and scale down, ie. n^(3/2) / n^(1/2) = (n^3 / n)^(1/2)
-                    .or_insert(Fraction::from(1)) /= operands[0].coefficient() ^ pow.n;
-            } else if let Basis::BasisNode(BasisNode {
-                operator: BasisOperator::E,
-                operands,
-                ..
-            }) = factor
-            {
-                *coefficient_hash
-                    .entry(Fraction { n: 0, d: 0 }) // (0,0) denotes e base
-                    .or_insert(Fraction::from(0)) -= operands[0].coefficient();
-            }
+        // move constants to constant coefficient, quotients that overflow are left unevaluated
+        if let Some(quotient) = Coefficient::from_basis(factor)
+            .and_then(|factor_constant| constant.clone().checked_div(factor_constant))
+        {
+            constant = quotient;
             return;
         }
+        final_coefficient /= factor.coefficient();
         // add base, exponent to denominator hash
-        let element = get_base(factor);
+        let element = get_base(factor).filter(|_| !factor.is_numeric());
         if element.is_some() {
             let (base, n, d) = element.unwrap();
             let leaf = base.with_coefficient(1);
@@ -403,32 +482,39 @@ fn assemble_mult(coefficient: Fraction, numerator: Vec<Basis>, denominator: Vec<
             numerator_hash.insert(k.clone(), (Fraction::from(val) - *v).into());
             denominator_hash.remove(k);
         });
-    coefficient_hash.iter().for_each(|(k, v)| {
-        // reinsert e coefficients
-        if k.n == 0 && k.d == 0 {
-            if *v > 0 {
-                numerator_hash.insert(EBasisNode(&Basis::from(*v)), (1, 1));
-            } else {
-                denominator_hash.insert(EBasisNode(&Basis::from(-*v)), (1, 1));
-            }
-        }
-        // reinsert pow coefficients
-        else {
-            // scale final coefficient if coefficient simplifies to rational number
-            if v.try_fractional_root((k.n, k.d)).is_some() {
-                final_coefficient *= *v;
-                return;
+    // reinsert constants, radicals are rationalised into the numerator, ie. 1/√2 = √2/2
+    let constant = match constant
+        .clone()
+        .checked_mul(Coefficient::from(final_coefficient))
+    {
+        Some(constant) => constant,
+        // keep the rational parts apart if they overflow together
+        None => {
+            if constant.rational != 1 {
+                numerator_hash.insert(Basis::from(constant.rational), (1, 1));
             }
-            if (v.n == 1 && v.d > 1) ^ (k.n < 0) {
-                denominator_hash.insert(PowBasisNode(k.n.abs(), k.d, &Basis::from(v.d)), (1, 1));
-            } else {
-                numerator_hash.insert(PowBasisNode(k.n, k.d, &Basis::from(*v)), (1, 1));
+            Coefficient {
+                rational: final_coefficient,
+                ..constant
             }
         }
+    };
+    final_coefficient = constant.rational;
+    let (above, below): (BTreeMap<Constant, Fraction>, BTreeMap<Constant, Fraction>) = constant
+        .constants
+        .into_iter()
+        .partition(|(_, pow)| *pow > 0);
+    constant_factors(above).into_iter().for_each(|factor| {
+        numerator_hash.insert(factor, (1, 1));
     });
+    constant_factors(below.into_iter().map(|(c, pow)| (c, -pow)).collect())
+        .into_iter()
+        .for_each(|factor| {
+            denominator_hash.insert(factor, (1, 1));
+        });
 
     // combine exponents and filter 0
-    let final_numerator = numerator_hash.iter().fold(vec![], |mut acc, (k, (n, d))| {
+    let mut final_numerator = numerator_hash.iter().fold(vec![], |mut acc, (k, (n, d))| {
         if k.is_num(0) || *n == 0 || *d == 0 {
             return acc;
         }
@@ -440,7 +526,7 @@ fn assemble_mult(coefficient: Fraction, numerator: Vec<Basis>, denominator: Vec<
         acc
     });
     // combine exponents and filter 0
-    let final_denominator = denominator_hash
+    let mut final_denominator = denominator_hash
         .iter()
         .fold(vec![], |mut acc, (k, (n, d))| {
             if k.is_num(0) || *n == 0 || *d == 0 {
@@ -453,6 +539,8 @@ fn assemble_mult(coefficient: Fraction, numerator: Vec<Basis>, denominator: Vec<
             }
             acc
         });
+    final_numerator.sort_by(canonical_order);
+    final_denominator.sort_by(canonical_order);
 
     // [x] / 1
     if final_numerator.len() == 1 && final_denominator.len() == 0 {
@@ -474,7 +562,7 @@ fn assemble_mult(coefficient: Fraction, numerator: Vec<Basis>, denominator: Vec<
                     Basis::BasisNode(BasisNode {
                         coefficient: Fraction::from(final_coefficient.n),
                         operator: BasisOperator::Mult,
-                        operands: final_numerator,
+                        operands: final_numerator.into(),
                     })
                 }
                 // [x] / [y]
@@ -490,14 +578,15 @@ fn assemble_mult(coefficient: Fraction, numerator: Vec<Basis>, denominator: Vec<
                     Basis::BasisNode(BasisNode {
                         coefficient: Fraction::from(final_coefficient.d),
                         operator: BasisOperator::Mult,
-                        operands: final_denominator,
+                        operands: final_denominator.into(),
                     })
                 }
                 // [x] / n
                 else {
                     final_denominator[0].clone() * final_coefficient.d
                 },
-            ],
+            ]
+            .into(),
         });
     }
 
@@ -508,7 +597,7 @@ fn assemble_mult(coefficient: Fraction, numerator: Vec<Basis>, denominator: Vec<
     Basis::BasisNode(BasisNode {
         coefficient: final_coefficient,
         operator: BasisOperator::Mult,
-        operands: final_numerator,
+        operands: final_numerator.into(),
     })
 }
 
@@ -562,7 +651,7 @@ pub fn DivBasisNode(numerator: &Basis, denominator: &Basis) -> Basis {
                 denominator_list.push(operands[1].clone());
             }
             BasisOperator::Mult => {
-                numerator_list.extend(operands.clone());
+                numerator_list.extend(operands.iter().cloned());
             }
             _ => numerator_list.push(numerator.clone()),
         },
@@ -579,7 +668,7 @@ pub fn DivBasisNode(numerator: &Basis, denominator: &Basis) -> Basis {
                 denominator_list.push(operands[0].clone());
             }
             BasisOperator::Mult => {
-                denominator_list.extend(operands.clone());
+                denominator_list.extend(operands.iter().cloned());
             }
             _ => denominator_list.push(denominator.clone()),
         },
@@ -608,6 +697,10 @@ pub fn PowBasisNode(n: i32, d: i32, base: &Basis) -> Basis {
     else if base.is_num(0) || base.is_num(1) {
         return base.clone();
     }
+    // INF^-x = 0
+    else if (base.is_inf(1) || base.is_inf(-1)) && pow.n < 0 {
+        return Basis::from(0);
+    }
     // INF^x = INF
     else if base.is_inf(1) {
         return Basis::inf(1);
@@ -621,6 +714,10 @@ pub fn PowBasisNode(n: i32, d: i32, base: &Basis) -> Basis {
         // even power
         return Basis::inf(1);
     }
+    // constant bases, extracts perfect powers and rationalises, ie. 8^(1/2) = 2√2, (4/9)^(3/2) = 8/27
+    if let Some(constant) = Coefficient::from_basis(base).and_then(|constant| constant.pow(pow)) {
+        return constant.to_basis();
+    }
     match base {
         Basis::BasisNode(BasisNode {
             coefficient: inner_coefficient,
@@ -639,27 +736,19 @@ pub fn PowBasisNode(n: i32, d: i32, base: &Basis) -> Basis {
                     return Basis::BasisNode(BasisNode {
                         coefficient: *inner_coefficient ^ pow.n,
                         operator: BasisOperator::Pow(pow),
-                        operands: vec![Basis::x()],
+                        operands: vec![Basis::x()].into(),
                     });
                 }
                 // x^(n/d)^(i_n/i_d) = x^(n*i_n/d*i_d)
                 else {
-                    return Basis::BasisNode(BasisNode {
-                        coefficient: Fraction::from(1),
-                        operator: BasisOperator::Mult,
-                        operands: vec![
-                            Basis::BasisNode(BasisNode {
-                                coefficient: Fraction::from(1),
-                                operator: BasisOperator::Pow(Fraction::from((n, d))),
-                                operands: vec![Basis::from(*inner_coefficient)],
-                            }),
-                            Basis::BasisNode(BasisNode {
-                                coefficient: Fraction::from(1),
-                                operator: BasisOperator::Pow(pow),
-                                operands: vec![Basis::x()],
-                            }),
-                        ],
-                    });
+                    return MultBasisNode(vec![
+                        coefficient_pow(*inner_coefficient, Fraction::from((n, d))),
+                        Basis::BasisNode(BasisNode {
+                            coefficient: Fraction::from(1),
+                            operator: BasisOperator::Pow(pow),
+                            operands: vec![Basis::x()].into(),
+                        }),
+                    ]);
                 }
             }
             // f(x)^(n/d)^(d/n) = f(x)^(1)
@@ -679,9 +768,15 @@ pub fn PowBasisNode(n: i32, d: i32, base: &Basis) -> Basis {
             BasisOperator::Div if inner_operands.iter().any(|op| op.is_numeric()) => {
                 return (inner_operands[0].clone() ^ pow) / (inner_operands[1].clone() ^ pow);
             }
-            // (ab)^n = a^n * b^n
+            // (cab)^n = c^n * a^n * b^n
             BasisOperator::Mult => {
-                return MultBasisNode(inner_operands.iter().map(|op| op.clone() ^ pow).collect())
+                return MultBasisNode(
+                    inner_operands
+                        .iter()
+                        .map(|op| op.clone() ^ pow)
+                        .chain([coefficient_pow(*inner_coefficient, pow)])
+                        .collect(),
+                )
             }
             _ => {}
         },
@@ -690,14 +785,17 @@ pub fn PowBasisNode(n: i32, d: i32, base: &Basis) -> Basis {
 
     // a^(n/d) = a^n / a^d
     if base.is_numeric() {
-        let try_coefficient = base.coefficient().try_fractional_root((pow.n, pow.d));
+        // only a number leaf is its coefficient, ie. not 3^21 when it overflows
+        let try_coefficient = Some(base.coefficient())
+            .filter(|coefficient| base.is_frac(*coefficient))
+            .and_then(|coefficient| coefficient.try_fractional_root((pow.n, pow.d)));
         if try_coefficient.is_some() {
             return Basis::from(try_coefficient.unwrap());
         }
         return Basis::BasisNode(BasisNode {
             coefficient: Fraction::from(1),
             operator: BasisOperator::Pow(pow),
-            operands: vec![base.clone()],
+            operands: vec![base.clone()].into(),
         });
     }
 
@@ -706,7 +804,7 @@ pub fn PowBasisNode(n: i32, d: i32, base: &Basis) -> Basis {
         return Basis::BasisNode(BasisNode {
             coefficient: base.coefficient() ^ pow.n,
             operator: BasisOperator::Pow(pow),
-            operands: vec![base.with_coefficient(1)],
+            operands: vec![base.with_coefficient(1)].into(),
         });
     }
 
@@ -716,27 +814,31 @@ pub fn PowBasisNode(n: i32, d: i32, base: &Basis) -> Basis {
         return Basis::BasisNode(BasisNode {
             coefficient: try_coefficient.unwrap(),
             operator: BasisOperator::Pow(pow),
-            operands: vec![base.with_coefficient(1)],
+            operands: vec![base.with_coefficient(1)].into(),
         });
     }
 
     // equivalent of coefficient^pow * f(x)^pow
-    Basis::BasisNode(BasisNode {
-        coefficient: Fraction::from(1),
-        operator: BasisOperator::Mult,
-        operands: vec![
-            Basis::BasisNode(BasisNode {
-                coefficient: Fraction::from(1),
-                operator: BasisOperator::Pow(Fraction::from((n, d))),
-                operands: vec![Basis::from(base.coefficient())],
-            }),
-            Basis::BasisNode(BasisNode {
-                coefficient: Fraction::from(1),
-                operator: BasisOperator::Pow(pow),
-                operands: vec![base.with_coefficient(1)],
-            }),
-        ],
-    })
+    MultBasisNode(vec![
+        coefficient_pow(base.coefficient(), pow),
+        Basis::BasisNode(BasisNode {
+            coefficient: Fraction::from(1),
+            operator: BasisOperator::Pow(pow),
+            operands: vec![base.with_coefficient(1)].into(),
+        }),
+    ])
+}
+
+/// rational coefficient raised to given power, simplified if the result is real, ie. 8^(1/2) = 2√2
+fn coefficient_pow(coefficient: Fraction, pow: Fraction) -> Basis {
+    match Coefficient::from(coefficient).pow(pow) {
+        Some(constant) => constant.to_basis(),
+        None => Basis::BasisNode(BasisNode {
+            coefficient: Fraction::from(1),
+            operator: BasisOperator::Pow(pow),
+            operands: vec![Basis::from(coefficient)].into(),
+        }),
+    }
 }
 
 /// handles Sqrt exponents, wrapper for PowBasisNode
@@ -773,7 +875,7 @@ pub fn LogBasisNode(base: &Basis) -> Basis {
     Basis::BasisNode(BasisNode {
         coefficient: Fraction::from(1),
         operator: BasisOperator::Log,
-        operands: vec![base.clone()],
+        operands: vec![base.clone()].into(),
     })
 }
 
@@ -798,7 +900,7 @@ pub fn EBasisNode(operand: &Basis) -> Basis {
     Basis::BasisNode(BasisNode {
         coefficient: Fraction::from(1),
         operator: BasisOperator::E,
-        operands: vec![operand.clone()],
+        operands: vec![operand.clone()].into(),
     })
 }
 
@@ -808,7 +910,7 @@ pub fn CosBasisNode(operand: &Basis) -> Basis {
     Basis::BasisNode(BasisNode {
         coefficient: Fraction::from(1),
         operator: BasisOperator::Cos,
-        operands: vec![operand.clone()],
+        operands: vec![operand.clone()].into(),
     })
 }
 
@@ -818,7 +920,7 @@ pub fn SinBasisNode(operand: &Basis) -> Basis {
     Basis::BasisNode(BasisNode {
         coefficient: Fraction::from(1),
         operator: BasisOperator::Sin,
-        operands: vec![operand.clone()],
+        operands: vec![operand.clone()].into(),
     })
 }
 
@@ -828,7 +930,7 @@ pub fn ACosBasisNode(operand: &Basis) -> Basis {
     Basis::BasisNode(BasisNode {
         coefficient: Fraction::from(1),
         operator: BasisOperator::Acos,
-        operands: vec![operand.clone()],
+        operands: vec![operand.clone()].into(),
     })
 }
 
@@ -838,7 +940,7 @@ pub fn ASinBasisNode(operand: &Basis) -> Basis {
     Basis::BasisNode(BasisNode {
         coefficient: Fraction::from(1),
         operator: BasisOperator::Asin,
-        operands: vec![operand.clone()],
+        operands: vec![operand.clone()].into(),
     })
 }
 
@@ -849,11 +951,11 @@ pub fn InvBasisNode(base: &Basis) -> Basis {
         Basis::BasisNode(BasisNode {
             operator, operands, ..
         }) => match operator {
-            // assumes basic case of e^x
+            // e^x is injective on the whole line
             BasisOperator::E if operands[0].is_x() => {
                 return LogBasisNode(&Basis::x());
             }
-            // assumes basic case of log(x)
+            // log(x) is injective on x > 0
             BasisOperator::Log if operands[0].is_x() => {
                 return EBasisNode(&Basis::x());
             }
@@ -865,7 +967,7 @@ pub fn InvBasisNode(base: &Basis) -> Basis {
     Basis::BasisNode(BasisNode {
         coefficient: Fraction::from(1), // TODO:D add reciprocal coefficient here ?
         operator: BasisOperator::Inv,
-        operands: vec![base.clone()],
+        operands: vec![base.clone()].into(),
     })
 }
 
@@ -875,6 +977,33 @@ pub fn IntBasisNode(integrand: &Basis) -> Basis {
     Basis::BasisNode(BasisNode {
         coefficient: Fraction::from(1),
         operator: BasisOperator::Int,
-        operands: vec![integrand.clone()],
+        operands: vec![integrand.clone()].into(),
     })
 }
+
+/// rebuilds `basis_node` over new operands through the builders above, so the result is simplified
+/// division by 0 is kept as is for validation to report, instead of panicking in `DivBasisNode`
+pub fn rebuild_node(basis_node: &BasisNode, operands: Vec<Basis>) -> Basis {
+    let node = match basis_node.operator {
+        BasisOperator::Add => AddBasisNode(operands),
+        BasisOperator::Minus => MinusBasisNode(operands),
+        BasisOperator::Mult => MultBasisNode(operands),
+        BasisOperator::Div if operands[1].is_num(0) || operands[1].coefficient() == 0 => {
+            return Basis::BasisNode(BasisNode {
+                operands: operands.into(),
+                ..basis_node.clone()
+            });
+        }
+        BasisOperator::Div => DivBasisNode(&operands[0], &operands[1]),
+        BasisOperator::Pow(pow) => PowBasisNode(pow.n, pow.d, &operands[0]),
+        BasisOperator::E => EBasisNode(&operands[0]),
+        BasisOperator::Log => LogBasisNode(&operands[0]),
+        BasisOperator::Cos => CosBasisNode(&operands[0]),
+        BasisOperator::Sin => SinBasisNode(&operands[0]),
+        BasisOperator::Acos => ACosBasisNode(&operands[0]),
+        BasisOperator::Asin => ASinBasisNode(&operands[0]),
+        BasisOperator::Inv => InvBasisNode(&operands[0]),
+        BasisOperator::Int => IntBasisNode(&operands[0]),
+    };
+    node * basis_node.coefficient
+}
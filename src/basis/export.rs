@@ -0,0 +1,358 @@
+// outer crate imports
+use crate::math::fraction::Fraction;
+// local imports
+use super::pretty::infix;
+use super::structs::*;
+
+/// syntax of exported Basis
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ExportFormat {
+    SymPy,       // srepr, ie. Pow(Symbol('x'), Integer(2))
+    Python,      // SymPy expression, ie. x**2
+    Mathematica, // FullForm, ie. Power[x, 2]
+    Infix,       // see pretty::infix, ie. x^2
+}
+
+/// exports Basis in given format
+pub fn export(basis: &Basis, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::SymPy => srepr(basis),
+        ExportFormat::Python => python(basis),
+        ExportFormat::Mathematica => full_form(basis),
+        ExportFormat::Infix => infix(basis),
+    }
+}
+
+/// exports every Basis in given format, one per line
+pub fn export_all<'a>(bases: impl IntoIterator<Item = &'a Basis>, format: ExportFormat) -> String {
+    bases
+        .into_iter()
+        .map(|basis| export(basis, format))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// SymPy srepr of Basis, ie. Add(Pow(Symbol('x'), Integer(2)), Integer(1))
+pub fn srepr(basis: &Basis) -> String {
+    tree(basis, Tree::SymPy)
+}
+
+/// Mathematica FullForm of Basis, ie. Plus[Power[x, 2], 1]
+pub fn full_form(basis: &Basis) -> String {
+    tree(basis, Tree::Mathematica)
+}
+
+/// SymPy expression of Basis, ie. x**2 + 1, for sympify or a Python session with `from sympy import *`
+pub fn python(basis: &Basis) -> String {
+    python_of(basis).0
+}
+
+// tree syntax
+
+/// tree syntaxes with a head for every operation, differing in names and brackets
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Tree {
+    SymPy,
+    Mathematica,
+}
+
+/// applies head to arguments, ie. Add(a, b) or Plus[a, b]
+fn call(style: Tree, head: &str, arguments: &[String]) -> String {
+    match style {
+        Tree::SymPy => format!("{}({})", head, arguments.join(", ")),
+        Tree::Mathematica => format!("{}[{}]", head, arguments.join(", ")),
+    }
+}
+
+/// head of an operation in tree syntax, SymPy name first
+fn head(style: Tree, names: (&'static str, &'static str)) -> &'static str {
+    match style {
+        Tree::SymPy => names.0,
+        Tree::Mathematica => names.1,
+    }
+}
+
+/// rational number in tree syntax, ie. Integer(2), Rational[1, 2]
+fn number(style: Tree, fraction: Fraction) -> String {
+    match (style, fraction) {
+        (Tree::SymPy, Fraction { n, d: 1 }) => format!("Integer({})", n),
+        (Tree::Mathematica, Fraction { n, d: 1 }) => n.to_string(),
+        (_, Fraction { n, d }) => call(style, "Rational", &[n.to_string(), d.to_string()]),
+    }
+}
+
+/// x or another variable in tree syntax
+fn symbol(style: Tree, name: char) -> String {
+    match style {
+        Tree::SymPy => format!("Symbol('{}')", name),
+        Tree::Mathematica => name.to_string(),
+    }
+}
+
+/// multiplies body by coefficient in tree syntax, unless coefficient is 1
+fn scaled(style: Tree, coefficient: Fraction, body: String) -> String {
+    if coefficient == 1 {
+        return body;
+    }
+    call(
+        style,
+        head(style, ("Mul", "Times")),
+        &[number(style, coefficient), body],
+    )
+}
+
+/// Basis in tree syntax
+fn tree(basis: &Basis, style: Tree) -> String {
+    match basis {
+        Basis::BasisLeaf(basis_leaf) => {
+            let element = match basis_leaf.element {
+                BasisElement::Num => return number(style, basis_leaf.coefficient),
+                BasisElement::Inf => {
+                    return match (style, basis_leaf.coefficient.n > 0) {
+                        (Tree::SymPy, true) => String::from("oo"),
+                        (Tree::SymPy, false) => String::from("-oo"),
+                        (Tree::Mathematica, true) => String::from("DirectedInfinity[1]"),
+                        (Tree::Mathematica, false) => String::from("DirectedInfinity[-1]"),
+                    }
+                }
+                BasisElement::X => symbol(style, 'x'),
+                BasisElement::Var(name) => symbol(style, name),
+            };
+            scaled(style, basis_leaf.coefficient, element)
+        }
+        Basis::BasisNode(basis_node) => {
+            scaled(style, basis_node.coefficient, tree_node(basis_node, style))
+        }
+    }
+}
+
+/// BasisNode in tree syntax, ignoring its coefficient
+fn tree_node(basis_node: &BasisNode, style: Tree) -> String {
+    let operands = basis_node
+        .operands
+        .iter()
+        .map(|op| tree(op, style))
+        .collect::<Vec<String>>();
+    let unary = |names| call(style, head(style, names), &operands[..1]);
+    let x = symbol(style, 'x');
+    match basis_node.operator {
+        BasisOperator::Add => call(style, head(style, ("Add", "Plus")), &operands),
+        // a - b - c as a + (-1)b + (-1)c
+        BasisOperator::Minus => {
+            let mut terms = vec![operands[0].clone()];
+            terms.extend(
+                operands[1..]
+                    .iter()
+                    .map(|op| scaled(style, Fraction::from(-1), op.clone())),
+            );
+            call(style, head(style, ("Add", "Plus")), &terms)
+        }
+        BasisOperator::Mult => call(style, head(style, ("Mul", "Times")), &operands),
+        // a/b as a * b^-1
+        BasisOperator::Div => {
+            let reciprocal = call(
+                style,
+                head(style, ("Pow", "Power")),
+                &[operands[1].clone(), number(style, Fraction::from(-1))],
+            );
+            call(
+                style,
+                head(style, ("Mul", "Times")),
+                &[operands[0].clone(), reciprocal],
+            )
+        }
+        BasisOperator::Pow(pow) => call(
+            style,
+            head(style, ("Pow", "Power")),
+            &[operands[0].clone(), number(style, pow)],
+        ),
+        BasisOperator::E => match style {
+            Tree::SymPy => call(style, "exp", &operands[..1]),
+            Tree::Mathematica => call(style, "Power", &[String::from("E"), operands[0].clone()]),
+        },
+        BasisOperator::Log => unary(("log", "Log")),
+        BasisOperator::Cos => unary(("cos", "Cos")),
+        BasisOperator::Sin => unary(("sin", "Sin")),
+        BasisOperator::Acos => unary(("acos", "ArcCos")),
+        BasisOperator::Asin => unary(("asin", "ArcSin")),
+        // left unevaluated, as the inverse function of x ↦ f(x) applied to x
+        BasisOperator::Inv => match style {
+            Tree::SymPy => format!(
+                "Function('InverseFunction')({}, {})",
+                call(style, "Lambda", &[x.clone(), operands[0].clone()]),
+                x
+            ),
+            Tree::Mathematica => format!(
+                "InverseFunction[{}][{}]",
+                call(style, "Function", &[x.clone(), operands[0].clone()]),
+                x
+            ),
+        },
+        BasisOperator::Int => call(
+            style,
+            head(style, ("Integral", "Integrate")),
+            &[operands[0].clone(), x],
+        ),
+    }
+}
+
+// python syntax
+
+/// binding strength of a Python expression, operands binding weaker than their context are parenthesised
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Sum,
+    Product,
+    Unary,
+    Power,
+    Atom,
+}
+
+/// Python expression, parenthesised if it binds weaker than `context`
+fn python_wrap(basis: &Basis, context: Precedence) -> String {
+    let (string, precedence) = python_of(basis);
+    if precedence < context {
+        format!("({})", string)
+    } else {
+        string
+    }
+}
+
+/// rational number in Python syntax, fractions as Rational to avoid float division
+fn python_number(fraction: Fraction) -> (String, Precedence) {
+    match fraction {
+        Fraction { n, d: 1 } if n < 0 => (n.to_string(), Precedence::Unary),
+        Fraction { n, d: 1 } => (n.to_string(), Precedence::Atom),
+        Fraction { n, d } => (format!("Rational({}, {})", n, d), Precedence::Atom),
+    }
+}
+
+/// multiplies body by coefficient in Python syntax, unless coefficient is 1
+fn python_scaled(
+    coefficient: Fraction,
+    body: String,
+    precedence: Precedence,
+) -> (String, Precedence) {
+    match coefficient {
+        Fraction { n: 1, d: 1 } => (body, precedence),
+        Fraction { n: -1, d: 1 } => (format!("-{}", body), precedence.min(Precedence::Unary)),
+        _ => (
+            format!("{}*{}", python_number(coefficient).0, body),
+            Precedence::Product,
+        ),
+    }
+}
+
+/// Python expression of Basis with the precedence of its outermost operation
+fn python_of(basis: &Basis) -> (String, Precedence) {
+    match basis {
+        Basis::BasisLeaf(basis_leaf) => match basis_leaf.element {
+            BasisElement::Num => python_number(basis_leaf.coefficient),
+            BasisElement::Inf if basis_leaf.coefficient.n > 0 => {
+                (String::from("oo"), Precedence::Atom)
+            }
+            BasisElement::Inf => (String::from("-oo"), Precedence::Unary),
+            BasisElement::X => {
+                python_scaled(basis_leaf.coefficient, String::from("x"), Precedence::Atom)
+            }
+            BasisElement::Var(name) => python_scaled(
+                basis_leaf.coefficient,
+                format!("Symbol('{}')", name),
+                Precedence::Atom,
+            ),
+        },
+        // parenthesise only sums under a coefficient, ie. 2*(x + 1) but 2*x**2
+        Basis::BasisNode(basis_node) => match python_node(basis_node) {
+            (body, Precedence::Sum) if basis_node.coefficient != 1 => python_scaled(
+                basis_node.coefficient,
+                format!("({})", body),
+                Precedence::Atom,
+            ),
+            (body, precedence) => python_scaled(basis_node.coefficient, body, precedence),
+        },
+    }
+}
+
+/// Python expression of a BasisNode, ignoring its coefficient
+fn python_node(basis_node: &BasisNode) -> (String, Precedence) {
+    let operands = &basis_node.operands;
+    let function = |name: &str| {
+        (
+            format!("{}({})", name, python_of(&operands[0]).0),
+            Precedence::Atom,
+        )
+    };
+    match basis_node.operator {
+        // negative terms are subtracted, ie. 1 - 2*x
+        BasisOperator::Add => {
+            let mut string = python_of(&operands[0]).0;
+            for op in operands.iter().skip(1) {
+                let term = python_of(op).0;
+                match term.strip_prefix('-') {
+                    Some(positive) => string += &format!(" - {}", positive),
+                    None => string += &format!(" + {}", term),
+                }
+            }
+            (string, Precedence::Sum)
+        }
+        BasisOperator::Minus => (
+            operands
+                .iter()
+                .enumerate()
+                .map(|(i, op)| match i {
+                    0 => python_of(op).0,
+                    _ => python_wrap(op, Precedence::Product),
+                })
+                .collect::<Vec<String>>()
+                .join(" - "),
+            Precedence::Sum,
+        ),
+        BasisOperator::Mult => (
+            operands
+                .iter()
+                .map(|op| python_wrap(op, Precedence::Product))
+                .collect::<Vec<String>>()
+                .join("*"),
+            Precedence::Product,
+        ),
+        BasisOperator::Div => (
+            format!(
+                "{}/{}",
+                python_wrap(&operands[0], Precedence::Product),
+                python_wrap(&operands[1], Precedence::Unary)
+            ),
+            Precedence::Product,
+        ),
+        BasisOperator::Pow(pow) => {
+            let exponent = match pow {
+                Fraction { n, d: 1 } if n < 0 => format!("({})", n),
+                _ => python_number(pow).0,
+            };
+            (
+                format!(
+                    "{}**{}",
+                    python_wrap(&operands[0], Precedence::Atom),
+                    exponent
+                ),
+                Precedence::Power,
+            )
+        }
+        BasisOperator::E => function("exp"),
+        BasisOperator::Log => function("log"),
+        BasisOperator::Cos => function("cos"),
+        BasisOperator::Sin => function("sin"),
+        BasisOperator::Acos => function("acos"),
+        BasisOperator::Asin => function("asin"),
+        BasisOperator::Inv => (
+            format!(
+                "Function('InverseFunction')(Lambda(x, {}), x)",
+                python_of(&operands[0]).0
+            ),
+            Precedence::Atom,
+        ),
+        BasisOperator::Int => (
+            format!("Integral({}, x)", python_of(&operands[0]).0),
+            Precedence::Atom,
+        ),
+    }
+}
@@ -0,0 +1,148 @@
+// std imports
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter, Result};
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::Deref;
+use std::rc::{Rc, Weak};
+// local imports
+use super::structs::Basis;
+
+/// number of interns between sweeps of dead entries from the node table
+const SWEEP_INTERVAL: usize = 1024;
+
+/// shared operand list of a BasisNode, structurally identical lists are interned to the same allocation
+#[derive(Clone)]
+pub struct Operands {
+    nodes: Rc<[Basis]>,
+    hash: u64, // structural hash, computed once when interned
+}
+
+impl Operands {
+    /// checks if both operand lists are the same interned allocation, O(1)
+    pub fn ptr_eq(&self, other: &Operands) -> bool {
+        Rc::ptr_eq(&self.nodes, &other.nodes)
+    }
+
+    /// id of the interned allocation, shared by all structurally identical operand lists alive at once
+    pub fn id(&self) -> usize {
+        Rc::as_ptr(&self.nodes) as *const Basis as usize
+    }
+}
+
+/// interns given operands into node table, reusing an existing allocation if one is alive
+impl From<Vec<Basis>> for Operands {
+    fn from(operands: Vec<Basis>) -> Self {
+        let mut hasher = DefaultHasher::new();
+        operands.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        NODE_TABLE.with(|table| table.borrow_mut().intern(operands, hash))
+    }
+}
+impl FromIterator<Basis> for Operands {
+    fn from_iter<I: IntoIterator<Item = Basis>>(iter: I) -> Self {
+        Operands::from(iter.into_iter().collect::<Vec<Basis>>())
+    }
+}
+
+/// allows Operands to be indexed, sliced, and iterated like a Vec
+impl Deref for Operands {
+    type Target = [Basis];
+
+    fn deref(&self) -> &[Basis] {
+        &self.nodes
+    }
+}
+impl<'a> IntoIterator for &'a Operands {
+    type Item = &'a Basis;
+    type IntoIter = std::slice::Iter<'a, Basis>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.iter()
+    }
+}
+
+/// ordered equality, shortcuts if both are the same interned allocation or their hashes differ
+impl PartialEq for Operands {
+    fn eq(&self, other: &Operands) -> bool {
+        self.ptr_eq(other) || (self.hash == other.hash && self.nodes[..] == other.nodes[..])
+    }
+}
+impl Eq for Operands {}
+
+/// uses hash computed at intern time, avoids rehashing the whole subtree
+impl Hash for Operands {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+/// formats as a list, same as the previous Vec representation
+impl Debug for Operands {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.nodes.iter()).finish()
+    }
+}
+
+/// table of live operand lists bucketed by structural hash
+#[derive(Default)]
+struct NodeTable {
+    buckets: HashMap<u64, Vec<Weak<[Basis]>>>,
+    interns: usize, // interns since last sweep
+}
+
+impl NodeTable {
+    /// returns existing allocation structurally equal to `operands` or stores a new one
+    fn intern(&mut self, operands: Vec<Basis>, hash: u64) -> Operands {
+        self.interns += 1;
+        if self.interns >= SWEEP_INTERVAL {
+            self.interns = 0;
+            self.sweep();
+        }
+
+        let bucket = self.buckets.entry(hash).or_default();
+        let existing = bucket
+            .iter()
+            .filter_map(|weak| weak.upgrade())
+            .find(|nodes| {
+                nodes.len() == operands.len()
+                    && nodes.iter().zip(operands.iter()).all(|(a, b)| a == b)
+            });
+        if let Some(nodes) = existing {
+            return Operands { nodes, hash };
+        }
+
+        let nodes: Rc<[Basis]> = Rc::from(operands);
+        bucket.push(Rc::downgrade(&nodes));
+        Operands { nodes, hash }
+    }
+
+    /// drops entries whose operands are no longer referenced by any Basis
+    fn sweep(&mut self) {
+        self.buckets.retain(|_, bucket| {
+            bucket.retain(|weak| weak.strong_count() > 0);
+            !bucket.is_empty()
+        });
+    }
+
+    /// number of live interned operand lists
+    fn len(&self) -> usize {
+        self.buckets
+            .values()
+            .map(|bucket| bucket.iter().filter(|weak| weak.strong_count() > 0).count())
+            .sum()
+    }
+}
+
+// thread local rather than static mut since native tests build Bases from parallel threads
+thread_local! {
+    static NODE_TABLE: RefCell<NodeTable> = RefCell::new(NodeTable::default());
+}
+
+/// number of distinct operand lists currently alive on this thread
+pub fn node_table_size() -> usize {
+    NODE_TABLE.with(|table| table.borrow().len())
+}
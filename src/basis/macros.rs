@@ -0,0 +1,98 @@
+/// builds a Basis from a math expression, expanding into the builder functions and overloaded operators
+/// `+ - * /` keep their usual precedence, `^` binds tightest to the factor on its left
+/// supports `x`, `inf`, integer literals, `e^...`, `log`/`ln`, `sin`, `cos`, `acos`, `asin`, `sqrt`, `inv`, `int`,
+/// exponents `^n`, `^-n`, `^(n/d)`, `^(-n/d)`, `^{expr}` and interpolation of Rust values with `{expr}` or a bare variable
+#[macro_export]
+macro_rules! basis {
+    // end of input, emit collected expression
+    (@munch [$($out:tt)*]) => { ($($out)*) };
+    // binary and unary operators are passed through to rust precedence
+    (@munch [$($out:tt)*] + $($rest:tt)*) => { $crate::basis!(@munch [$($out)* +] $($rest)*) };
+    (@munch [$($out:tt)*] - $($rest:tt)*) => { $crate::basis!(@munch [$($out)* -] $($rest)*) };
+    (@munch [$($out:tt)*] * $($rest:tt)*) => { $crate::basis!(@munch [$($out)* *] $($rest)*) };
+    (@munch [$($out:tt)*] / $($rest:tt)*) => { $crate::basis!(@munch [$($out)* /] $($rest)*) };
+    (@munch [$($out:tt)*] $($rest:tt)+) => { $crate::basis!(@atom [$($out)*] $($rest)+) };
+
+    // e^f(x)
+    (@atom [$($out:tt)*] e ^ ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::builders::EBasisNode(&$crate::basis!($($inner)+))) $($rest)*)
+    };
+    (@atom [$($out:tt)*] e ^ $inner:tt $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::builders::EBasisNode(&$crate::basis!($inner))) $($rest)*)
+    };
+    // unary functions
+    (@atom [$($out:tt)*] log ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::builders::LogBasisNode(&$crate::basis!($($inner)+))) $($rest)*)
+    };
+    (@atom [$($out:tt)*] ln ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::builders::LogBasisNode(&$crate::basis!($($inner)+))) $($rest)*)
+    };
+    (@atom [$($out:tt)*] sin ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::builders::SinBasisNode(&$crate::basis!($($inner)+))) $($rest)*)
+    };
+    (@atom [$($out:tt)*] cos ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::builders::CosBasisNode(&$crate::basis!($($inner)+))) $($rest)*)
+    };
+    (@atom [$($out:tt)*] acos ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::builders::ACosBasisNode(&$crate::basis!($($inner)+))) $($rest)*)
+    };
+    (@atom [$($out:tt)*] asin ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::builders::ASinBasisNode(&$crate::basis!($($inner)+))) $($rest)*)
+    };
+    (@atom [$($out:tt)*] sqrt ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::builders::SqrtBasisNode(1, &$crate::basis!($($inner)+))) $($rest)*)
+    };
+    (@atom [$($out:tt)*] inv ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::builders::InvBasisNode(&$crate::basis!($($inner)+))) $($rest)*)
+    };
+    (@atom [$($out:tt)*] int ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::builders::IntBasisNode(&$crate::basis!($($inner)+))) $($rest)*)
+    };
+    // leaves
+    (@atom [$($out:tt)*] x $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::structs::Basis::x()) $($rest)*)
+    };
+    (@atom [$($out:tt)*] inf $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::structs::Basis::inf(1)) $($rest)*)
+    };
+    (@atom [$($out:tt)*] $n:literal $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::structs::Basis::from($n)) $($rest)*)
+    };
+    // parenthesised subexpression
+    (@atom [$($out:tt)*] ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis!($($inner)+)) $($rest)*)
+    };
+    // interpolated rust values
+    (@atom [$($out:tt)*] {$value:expr} $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::structs::Basis::from($value)) $($rest)*)
+    };
+    (@atom [$($out:tt)*] $value:ident $($rest:tt)*) => {
+        $crate::basis!(@pow [$($out)*] ($crate::basis::structs::Basis::from($value.clone())) $($rest)*)
+    };
+
+    // exponent of preceding factor
+    (@pow [$($out:tt)*] $factor:tt ^ (- $n:literal / $d:literal) $($rest:tt)*) => {
+        $crate::basis!(@munch [$($out)* ($factor ^ (-$n, $d))] $($rest)*)
+    };
+    (@pow [$($out:tt)*] $factor:tt ^ ($n:literal / $d:literal) $($rest:tt)*) => {
+        $crate::basis!(@munch [$($out)* ($factor ^ ($n, $d))] $($rest)*)
+    };
+    (@pow [$($out:tt)*] $factor:tt ^ (- $n:literal) $($rest:tt)*) => {
+        $crate::basis!(@munch [$($out)* ($factor ^ -$n)] $($rest)*)
+    };
+    (@pow [$($out:tt)*] $factor:tt ^ - $n:literal $($rest:tt)*) => {
+        $crate::basis!(@munch [$($out)* ($factor ^ -$n)] $($rest)*)
+    };
+    (@pow [$($out:tt)*] $factor:tt ^ $n:literal $($rest:tt)*) => {
+        $crate::basis!(@munch [$($out)* ($factor ^ $n)] $($rest)*)
+    };
+    (@pow [$($out:tt)*] $factor:tt ^ {$pow:expr} $($rest:tt)*) => {
+        $crate::basis!(@munch [$($out)* ($factor ^ $pow)] $($rest)*)
+    };
+    (@pow [$($out:tt)*] $factor:tt $($rest:tt)*) => {
+        $crate::basis!(@munch [$($out)* $factor] $($rest)*)
+    };
+
+    // entry point
+    ($($expr:tt)+) => { $crate::basis!(@munch [] $($expr)+) };
+}
@@ -1,3 +1,8 @@
 pub mod builders;
+pub mod export;
+pub mod intern;
+mod macros;
 pub mod operators;
+pub mod pretty;
 pub mod structs;
+pub mod traversal;
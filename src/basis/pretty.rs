@@ -0,0 +1,645 @@
+// std imports
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, Result};
+// outer crate imports
+use crate::game::flags::DISPLAY_LN_FOR_LOG;
+use crate::math::coefficient::Coefficient;
+use crate::math::fraction::Fraction;
+// local imports
+use super::structs::*;
+
+/// binding strength of an expression, operands binding weaker than their context are parenthesised
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Precedence {
+    Sum,
+    Product,
+    Power,
+    Atom,
+}
+
+/// single line form of Basis with only the parentheses precedence needs, ie. x^2/log(x), -x + 1
+pub fn infix(basis: &Basis) -> String {
+    infix_of(basis).0
+}
+
+/// multi-line form of Basis with stacked fractions, raised exponents, radicals and integral signs
+pub fn pretty(basis: &Basis) -> String {
+    pretty_of(basis).0.to_string()
+}
+
+/// Basis with the sign of its coefficient flipped, if that coefficient is negative
+fn negated(basis: &Basis) -> Option<Basis> {
+    match basis {
+        Basis::BasisLeaf(basis_leaf) if basis_leaf.coefficient < 0 => {
+            Some(Basis::BasisLeaf(BasisLeaf {
+                coefficient: -basis_leaf.coefficient,
+                element: basis_leaf.element.clone(),
+            }))
+        }
+        Basis::BasisNode(basis_node)
+            if basis_node.coefficient < 0
+                && !matches!(
+                    basis_node.operator,
+                    BasisOperator::Add | BasisOperator::Minus
+                ) =>
+        {
+            Some(Basis::BasisNode(BasisNode {
+                coefficient: -basis_node.coefficient,
+                operator: basis_node.operator,
+                operands: basis_node.operands.clone(),
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// operands in reading order, sums and products by descending degree in x with positive terms first
+fn display_order(basis_node: &BasisNode) -> Vec<Basis> {
+    let mut operands = basis_node.operands.to_vec();
+    if matches!(
+        basis_node.operator,
+        BasisOperator::Add | BasisOperator::Mult
+    ) {
+        // stable, so operands keep the builders' canonical order otherwise
+        operands.sort_by(|a, b| {
+            degree(b)
+                .partial_cmp(&degree(a))
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| negated(a).is_some().cmp(&negated(b).is_some()))
+        });
+    }
+    operands
+}
+
+/// polynomial degree of a term in x, 0 for constants and other functions
+fn degree(basis: &Basis) -> Fraction {
+    match basis {
+        Basis::BasisLeaf(BasisLeaf {
+            element: BasisElement::X,
+            ..
+        }) => Fraction::from(1),
+        Basis::BasisNode(BasisNode {
+            operator: BasisOperator::Pow(n),
+            operands,
+            ..
+        }) if operands[0].is_x() => *n,
+        Basis::BasisNode(BasisNode {
+            operator: BasisOperator::Mult,
+            operands,
+            ..
+        }) => operands
+            .iter()
+            .fold(Fraction::from(0), |acc, op| acc + degree(op)),
+        _ => Fraction::from(0),
+    }
+}
+
+/// Basis node with its coefficient reset to 1
+fn without_coefficient(basis_node: &BasisNode) -> BasisNode {
+    BasisNode {
+        coefficient: Fraction::from(1),
+        operator: basis_node.operator,
+        operands: basis_node.operands.clone(),
+    }
+}
+
+/// superscript digits and minus sign, ie. -12 → ⁻¹²
+fn superscript(n: i32) -> String {
+    n.to_string()
+        .chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            '9' => '⁹',
+            _ => unreachable!("{} is not a digit or sign", c),
+        })
+        .collect()
+}
+
+/// radical sign of given degree, ie. √, ∛, ∜, ⁵√
+fn radical_sign(degree: i32) -> String {
+    match degree {
+        2 => String::from("√"),
+        3 => String::from("∛"),
+        4 => String::from("∜"),
+        _ => format!("{}√", superscript(degree)),
+    }
+}
+
+/// name of function operators, ie. log, arccos, f⁻¹
+fn function_name(operator: BasisOperator) -> String {
+    match operator {
+        BasisOperator::Acos => String::from("arccos"),
+        BasisOperator::Asin => String::from("arcsin"),
+        BasisOperator::Inv => String::from("f⁻¹"),
+        BasisOperator::Log if unsafe { DISPLAY_LN_FOR_LOG } => String::from("ln"),
+        _ => operator.to_string(),
+    }
+}
+
+// single line
+
+/// infix string of Basis with the precedence of its outermost operation
+fn infix_of(basis: &Basis) -> (String, Precedence) {
+    if let Some(negative) = negated(basis) {
+        return (
+            format!("-{}", wrap(&negative, Precedence::Product)),
+            Precedence::Sum,
+        );
+    }
+    match basis {
+        Basis::BasisLeaf(basis_leaf) => infix_leaf(basis_leaf),
+        Basis::BasisNode(basis_node) => match Coefficient::from_basis(basis) {
+            // symbolic constants, ie. 2√2
+            Some(constant) if !constant.is_rational() => {
+                (constant.to_string(), Precedence::Product)
+            }
+            _ => infix_node(basis_node),
+        },
+    }
+}
+
+/// infix string of Basis, parenthesised if it binds weaker than `context`
+fn wrap(basis: &Basis, context: Precedence) -> String {
+    let (string, precedence) = infix_of(basis);
+    if precedence < context {
+        format!("({})", string)
+    } else {
+        string
+    }
+}
+
+/// infix string of a non-negative BasisLeaf, ie. 3x/2
+fn infix_leaf(basis_leaf: &BasisLeaf) -> (String, Precedence) {
+    let Fraction { n, d } = basis_leaf.coefficient;
+    match basis_leaf.element {
+        BasisElement::Num if d == 1 => (n.to_string(), Precedence::Atom),
+        BasisElement::Num => (format!("{}/{}", n, d), Precedence::Product),
+        BasisElement::Inf => (String::from("∞"), Precedence::Atom),
+        _ => {
+            let symbol = BasisLeaf {
+                coefficient: Fraction::from(1),
+                element: basis_leaf.element.clone(),
+            }
+            .to_string();
+            match (n, d) {
+                (1, 1) => (symbol, Precedence::Atom),
+                (1, _) => (format!("{}/{}", symbol, d), Precedence::Product),
+                (_, 1) => (format!("{}{}", n, symbol), Precedence::Product),
+                _ => (format!("{}{}/{}", n, symbol, d), Precedence::Product),
+            }
+        }
+    }
+}
+
+/// infix string of a BasisNode with a non-negative coefficient
+fn infix_node(basis_node: &BasisNode) -> (String, Precedence) {
+    let (body, precedence) = infix_body(&without_coefficient(basis_node));
+    let body_in = |context: Precedence| {
+        if precedence < context {
+            format!("({})", body)
+        } else {
+            body.clone()
+        }
+    };
+    match (basis_node.coefficient, denominator(basis_node)) {
+        (Fraction { n: 1, d: 1 }, _) => (body, precedence),
+        // fold integers into reciprocals, ie. 2/(x + 1) rather than 2·1/(x + 1)
+        (Fraction { n, d: 1 }, Some(denominator)) => (
+            format!("{}/{}", n, wrap(denominator, Precedence::Power)),
+            Precedence::Product,
+        ),
+        // juxtapose integers, ie. 2sin(x), but not 2·2^x
+        (Fraction { n, d: 1 }, None) => {
+            let body = body_in(Precedence::Product);
+            let separator = if body.starts_with(|c: char| c.is_ascii_digit()) {
+                "·"
+            } else {
+                ""
+            };
+            (format!("{}{}{}", n, separator, body), Precedence::Product)
+        }
+        (Fraction { n, d }, _) => (
+            format!("({}/{}){}", n, d, body_in(Precedence::Product)),
+            Precedence::Product,
+        ),
+    }
+}
+
+/// denominator of a BasisNode with numerator 1, ie. 1/x or (1)/(x + 1)
+fn denominator(basis_node: &BasisNode) -> Option<&Basis> {
+    match basis_node.operator {
+        BasisOperator::Pow(Fraction { n: -1, d: 1 }) => Some(&basis_node.operands[0]),
+        BasisOperator::Div if basis_node.operands[0].is_num(1) => Some(&basis_node.operands[1]),
+        _ => None,
+    }
+}
+
+/// infix string of a BasisNode ignoring its coefficient
+fn infix_body(basis_node: &BasisNode) -> (String, Precedence) {
+    let operands = &display_order(basis_node);
+    match basis_node.operator {
+        BasisOperator::Add | BasisOperator::Minus => {
+            let mut string = infix_of(&operands[0]).0;
+            for op in operands.iter().skip(1) {
+                match negated(op) {
+                    Some(positive) if basis_node.operator == BasisOperator::Add => {
+                        string += &format!(" - {}", wrap(&positive, Precedence::Product));
+                    }
+                    _ if basis_node.operator == BasisOperator::Minus => {
+                        string += &format!(" - {}", wrap(op, Precedence::Product));
+                    }
+                    _ => string += &format!(" + {}", infix_of(op).0),
+                }
+            }
+            (string, Precedence::Sum)
+        }
+        BasisOperator::Mult => (
+            operands
+                .iter()
+                .map(|op| wrap(op, Precedence::Product))
+                .collect::<Vec<String>>()
+                .join("·"),
+            Precedence::Product,
+        ),
+        BasisOperator::Div => (
+            format!(
+                "{}/{}",
+                wrap(&operands[0], Precedence::Product),
+                wrap(&operands[1], Precedence::Power)
+            ),
+            Precedence::Product,
+        ),
+        BasisOperator::Pow(pow) => match (pow.n, pow.d) {
+            (-1, 1) => (
+                format!("1/{}", wrap(&operands[0], Precedence::Power)),
+                Precedence::Product,
+            ),
+            (1, d) => (
+                format!(
+                    "{}{}",
+                    radical_sign(d),
+                    wrap(&operands[0], Precedence::Atom)
+                ),
+                Precedence::Power,
+            ),
+            (n, 1) => (
+                format!("{}^{}", wrap(&operands[0], Precedence::Atom), n),
+                Precedence::Power,
+            ),
+            (n, d) => (
+                format!("{}^({}/{})", wrap(&operands[0], Precedence::Atom), n, d),
+                Precedence::Power,
+            ),
+        },
+        BasisOperator::E => (
+            format!("e^{}", wrap(&operands[0], Precedence::Atom)),
+            Precedence::Power,
+        ),
+        BasisOperator::Int => (
+            format!("∫({})dx", infix_of(&operands[0]).0),
+            Precedence::Atom,
+        ),
+        operator => (
+            format!("{}({})", function_name(operator), infix_of(&operands[0]).0),
+            Precedence::Atom,
+        ),
+    }
+}
+
+// multi-line
+
+/// rectangle of text lines, `baseline` is the row that lines up with neighbouring blocks
+#[derive(Clone, Debug)]
+struct Block {
+    lines: Vec<String>,
+    baseline: usize,
+}
+
+impl Block {
+    /// single line block
+    fn text(string: &str) -> Block {
+        Block {
+            lines: vec![string.to_string()],
+            baseline: 0,
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// line `row` padded to the width of the block
+    fn padded(&self, row: usize) -> String {
+        let line = &self.lines[row];
+        format!(
+            "{}{}",
+            line,
+            " ".repeat(self.width() - line.chars().count())
+        )
+    }
+
+    /// places other block to the right, lining up baselines
+    fn beside(self, other: Block) -> Block {
+        let above = self.baseline.max(other.baseline);
+        let below = (self.height() - self.baseline).max(other.height() - other.baseline);
+        let row_of = |block: &Block, row: usize| {
+            (row + block.baseline)
+                .checked_sub(above)
+                .filter(|row| *row < block.height())
+        };
+        let lines = (0..above + below)
+            .map(|row| {
+                let left = match row_of(&self, row) {
+                    Some(row) => self.padded(row),
+                    None => " ".repeat(self.width()),
+                };
+                let right = match row_of(&other, row) {
+                    Some(row) => other.padded(row),
+                    None => " ".repeat(other.width()),
+                };
+                left + &right
+            })
+            .collect();
+        Block {
+            lines,
+            baseline: above,
+        }
+    }
+
+    /// stacks numerator over denominator with a fraction bar on the baseline
+    fn over(numerator: Block, denominator: Block) -> Block {
+        let width = numerator.width().max(denominator.width());
+        let centred = |block: &Block| {
+            (0..block.height())
+                .map(|row| {
+                    let line = block.padded(row);
+                    let left = (width - block.width()) / 2;
+                    format!(
+                        "{}{}{}",
+                        " ".repeat(left),
+                        line,
+                        " ".repeat(width - block.width() - left)
+                    )
+                })
+                .collect::<Vec<String>>()
+        };
+        let mut lines = centred(&numerator);
+        lines.push("─".repeat(width));
+        lines.extend(centred(&denominator));
+        Block {
+            lines,
+            baseline: numerator.height(),
+        }
+    }
+
+    /// raises exponent above the top right of the block
+    fn raised(self, exponent: Block) -> Block {
+        let (width, exponent_width) = (self.width(), exponent.width());
+        let mut lines = (0..exponent.height())
+            .map(|row| format!("{}{}", " ".repeat(width), exponent.padded(row)))
+            .collect::<Vec<String>>();
+        lines.extend(
+            (0..self.height())
+                .map(|row| format!("{}{}", self.padded(row), " ".repeat(exponent_width))),
+        );
+        Block {
+            lines,
+            baseline: exponent.height() + self.baseline,
+        }
+    }
+
+    /// draws a radical sign of given degree with a bar over the block
+    fn radical(self, degree: i32) -> Block {
+        let sign = radical_sign(degree);
+        let indent = " ".repeat(sign.chars().count() - 1);
+        let mut lines = vec![format!("{} {}", indent, "─".repeat(self.width()))];
+        for row in 0..self.height() {
+            let left = if row + 1 == self.height() {
+                sign.clone()
+            } else {
+                format!("{}│", indent)
+            };
+            lines.push(left + &self.padded(row));
+        }
+        Block {
+            lines,
+            baseline: self.baseline + 1,
+        }
+    }
+
+    /// surrounds the block with parentheses as tall as it is
+    fn parenthesised(self) -> Block {
+        self.bracketed(('(', ')'), ('⎛', '⎞'), ('⎜', '⎟'), ('⎝', '⎠'))
+    }
+
+    /// prefixes the block with an integral sign as tall as it is, followed by dx
+    fn integral(self) -> Block {
+        let sign = Block {
+            lines: match self.height() {
+                1 => vec![String::from("∫")],
+                height => std::iter::once(String::from("⌠"))
+                    .chain((2..height).map(|_| String::from("⎮")))
+                    .chain(std::iter::once(String::from("⌡")))
+                    .collect(),
+            },
+            baseline: self.baseline,
+        };
+        sign.beside(self).beside(Block::text(" dx"))
+    }
+
+    /// surrounds the block with single characters on one line, or top, middle and bottom pieces otherwise
+    fn bracketed(
+        self,
+        single: (char, char),
+        top: (char, char),
+        middle: (char, char),
+        bottom: (char, char),
+    ) -> Block {
+        let height = self.height();
+        let lines = (0..height)
+            .map(|row| {
+                let (left, right) = match row {
+                    _ if height == 1 => single,
+                    0 => top,
+                    _ if row + 1 == height => bottom,
+                    _ => middle,
+                };
+                format!("{}{}{}", left, self.padded(row), right)
+            })
+            .collect();
+        Block {
+            lines,
+            baseline: self.baseline,
+        }
+    }
+}
+
+/// lines of the block joined by newlines, trailing spaces removed
+impl Display for Block {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        let lines = self
+            .lines
+            .iter()
+            .map(|line| line.trim_end())
+            .collect::<Vec<&str>>();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// block of Basis with the precedence of its outermost operation
+fn pretty_of(basis: &Basis) -> (Block, Precedence) {
+    if let Some(negative) = negated(basis) {
+        return (
+            Block::text("-").beside(pretty_wrap(&negative, Precedence::Product)),
+            Precedence::Sum,
+        );
+    }
+    match basis {
+        Basis::BasisLeaf(basis_leaf) => pretty_leaf(basis_leaf),
+        Basis::BasisNode(basis_node) => match Coefficient::from_basis(basis) {
+            Some(constant) if !constant.is_rational() => {
+                (Block::text(&constant.to_string()), Precedence::Product)
+            }
+            _ => pretty_node(basis_node),
+        },
+    }
+}
+
+/// block of Basis, parenthesised if it binds weaker than `context`
+fn pretty_wrap(basis: &Basis, context: Precedence) -> Block {
+    let (block, precedence) = pretty_of(basis);
+    if precedence < context {
+        block.parenthesised()
+    } else {
+        block
+    }
+}
+
+/// block of a non-negative BasisLeaf, fractional coefficients are stacked
+fn pretty_leaf(basis_leaf: &BasisLeaf) -> (Block, Precedence) {
+    let Fraction { n, d } = basis_leaf.coefficient;
+    if d == 1 {
+        let (string, precedence) = infix_leaf(basis_leaf);
+        return (Block::text(&string), precedence);
+    }
+    let numerator = infix_leaf(&BasisLeaf {
+        coefficient: Fraction::from(n),
+        element: basis_leaf.element.clone(),
+    })
+    .0;
+    (
+        Block::over(Block::text(&numerator), Block::text(&d.to_string())),
+        Precedence::Product,
+    )
+}
+
+/// block of a BasisNode with a non-negative coefficient
+fn pretty_node(basis_node: &BasisNode) -> (Block, Precedence) {
+    let (body, precedence) = pretty_body(&without_coefficient(basis_node));
+    let body = if precedence < Precedence::Product && basis_node.coefficient != 1 {
+        body.parenthesised()
+    } else {
+        body
+    };
+    match (basis_node.coefficient, denominator(basis_node)) {
+        (Fraction { n: 1, d: 1 }, _) => (body, precedence),
+        (Fraction { n, d: 1 }, Some(denominator)) => (
+            Block::over(Block::text(&n.to_string()), pretty_of(denominator).0),
+            Precedence::Product,
+        ),
+        (Fraction { n, d: 1 }, None) => (
+            Block::text(&n.to_string()).beside(body),
+            Precedence::Product,
+        ),
+        (Fraction { n, d }, _) => (
+            Block::over(Block::text(&n.to_string()), Block::text(&d.to_string())).beside(body),
+            Precedence::Product,
+        ),
+    }
+}
+
+/// block of a BasisNode ignoring its coefficient
+fn pretty_body(basis_node: &BasisNode) -> (Block, Precedence) {
+    let operands = &display_order(basis_node);
+    match basis_node.operator {
+        BasisOperator::Add | BasisOperator::Minus => {
+            let mut block = pretty_of(&operands[0]).0;
+            for op in operands.iter().skip(1) {
+                block = match negated(op) {
+                    Some(positive) if basis_node.operator == BasisOperator::Add => block
+                        .beside(Block::text(" - "))
+                        .beside(pretty_wrap(&positive, Precedence::Product)),
+                    _ if basis_node.operator == BasisOperator::Minus => block
+                        .beside(Block::text(" - "))
+                        .beside(pretty_wrap(op, Precedence::Product)),
+                    _ => block.beside(Block::text(" + ")).beside(pretty_of(op).0),
+                };
+            }
+            (block, Precedence::Sum)
+        }
+        BasisOperator::Mult => {
+            let mut factors = operands
+                .iter()
+                .map(|op| pretty_wrap(op, Precedence::Product));
+            let first = factors.next().unwrap_or_else(|| Block::text("1"));
+            (
+                factors.fold(first, |block, factor| {
+                    block.beside(Block::text("·")).beside(factor)
+                }),
+                Precedence::Product,
+            )
+        }
+        BasisOperator::Div => (
+            Block::over(pretty_of(&operands[0]).0, pretty_of(&operands[1]).0),
+            Precedence::Product,
+        ),
+        BasisOperator::Pow(pow) => match (pow.n, pow.d) {
+            (-1, 1) => (
+                Block::over(Block::text("1"), pretty_of(&operands[0]).0),
+                Precedence::Product,
+            ),
+            (1, d) => (pretty_of(&operands[0]).0.radical(d), Precedence::Atom),
+            (n, 1) => {
+                let base = pretty_wrap(&operands[0], Precedence::Atom);
+                // single line bases take superscript digits, ie. x²
+                let block = if base.height() == 1 {
+                    base.beside(Block::text(&superscript(n)))
+                } else {
+                    base.raised(Block::text(&n.to_string()))
+                };
+                (block, Precedence::Power)
+            }
+            (n, d) => (
+                pretty_wrap(&operands[0], Precedence::Atom)
+                    .raised(Block::text(&format!("{}/{}", n, d))),
+                Precedence::Power,
+            ),
+        },
+        BasisOperator::E => (
+            Block::text("e").raised(pretty_of(&operands[0]).0),
+            Precedence::Power,
+        ),
+        BasisOperator::Int => (pretty_of(&operands[0]).0.integral(), Precedence::Atom),
+        operator => (
+            Block::text(&function_name(operator)).beside(pretty_of(&operands[0]).0.parenthesised()),
+            Precedence::Atom,
+        ),
+    }
+}
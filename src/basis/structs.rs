@@ -3,9 +3,14 @@ use std::fmt::{Display, Formatter, Result};
 // outer crate imports
 use crate::game::cards::BasisCard;
 use crate::game::flags::{DISPLAY_LN_FOR_LOG, USE_FRACTIONAL_EXPONENTS};
+use crate::math::coefficient::Coefficient;
+use crate::math::error::MathError;
 use crate::math::fraction::Fraction;
+// local imports
+use super::intern::Operands;
+use super::pretty::pretty;
 // util imports
-use crate::util::ToLatex;
+use crate::util::{Language, MathMLFlags, ToLatex, ToMathML, ToSpeech};
 
 /// type union of the starter basis or complex basis
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
@@ -106,10 +111,24 @@ impl Basis {
             })
         )
     }
+    /// checks if basis is a variable BasisLeaf with given symbol, x is always BasisElement::X
+    pub fn is_var(&self, symbol: char) -> bool {
+        match self {
+            Basis::BasisLeaf(BasisLeaf {
+                element: BasisElement::X,
+                ..
+            }) => symbol == 'x',
+            Basis::BasisLeaf(BasisLeaf {
+                element: BasisElement::Var(var),
+                ..
+            }) => *var == symbol,
+            _ => false,
+        }
+    }
     /// checks if basis is INF with given sign
     pub fn is_inf(&self, i: i32) -> bool {
         if !(i == 1 || i == -1) {
-            panic!("INF must be -1 or 1 only")
+            return false; // INF is only ever -1 or 1
         }
         matches!(
             self,
@@ -124,10 +143,21 @@ impl Basis {
     pub fn x() -> Basis {
         Basis::BasisLeaf(BasisLeaf::x())
     }
+    /// creates Basis of variable BasisLeaf with given symbol
+    pub fn var(symbol: char) -> Basis {
+        Basis::BasisLeaf(BasisLeaf::var(symbol))
+    }
     /// creates Basis of INF BasisLeaf
     pub fn inf(i: i32) -> Basis {
         Basis::BasisLeaf(BasisLeaf::inf(i))
     }
+    /// creates Basis of INF BasisLeaf, returns MathError instead of panicking on invalid sign
+    pub fn try_inf(i: i32) -> std::result::Result<Basis, MathError> {
+        if !(i == 1 || i == -1) {
+            return Err(MathError::Malformed(format!("INF with coefficient {}", i)));
+        }
+        Ok(Basis::inf(i))
+    }
 
     /// checks if two Bases have same structure
     pub fn like(&self, other: &Basis) -> bool {
@@ -153,22 +183,22 @@ impl From<BasisCard> for Basis {
             BasisCard::X2 => Basis::BasisNode(BasisNode {
                 coefficient: Fraction::from(1),
                 operator: BasisOperator::Pow(Fraction { n: 2, d: 1 }),
-                operands: vec![Basis::x()],
+                operands: vec![Basis::x()].into(),
             }),
             BasisCard::Cos => Basis::BasisNode(BasisNode {
                 coefficient: Fraction::from(1),
                 operator: BasisOperator::Cos,
-                operands: vec![Basis::x()],
+                operands: vec![Basis::x()].into(),
             }),
             BasisCard::Sin => Basis::BasisNode(BasisNode {
                 coefficient: Fraction::from(1),
                 operator: BasisOperator::Sin,
-                operands: vec![Basis::x()],
+                operands: vec![Basis::x()].into(),
             }),
             BasisCard::E => Basis::BasisNode(BasisNode {
                 coefficient: Fraction::from(1),
                 operator: BasisOperator::E,
-                operands: vec![Basis::x()],
+                operands: vec![Basis::x()].into(),
             }),
         }
     }
@@ -195,12 +225,20 @@ impl From<i32> for Basis {
     }
 }
 
-/// string representation of Basis, defers to BasisLeaf and BasisNode
+/// string representation of Basis, defers to BasisLeaf and BasisNode, or to pretty with {:#}
 impl Display for Basis {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        // {:#} lays out Basis over multiple lines
+        if f.alternate() {
+            return write!(f, "{}", pretty(self));
+        }
         match self {
             Basis::BasisLeaf(basis_leaf) => write!(f, "{}", basis_leaf),
-            Basis::BasisNode(basis_node) => write!(f, "{}", basis_node),
+            Basis::BasisNode(basis_node) => match Coefficient::from_basis(self) {
+                // symbolic constants, ie. 2√2
+                Some(constant) if !constant.is_rational() => write!(f, "{}", constant),
+                _ => write!(f, "{}", basis_node),
+            },
         }
     }
 }
@@ -210,11 +248,66 @@ impl ToLatex for Basis {
     fn to_latex(&self) -> String {
         match self {
             Basis::BasisLeaf(basis_leaf) => format!("{}", basis_leaf.to_latex()),
-            Basis::BasisNode(basis_node) => format!("{}", basis_node.to_latex()),
+            Basis::BasisNode(basis_node) => match Coefficient::from_basis(self) {
+                // symbolic constants, ie. 2\sqrt{2}
+                Some(constant) if !constant.is_rational() => constant.to_latex(),
+                _ => format!("{}", basis_node.to_latex()),
+            },
+        }
+    }
+}
+
+/// MathML representation of Basis, defers to BasisLeaf and BasisNode
+impl ToMathML for Basis {
+    fn to_mathml_with(&self, flags: MathMLFlags) -> String {
+        match self {
+            Basis::BasisLeaf(basis_leaf) => basis_leaf.to_mathml_with(flags),
+            Basis::BasisNode(basis_node) => match Coefficient::from_basis(self) {
+                // symbolic constants, ie. 2<msqrt><mn>2</mn></msqrt>
+                Some(constant) if !constant.is_rational() => {
+                    format!("<mrow>{}</mrow>", constant.to_mathml_with(flags))
+                }
+                _ => basis_node.to_mathml_with(flags),
+            },
+        }
+    }
+}
+
+/// spoken Basis, defers to BasisLeaf and BasisNode
+impl ToSpeech for Basis {
+    fn to_speech(&self, language: Language) -> String {
+        match self {
+            Basis::BasisLeaf(basis_leaf) => basis_leaf.to_speech(language),
+            Basis::BasisNode(basis_node) => match Coefficient::from_basis(self) {
+                // symbolic constants, ie. 2 times the square root of 2
+                Some(constant) if !constant.is_rational() => constant.to_speech(language),
+                _ => basis_node.to_speech(language),
+            },
         }
     }
 }
 
+/// spoken Basis, grouping sums, products and quotients so they read unambiguously as operands
+fn grouped_speech(basis: &Basis, language: Language) -> String {
+    match basis {
+        Basis::BasisNode(BasisNode {
+            operator:
+                BasisOperator::Add | BasisOperator::Minus | BasisOperator::Mult | BasisOperator::Div,
+            ..
+        }) => group_speech(basis, language),
+        _ => basis.to_speech(language),
+    }
+}
+
+/// spoken Basis between an opening and closing word, ie. the quantity x plus 1 end quantity
+fn group_speech(basis: &Basis, language: Language) -> String {
+    let speech = basis.to_speech(language);
+    match language {
+        Language::English => format!("the quantity {} end quantity", speech),
+        Language::Japanese => format!("かっこ{}かっことじ", speech),
+    }
+}
+
 /// most basic Basis type
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
 pub struct BasisLeaf {
@@ -227,6 +320,7 @@ pub struct BasisLeaf {
 pub enum BasisElement {
     Num,
     X,
+    Var(char), // variables other than x, treated as constants by single variable functions
     Inf,
 }
 
@@ -239,7 +333,19 @@ impl BasisLeaf {
         }
     }
 
-    /// creates basic INF BasisLeaf with given sign
+    /// creates basic variable BasisLeaf, x is always BasisElement::X
+    pub fn var(symbol: char) -> BasisLeaf {
+        BasisLeaf {
+            coefficient: Fraction::from(1),
+            element: if symbol == 'x' {
+                BasisElement::X
+            } else {
+                BasisElement::Var(symbol)
+            },
+        }
+    }
+
+    /// creates basic INF BasisLeaf with given sign, see `Basis::try_inf` for computed signs
     pub fn inf(i: i32) -> BasisLeaf {
         if !(i == 1 || i == -1) {
             panic!("INF must be -1 or 1 only")
@@ -254,23 +360,31 @@ impl BasisLeaf {
     pub fn like(&self, other: &BasisLeaf) -> bool {
         self.element == other.element
     }
+
+    /// symbol of variable BasisLeaf, x for non variables
+    fn symbol(&self) -> char {
+        match self.element {
+            BasisElement::Var(symbol) => symbol,
+            _ => 'x',
+        }
+    }
 }
 
 /// string representation of BasisLeaf, shows coefficient and element
 impl Display for BasisLeaf {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self.element {
-            BasisElement::X => write!(
+            BasisElement::X | BasisElement::Var(_) => write!(
                 f,
                 "{}{}",
                 if self.coefficient == 1 {
                     String::new()
-                } else if self.coefficient == 1 {
+                } else if self.coefficient == -1 {
                     "-".to_string()
                 } else {
                     self.coefficient.to_string()
                 },
-                "x"
+                self.symbol()
             ),
             BasisElement::Num => write!(f, "{}", self.coefficient),
             BasisElement::Inf => write!(
@@ -296,14 +410,15 @@ impl ToLatex for BasisLeaf {
                     format!("{coefficient}", coefficient = self.coefficient.to_latex())
                 }
             }
-            BasisElement::X => {
+            BasisElement::X | BasisElement::Var(_) => {
                 if self.coefficient.n == 1 && self.coefficient.d != 1 {
                     format!(
-                        "\\frac{{x}}{{{denominator}}}",
+                        "\\frac{{{symbol}}}{{{denominator}}}",
+                        symbol = self.symbol(),
                         denominator = self.coefficient.d
                     )
                 } else {
-                    format!("{}x", self.coefficient.to_latex())
+                    format!("{}{}", self.coefficient.to_latex(), self.symbol())
                 }
             }
             BasisElement::Inf => (if self.coefficient > 1 {
@@ -316,13 +431,89 @@ impl ToLatex for BasisLeaf {
     }
 }
 
+/// MathML representation of BasisLeaf, mirrors ToLatex
+impl ToMathML for BasisLeaf {
+    fn to_mathml_with(&self, flags: MathMLFlags) -> String {
+        match self.element {
+            BasisElement::Num => match self.coefficient {
+                Fraction { n: 1, d: 1 } => String::from("<mn>1</mn>"),
+                Fraction { n: -1, d: 1 } => String::from("<mrow><mo>-</mo><mn>1</mn></mrow>"),
+                _ => format!("<mrow>{}</mrow>", self.coefficient.to_mathml_with(flags)),
+            },
+            BasisElement::X | BasisElement::Var(_) => {
+                if self.coefficient.n == 1 && self.coefficient.d != 1 {
+                    format!(
+                        "<mfrac><mi>{symbol}</mi><mn>{denominator}</mn></mfrac>",
+                        symbol = self.symbol(),
+                        denominator = self.coefficient.d
+                    )
+                } else if self.coefficient == 1 {
+                    format!("<mi>{}</mi>", self.symbol())
+                } else {
+                    format!(
+                        "<mrow>{}<mi>{}</mi></mrow>",
+                        self.coefficient.to_mathml_with(flags),
+                        self.symbol()
+                    )
+                }
+            }
+            BasisElement::Inf => (if self.coefficient > 0 {
+                "<mi>∞</mi>"
+            } else {
+                "<mrow><mo>-</mo><mi>∞</mi></mrow>"
+            })
+            .to_string(),
+        }
+    }
+}
+
+/// spoken BasisLeaf, ie. 2 x, x over 2, minus infinity
+impl ToSpeech for BasisLeaf {
+    fn to_speech(&self, language: Language) -> String {
+        match self.element {
+            BasisElement::Num => self.coefficient.to_speech(language),
+            BasisElement::X | BasisElement::Var(_) => {
+                let symbol = match (self.symbol(), language) {
+                    ('x', Language::Japanese) => String::from("エックス"),
+                    ('y', Language::Japanese) => String::from("ワイ"),
+                    ('z', Language::Japanese) => String::from("ゼット"),
+                    (symbol, _) => symbol.to_string(),
+                };
+                let Fraction { n, d } = self.coefficient;
+                match language {
+                    _ if self.coefficient == 1 => symbol,
+                    Language::English if n == -1 && d == 1 => format!("minus {}", symbol),
+                    Language::English if d == 1 => format!("{} {}", n, symbol),
+                    Language::English => {
+                        let numerator = BasisLeaf {
+                            coefficient: Fraction::from(n),
+                            element: self.element.clone(),
+                        };
+                        format!("{} over {}", numerator.to_speech(language), d)
+                    }
+                    Language::Japanese if n == -1 && d == 1 => format!("マイナス{}", symbol),
+                    Language::Japanese => {
+                        format!("{}{}", self.coefficient.to_speech(language), symbol)
+                    }
+                }
+            }
+            BasisElement::Inf => match (self.coefficient > 0, language) {
+                (true, Language::English) => String::from("infinity"),
+                (false, Language::English) => String::from("minus infinity"),
+                (true, Language::Japanese) => String::from("無限大"),
+                (false, Language::Japanese) => String::from("マイナス無限大"),
+            },
+        }
+    }
+}
+
 /// used for complex bases with mathematical functions and operators
 #[derive(Clone, Debug, Hash, Eq)]
 pub struct BasisNode {
     pub coefficient: Fraction,
     pub operator: BasisOperator,
-    // Vec heap allocates, prevents recursive struct reference
-    pub operands: Vec<Basis>,
+    // interned and reference counted, clones share subtrees instead of copying them
+    pub operands: Operands,
 }
 
 /// string representation of BasisNode, shows custom format for unary operators, concats binary operators
@@ -417,16 +608,20 @@ impl ToLatex for BasisNode {
                         format!("{} {} {}", acc, self.operator.to_latex(), op.to_latex())
                     })
             ),
-            BasisOperator::Mult => format!(
-                "{}",
-                self.operands
-                    .iter()
-                    .fold(self.coefficient.to_latex(), |acc, op| format!(
-                        "{}{}",
-                        acc,
-                        op.to_latex()
-                    ))
-            ),
+            BasisOperator::Mult => {
+                // collect constant factors in front, ie. \sqrt{2}\log(3)x
+                let mut coefficient = Coefficient::from(self.coefficient);
+                let mut factors = vec![];
+                for op in self.operands.iter() {
+                    match Coefficient::from_basis(op)
+                        .and_then(|constant| coefficient.clone().checked_mul(constant))
+                    {
+                        Some(product) => coefficient = product,
+                        None => factors.push(op.to_latex()),
+                    }
+                }
+                format!("{}{}", coefficient.to_latex(), factors.concat())
+            }
             BasisOperator::Div => format!(
                 "{coefficient}\\frac{{{numerator}}}{{{denominator}}}",
                 coefficient = self.coefficient.to_latex(),
@@ -489,6 +684,206 @@ impl ToLatex for BasisNode {
     }
 }
 
+/// MathML representation of BasisNode, mirrors ToLatex
+impl ToMathML for BasisNode {
+    fn to_mathml_with(&self, flags: MathMLFlags) -> String {
+        let parenthesised = |mathml: String| format!("<mrow><mo>(</mo>{}<mo>)</mo></mrow>", mathml);
+        let mathml = match self.operator {
+            BasisOperator::E => format!(
+                "{coefficient}<msup>{operator}<mrow>{operands}</mrow></msup>",
+                coefficient = self.coefficient.to_mathml_with(flags),
+                operator = self.operator.to_mathml_with(flags),
+                operands = self.operands[0].to_mathml_with(flags)
+            ),
+            BasisOperator::Add | BasisOperator::Minus => self
+                .operands
+                .iter()
+                .map(|op| op.to_mathml_with(flags))
+                .collect::<Vec<String>>()
+                .join(&self.operator.to_mathml_with(flags)),
+            BasisOperator::Mult => {
+                // collect constant factors in front, ie. <msqrt><mn>2</mn></msqrt><mi>x</mi>
+                let mut coefficient = Coefficient::from(self.coefficient);
+                let mut factors = vec![];
+                for op in self.operands.iter() {
+                    match Coefficient::from_basis(op)
+                        .and_then(|constant| coefficient.clone().checked_mul(constant))
+                    {
+                        Some(product) => coefficient = product,
+                        None => factors.push(op.to_mathml_with(flags)),
+                    }
+                }
+                format!(
+                    "{}{}",
+                    coefficient.to_mathml_with(flags),
+                    factors.join(&self.operator.to_mathml_with(flags))
+                )
+            }
+            BasisOperator::Div => format!(
+                "{coefficient}<mfrac><mrow>{numerator}</mrow><mrow>{denominator}</mrow></mfrac>",
+                coefficient = self.coefficient.to_mathml_with(flags),
+                numerator = self.operands[0].to_mathml_with(flags),
+                denominator = self.operands[1].to_mathml_with(flags)
+            ),
+            BasisOperator::Pow(pow) => {
+                let flag = flags.fractional_exponents;
+                if pow == -1 {
+                    let numerator_coefficient = Fraction::from(self.coefficient.n);
+                    let denominator_coefficient = Fraction::from(self.coefficient.d);
+                    format!(
+                        "<mfrac><mrow>{numerator}</mrow><mrow>{denominator_coefficient}{denominator}</mrow></mfrac>",
+                        numerator = if self.coefficient.n.abs() == 1 {
+                            format!("{}<mn>1</mn>", numerator_coefficient.to_mathml_with(flags))
+                        } else {
+                            numerator_coefficient.to_mathml_with(flags)
+                        },
+                        denominator_coefficient = denominator_coefficient.to_mathml_with(flags),
+                        denominator = self.operands[0].to_mathml_with(flags)
+                    )
+                } else if !flag && pow.n == 1 {
+                    match pow.d {
+                        2 => format!(
+                            "{}<msqrt>{}</msqrt>",
+                            self.coefficient.to_mathml_with(flags),
+                            self.operands[0].to_mathml_with(flags)
+                        ),
+                        degree => format!(
+                            "{}<mroot><mrow>{}</mrow><mn>{}</mn></mroot>",
+                            self.coefficient.to_mathml_with(flags),
+                            self.operands[0].to_mathml_with(flags),
+                            degree
+                        ),
+                    }
+                } else {
+                    let base = match self.operands[0] {
+                        Basis::BasisLeaf(_) => self.operands[0].to_mathml_with(flags),
+                        Basis::BasisNode(_) => {
+                            parenthesised(self.operands[0].to_mathml_with(flags))
+                        }
+                    };
+                    format!(
+                        "{coefficient}<msup>{base}<mrow>{exponent}</mrow></msup>",
+                        coefficient = self.coefficient.to_mathml_with(flags),
+                        base = base,
+                        exponent = self.operator.to_mathml_with(flags)
+                    )
+                }
+            }
+            _ => format!(
+                "{coefficient}{function}<mo>&#x2061;</mo>{operand}",
+                coefficient = self.coefficient.to_mathml_with(flags),
+                function = self.operator.to_mathml_with(flags),
+                operand = parenthesised(self.operands[0].to_mathml_with(flags))
+            ),
+        };
+        format!("<mrow>{}</mrow>", mathml)
+    }
+}
+
+/// spoken BasisNode, ie. x squared times the natural log of x, エックスの2乗かけるエックスの自然対数
+impl ToSpeech for BasisNode {
+    fn to_speech(&self, language: Language) -> String {
+        let operand = || grouped_speech(&self.operands[0], language);
+        // any node raised to a power is grouped, ie. cos(x)^2 isn't read as cosine of x squared
+        let base = || match &self.operands[0] {
+            Basis::BasisNode(_) => group_speech(&self.operands[0], language),
+            leaf => leaf.to_speech(language),
+        };
+        let operator = self.operator.to_speech(language);
+        let speech = match (self.operator, language) {
+            (BasisOperator::Add | BasisOperator::Minus, _) => {
+                let minus = BasisOperator::Minus.to_speech(language);
+                let mut speech = self.operands[0].to_speech(language);
+                for op in self.operands.iter().skip(1) {
+                    let op_speech = op.to_speech(language);
+                    // x + -1 reads as x minus 1
+                    speech = match op_speech.strip_prefix(&minus) {
+                        Some(rest) if self.operator == BasisOperator::Add => {
+                            format!("{} {} {}", speech, minus, rest.trim_start())
+                        }
+                        _ => format!("{} {} {}", speech, operator, op_speech),
+                    };
+                }
+                if language == Language::Japanese {
+                    speech = speech.replace(' ', "");
+                }
+                return speech;
+            }
+            (BasisOperator::Mult, Language::English) => self
+                .operands
+                .iter()
+                .map(|op| grouped_speech(op, language))
+                .collect::<Vec<String>>()
+                .join(&format!(" {} ", operator)),
+            (BasisOperator::Mult, Language::Japanese) => self
+                .operands
+                .iter()
+                .map(|op| grouped_speech(op, language))
+                .collect::<Vec<String>>()
+                .join(&operator),
+            (BasisOperator::Div, Language::English) => format!(
+                "{} over {}",
+                operand(),
+                grouped_speech(&self.operands[1], language)
+            ),
+            (BasisOperator::Div, Language::Japanese) => format!(
+                "{}分の{}",
+                grouped_speech(&self.operands[1], language),
+                operand()
+            ),
+            (BasisOperator::Pow(pow), Language::English) => match (pow.n, pow.d) {
+                (2, 1) => format!("{} squared", base()),
+                (3, 1) => format!("{} cubed", base()),
+                (-1, 1) => format!("1 over {}", operand()),
+                (1, 2) => format!("the square root of {}", operand()),
+                (1, 3) => format!("the cube root of {}", operand()),
+                (1, d) => format!("the {}th root of {}", d, operand()),
+                _ => format!("{} {}", base(), operator),
+            },
+            (BasisOperator::Pow(pow), Language::Japanese) => match (pow.n, pow.d) {
+                (-1, 1) => format!("{}分の1", operand()),
+                (1, 2) => format!("ルート{}", operand()),
+                (1, d) => format!("{}の{}乗根", operand(), d),
+                _ => format!("{}の{}", base(), operator),
+            },
+            (BasisOperator::E, Language::English) => match self.operands[0] {
+                Basis::BasisLeaf(_) => format!("e to the {}", operand()),
+                Basis::BasisNode(_) => format!("e to the power of {}", operand()),
+            },
+            // e^(x^2) is grouped so it doesn't read as イーのエックスの2乗乗
+            (BasisOperator::E, Language::Japanese) => match &self.operands[0] {
+                op @ Basis::BasisNode(BasisNode {
+                    operator: BasisOperator::Pow(_),
+                    ..
+                }) => format!("イーの{}乗", group_speech(op, language)),
+                _ => format!("イーの{}乗", operand()),
+            },
+            (
+                BasisOperator::Cos | BasisOperator::Sin | BasisOperator::Acos | BasisOperator::Asin,
+                Language::English,
+            ) => format!("{} of {}", operator, operand()),
+            (
+                BasisOperator::Cos | BasisOperator::Sin | BasisOperator::Acos | BasisOperator::Asin,
+                Language::Japanese,
+            ) => format!("{}{}", operator, operand()),
+            (_, Language::English) => format!("{} of {}", operator, operand()),
+            (_, Language::Japanese) => format!("{}の{}", operand(), operator),
+        };
+
+        match (self.coefficient, language) {
+            (Fraction { n: 1, d: 1 }, _) => speech,
+            (Fraction { n: -1, d: 1 }, Language::English) => format!("minus {}", speech),
+            (Fraction { n: -1, d: 1 }, Language::Japanese) => format!("マイナス{}", speech),
+            (coefficient, Language::English) => {
+                format!("{} times {}", coefficient.to_speech(language), speech)
+            }
+            (coefficient, Language::Japanese) => {
+                format!("{}かける{}", coefficient.to_speech(language), speech)
+            }
+        }
+    }
+}
+
 /// custom equality for Basis, compares non-sortable Basis structs string-wise
 impl PartialEq for BasisNode {
     fn eq(&self, other: &BasisNode) -> bool {
@@ -498,6 +893,10 @@ impl PartialEq for BasisNode {
         if self.operator != other.operator {
             return false;
         }
+        // same interned operands, skip structural comparison
+        if self.operands.ptr_eq(&other.operands) {
+            return true;
+        }
         if self.operands.len() != other.operands.len() {
             return false;
         }
@@ -590,3 +989,72 @@ impl ToLatex for BasisOperator {
         format!("{}", latex)
     }
 }
+
+/// MathML representation of BasisOperator, the exponent for Pow and the function name otherwise
+impl ToMathML for BasisOperator {
+    fn to_mathml_with(&self, flags: MathMLFlags) -> String {
+        match self {
+            BasisOperator::Add => "<mo>+</mo>".to_string(),
+            BasisOperator::Minus => "<mo>-</mo>".to_string(),
+            // invisible times
+            BasisOperator::Mult => "<mo>&#x2062;</mo>".to_string(),
+            BasisOperator::Div => "<mo>/</mo>".to_string(),
+            BasisOperator::Pow(Fraction { n, d: 1 }) if *n < 0 => {
+                format!("<mo>-</mo><mn>{}</mn>", -n)
+            }
+            BasisOperator::Pow(Fraction { n, d: 1 }) => format!("<mn>{}</mn>", n),
+            BasisOperator::Pow(pow) => pow.to_mathml_with(flags),
+            BasisOperator::E => "<mi>e</mi>".to_string(),
+            BasisOperator::Log => {
+                let flag = flags.ln_for_log;
+                (if flag { "<mi>ln</mi>" } else { "<mi>log</mi>" }).to_string()
+            }
+            BasisOperator::Cos => "<mi>cos</mi>".to_string(),
+            BasisOperator::Sin => "<mi>sin</mi>".to_string(),
+            BasisOperator::Acos => "<mi>arccos</mi>".to_string(),
+            BasisOperator::Asin => "<mi>arcsin</mi>".to_string(),
+            BasisOperator::Inv => {
+                "<msup><mi>f</mi><mrow><mo>-</mo><mn>1</mn></mrow></msup>".to_string()
+            }
+            BasisOperator::Int => "<mo>∫</mo>".to_string(),
+        }
+    }
+}
+
+/// spoken name of BasisOperator, the exponent for Pow, ie. to the power of 4
+impl ToSpeech for BasisOperator {
+    fn to_speech(&self, language: Language) -> String {
+        match (self, language) {
+            (BasisOperator::Add, Language::English) => String::from("plus"),
+            (BasisOperator::Add, Language::Japanese) => String::from("プラス"),
+            (BasisOperator::Minus, Language::English) => String::from("minus"),
+            (BasisOperator::Minus, Language::Japanese) => String::from("マイナス"),
+            (BasisOperator::Mult, Language::English) => String::from("times"),
+            (BasisOperator::Mult, Language::Japanese) => String::from("かける"),
+            (BasisOperator::Div, Language::English) => String::from("over"),
+            (BasisOperator::Div, Language::Japanese) => String::from("分の"),
+            (BasisOperator::Pow(pow), Language::English) => {
+                format!("to the power of {}", pow.to_speech(language))
+            }
+            (BasisOperator::Pow(pow), Language::Japanese) => {
+                format!("{}乗", pow.to_speech(language))
+            }
+            (BasisOperator::E, Language::English) => String::from("e"),
+            (BasisOperator::E, Language::Japanese) => String::from("イー"),
+            (BasisOperator::Log, Language::English) => String::from("the natural log"),
+            (BasisOperator::Log, Language::Japanese) => String::from("自然対数"),
+            (BasisOperator::Cos, Language::English) => String::from("cosine"),
+            (BasisOperator::Cos, Language::Japanese) => String::from("コサイン"),
+            (BasisOperator::Sin, Language::English) => String::from("sine"),
+            (BasisOperator::Sin, Language::Japanese) => String::from("サイン"),
+            (BasisOperator::Acos, Language::English) => String::from("arccosine"),
+            (BasisOperator::Acos, Language::Japanese) => String::from("アークコサイン"),
+            (BasisOperator::Asin, Language::English) => String::from("arcsine"),
+            (BasisOperator::Asin, Language::Japanese) => String::from("アークサイン"),
+            (BasisOperator::Inv, Language::English) => String::from("the inverse"),
+            (BasisOperator::Inv, Language::Japanese) => String::from("逆関数"),
+            (BasisOperator::Int, Language::English) => String::from("the integral"),
+            (BasisOperator::Int, Language::Japanese) => String::from("積分"),
+        }
+    }
+}
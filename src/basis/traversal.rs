@@ -0,0 +1,181 @@
+// std imports
+use std::collections::BTreeSet;
+use std::vec::IntoIter;
+// local imports
+use super::builders::rebuild_node;
+use super::structs::*;
+
+/// visits every Basis of a tree, `enter` is called pre-order and `leave` post-order
+pub trait BasisVisitor {
+    /// called before the operands of `basis` are visited, return false to skip its operands
+    fn enter(&mut self, _basis: &Basis) -> bool {
+        true
+    }
+    /// called after the operands of `basis` are visited
+    fn leave(&mut self, _basis: &Basis) {}
+}
+
+/// folds a Basis tree bottom-up into a single value of type T
+pub trait BasisFolder<T> {
+    /// combines `basis` with the already folded values of its operands (empty for BasisLeafs)
+    fn fold(&mut self, basis: &Basis, operands: Vec<T>) -> T;
+}
+
+/// allows closures to be used as folders
+impl<T, F> BasisFolder<T> for F
+where
+    F: FnMut(&Basis, Vec<T>) -> T,
+{
+    fn fold(&mut self, basis: &Basis, operands: Vec<T>) -> T {
+        self(basis, operands)
+    }
+}
+
+/// pre-order iterator over a Basis tree, parents before operands
+pub struct PreOrder<'a> {
+    stack: Vec<&'a Basis>,
+}
+
+impl<'a> Iterator for PreOrder<'a> {
+    type Item = &'a Basis;
+
+    fn next(&mut self) -> Option<&'a Basis> {
+        let basis = self.stack.pop()?;
+        if let Basis::BasisNode(BasisNode { operands, .. }) = basis {
+            self.stack.extend(operands.iter().rev());
+        }
+        Some(basis)
+    }
+}
+
+impl Basis {
+    /// walks tree with given visitor
+    pub fn walk<V: BasisVisitor>(&self, visitor: &mut V) {
+        if visitor.enter(self) {
+            if let Basis::BasisNode(BasisNode { operands, .. }) = self {
+                operands.iter().for_each(|op| op.walk(visitor));
+            }
+        }
+        visitor.leave(self);
+    }
+
+    /// folds tree bottom-up with given folder
+    pub fn fold<T, F: BasisFolder<T>>(&self, folder: &mut F) -> T {
+        let operands = match self {
+            Basis::BasisLeaf(_) => vec![],
+            Basis::BasisNode(BasisNode { operands, .. }) => {
+                operands.iter().map(|op| op.fold(folder)).collect()
+            }
+        };
+        folder.fold(self, operands)
+    }
+
+    /// iterates tree with parents before their operands
+    pub fn pre_order(&self) -> PreOrder<'_> {
+        PreOrder { stack: vec![self] }
+    }
+
+    /// iterates tree with operands before their parents
+    pub fn post_order(&self) -> IntoIter<&Basis> {
+        let mut out = vec![];
+        let mut stack = vec![self];
+        // reversed pre-order with operands pushed left to right
+        while let Some(basis) = stack.pop() {
+            out.push(basis);
+            if let Basis::BasisNode(BasisNode { operands, .. }) = basis {
+                stack.extend(operands.iter());
+            }
+        }
+        out.reverse();
+        out.into_iter()
+    }
+
+    /// rebuilds tree bottom-up, applying `f` to every Basis after its operands have been mapped
+    /// nodes `f` leaves unchanged are rebuilt through the builders if their operands changed, so the result is simplified
+    pub fn map_nodes<F: Fn(&Basis) -> Basis>(&self, f: &F) -> Basis {
+        match self {
+            Basis::BasisLeaf(_) => f(self),
+            Basis::BasisNode(basis_node) => {
+                let operands = basis_node
+                    .operands
+                    .iter()
+                    .map(|op| op.map_nodes(f))
+                    .collect::<Vec<Basis>>();
+                // `f` sees the node as is, simplifying first could turn it into a Basis `f` maps again
+                let node = Basis::BasisNode(BasisNode {
+                    operands: operands.clone().into(),
+                    ..basis_node.clone()
+                });
+                let mapped = f(&node);
+                if mapped == node {
+                    rebuilt(basis_node, operands)
+                } else {
+                    mapped
+                }
+            }
+        }
+    }
+
+    /// checks if any Basis in the tree matches `predicate`
+    pub fn contains<P: Fn(&Basis) -> bool>(&self, predicate: P) -> bool {
+        self.pre_order().any(predicate)
+    }
+
+    /// finds every Basis in the tree matching `predicate`, in pre-order
+    pub fn find_all<P: Fn(&Basis) -> bool>(&self, predicate: P) -> Vec<&Basis> {
+        self.pre_order().filter(|basis| predicate(basis)).collect()
+    }
+
+    /// checks if Basis depends on the variable `symbol`, Int and Inv nodes are functions of x
+    pub fn depends_on(&self, symbol: char) -> bool {
+        self.contains(|basis| {
+            basis.is_var(symbol)
+                || (symbol == 'x'
+                    && (basis.is_node(BasisOperator::Int) || basis.is_node(BasisOperator::Inv)))
+        })
+    }
+
+    /// every variable symbol in the tree, x included
+    pub fn variables(&self) -> BTreeSet<char> {
+        self.pre_order()
+            .filter_map(|basis| match basis {
+                Basis::BasisLeaf(BasisLeaf {
+                    element: BasisElement::X,
+                    ..
+                }) => Some('x'),
+                Basis::BasisLeaf(BasisLeaf {
+                    element: BasisElement::Var(symbol),
+                    ..
+                }) => Some(*symbol),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// replaces every subtree equal to `pattern` with `replacement`, outermost matches first
+    /// nodes with replaced operands are rebuilt through the builders, so the result is simplified
+    pub fn substitute(&self, pattern: &Basis, replacement: &Basis) -> Basis {
+        if self == pattern {
+            return replacement.clone();
+        }
+        match self {
+            Basis::BasisLeaf(_) => self.clone(),
+            Basis::BasisNode(basis_node) => {
+                let operands = basis_node
+                    .operands
+                    .iter()
+                    .map(|op| op.substitute(pattern, replacement))
+                    .collect::<Vec<Basis>>();
+                rebuilt(basis_node, operands)
+            }
+        }
+    }
+}
+
+/// node over given operands, only goes through the builders if an operand changed
+fn rebuilt(basis_node: &BasisNode, operands: Vec<Basis>) -> Basis {
+    if operands[..] == basis_node.operands[..] {
+        return Basis::BasisNode(basis_node.clone());
+    }
+    rebuild_node(basis_node, operands)
+}
@@ -103,6 +103,7 @@ impl Canvas {
             anim_controller: AnimController {
                 anim_items: HashMap::default(),
                 anim_chain: HashMap::default(),
+                pending_deal: Vec::default(),
                 render_animation_frame_handle: request_animation_frame(on_animation_frame),
             },
         }
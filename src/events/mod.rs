@@ -1,3 +1,3 @@
 pub mod event_listeners;
-mod mousedown_handler;
+pub mod mousedown_handler;
 mod mousemove_handler;
@@ -1,14 +1,24 @@
-// std imports
-use std::cmp::min;
+// wasm-bindgen imports
+use gloo::timers::callback::Timeout;
 // outer crate imports
-use crate::basis::structs::*;
+use crate::game::ai::{self, AI_PLAYER};
 use crate::game::cards::*;
-use crate::game::{field::FieldBasis, flags::ALLOW_LINEAR_DEPENDENCE, structs::*};
+use crate::game::engine::{Action, GameEvent, Operand, RuleError};
+use crate::game::flags::AI_DIFFICULTY;
+use crate::game::structs::*;
+use crate::math::error::MathError;
 use crate::render::anim;
 use crate::render::render;
 use crate::render::util::RenderId;
+#[cfg(feature = "serde")]
+use crate::storage;
+// util imports
+use crate::util::js_log;
 // root imports
-use crate::GAME;
+use crate::{GAME, MENU};
+
+/// delay before the computer plays, so its turn can be followed
+const AI_DELAY_MS: u32 = 800;
 
 /// delegates event handling based on turn num
 pub fn handle_mousedown(str_id: String) {
@@ -16,6 +26,11 @@ pub fn handle_mousedown(str_id: String) {
     let turn = &game.turn;
     let id = RenderId::from(str_id);
 
+    // clicks wait for the computer to finish its turn
+    if is_ai_turn(game) {
+        return;
+    }
+
     match turn {
         Turn { number: n, .. } if n % 2 == 0 => {
             // even-number turn, player 1
@@ -33,11 +48,6 @@ pub fn handle_mousedown(str_id: String) {
 pub fn branch_turn_phase(id: RenderId, player_num: u32) {
     let game = unsafe { GAME.as_mut().unwrap() };
     let turn = &game.turn;
-    let player = if player_num == 1 {
-        &game.player_1
-    } else {
-        &game.player_2
-    };
 
     let (id_key, id_val) = id.key_val();
 
@@ -49,320 +59,148 @@ pub fn branch_turn_phase(id: RenderId, player_num: u32) {
     }
 
     match turn.phase {
-        TurnPhase::IDLE if id_key == format!("p{}", player_num) => {
-            game.active.selected.push(id);
-            idle_turn_phase(player[id_val]);
-        }
+        TurnPhase::IDLE if id_key == format!("p{}", player_num) => idle_turn_phase(id),
+        // deck passes the turn when no card can be played
+        TurnPhase::IDLE if id == RenderId::Deck => play(Action::Pass),
         TurnPhase::SELECT(select_operator) => select_turn_phase(select_operator, (id_key, id_val)),
         TurnPhase::FIELD_SELECT(field_operator) if id_key == "f" => {
             field_select_phase(field_operator, (id_key, id_val))
         }
-        TurnPhase::MULTISELECT(multi_operator) => {
-            multi_select_phase(multi_operator, id, player_num)
-        }
+        TurnPhase::MULTISELECT(multi_operator) => multi_select_phase(multi_operator, id),
         _ => {} // js_log!("Turn Phase Error: received {} on turn {:?}", id, turn),
     }
 }
 
-/// handles idle turn phase, where player can select a card
-fn idle_turn_phase(card: Card) {
+/// handles idle turn phase, where player can select a card that starts a legal action
+fn idle_turn_phase(id: RenderId) {
     let game = unsafe { GAME.as_mut().unwrap() };
+    let card_index = id.key_val().1;
+    let card = game.get_current_player()[card_index];
 
-    // match against current card in player hand
-    match card {
-        Card::BasisCard(basis_card) => {
-            // allow play if empty slot
-            if game.field.basis.iter().any(|b| b.basis.is_none()) {
-                next_phase(TurnPhase::SELECT(Card::BasisCard(basis_card)));
-            }
-        }
-        Card::DerivativeCard(derivative_card) => {
-            if matches!(
-                derivative_card,
-                DerivativeCard::Derivative | DerivativeCard::Integral
-            ) {
-                next_phase(TurnPhase::SELECT(card));
-            } else if matches!(derivative_card, DerivativeCard::Nabla)
-            // prevent player from playing laplacian on first turn for each player
-                || (game.turn.number >= 2 && matches!(derivative_card, DerivativeCard::Laplacian))
-            {
-                // field select
-                next_phase(TurnPhase::FIELD_SELECT(Card::DerivativeCard(
-                    derivative_card,
-                )));
-            }
-        }
-        // multiselect
-        Card::AlgebraicCard(algebraic_card)
-            if matches!(algebraic_card, AlgebraicCard::Div | AlgebraicCard::Mult) =>
-        {
-            next_phase(TurnPhase::MULTISELECT(Card::AlgebraicCard(algebraic_card)));
-        }
-        // select
-        card => {
-            next_phase(TurnPhase::SELECT(card));
-        }
-    }
+    // any Mult/Div that can be played can be played on 2 operands
+    let phase = match game
+        .playable_actions(2)
+        .iter()
+        .find(|action| action.card() == Some(card_index))
+    {
+        Some(Action::FieldOperator { .. }) => TurnPhase::FIELD_SELECT(card),
+        Some(Action::MultiCombine { .. }) => TurnPhase::MULTISELECT(card),
+        Some(_) => TurnPhase::SELECT(card),
+        None => return,
+    };
+    game.active.selected.push(id);
+    next_phase(phase);
 }
 
 /// handles select turn phase, player can choose single target of selected card
 fn select_turn_phase(select_operator: Card, (id_key, id_val): (String, usize)) {
-    let game = unsafe { GAME.as_mut().unwrap() };
-
-    match select_operator {
+    if id_key != "f" {
+        return;
+    }
+    let card = selected_card();
+    play(match select_operator {
         // play basis from hand if empty slot
-        Card::BasisCard(basis_card) => {
-            if id_key == "f"
-                && game.field[id_val].basis.is_none()
-                && !matches!(basis_card, BasisCard::Zero)
-            {
-                game.field[id_val] = FieldBasis::new(&Basis::from(basis_card));
-                end_turn();
-            }
-        }
+        Card::BasisCard(_) => Action::PlayBasis { card, slot: id_val },
         // play function from hand onto field
-        operator_card => {
-            if id_key == "f" {
-                if matches!(
-                    operator_card,
-                    Card::DerivativeCard(DerivativeCard::Derivative | DerivativeCard::Integral)
-                ) {
-                    handle_derivative_card(operator_card, id_val);
-                } else if matches!(operator_card, Card::AlgebraicCard(AlgebraicCard::Inverse)) {
-                    let result_basis =
-                        apply_card(&operator_card)(game.field[id_val].basis.as_ref().unwrap());
-                    game.field.inverse(id_val, Some(result_basis))
-                } else {
-                    let selected_field_basis = &mut game.field[id_val];
-                    let result_basis =
-                        apply_card(&operator_card)(selected_field_basis.basis.as_ref().unwrap());
-                    if result_basis.is_num(0) || result_basis.is_inf(1) || result_basis.is_inf(-1) {
-                        game.field[id_val] = FieldBasis::none();
-                    } else {
-                        game.field[id_val] = FieldBasis::new(&result_basis);
-                    }
-                }
-                end_turn();
-            }
-        }
-    }
+        _ => Action::ApplyOperator { card, slot: id_val },
+    });
 }
 
 /// handles field select turn phase, player can choose side of field to target with selected card
-fn field_select_phase(field_operator: Card, (_id_key, id_val): (String, usize)) {
-    let card_range = if id_val < 3 { 0..3 } else { 3..6 };
-    // for each basis on one half of the field
-    for i in card_range {
-        handle_derivative_card(field_operator, i);
-    }
-    end_turn();
-}
-
-/// manages derivatives of FieldBasis, looks up history of derivatives/integrals and applies if possible
-fn handle_derivative_card(card: Card, i: usize) {
-    let game = unsafe { GAME.as_mut().unwrap() };
-
-    let is_laplacian = matches!(card, Card::DerivativeCard(DerivativeCard::Laplacian));
-    let is_integral = matches!(card, Card::DerivativeCard(DerivativeCard::Integral));
-    let is_derivative = matches!(
-        card,
-        Card::DerivativeCard(DerivativeCard::Derivative | DerivativeCard::Nabla)
-    );
-
-    let selected_field_basis = &game.field[i];
-    if selected_field_basis.basis.is_none() {
-        return;
-    }
-
-    // shortcut if already in history
-    if selected_field_basis.has_value(&card) {
-        if is_derivative || is_laplacian {
-            game.field.derivative(i, None);
-        } else if is_integral {
-            game.field.integral(i, None);
-        }
-        if is_laplacian {
-            game.field.derivative(i, None);
-        }
-    } else {
-        // calculate derivative/integral
-        let result_basis = apply_card(&card)(selected_field_basis.basis.as_ref().unwrap());
-        if result_basis.is_num(0) {
-            game.field[i] = FieldBasis::none();
-            return;
-        } else {
-            if is_derivative || is_laplacian {
-                game.field.derivative(i, Some(result_basis.clone()));
-            } else if is_integral {
-                game.field.integral(i, Some(result_basis.clone()));
-            }
-        }
-        // calculate second derivative if laplacian
-        if is_laplacian {
-            let second_derivative = apply_card(&card)(&result_basis);
-            if second_derivative.is_num(0) {
-                game.field[i] = FieldBasis::none();
-                return;
-            }
-            game.field.derivative(i, Some(second_derivative));
-        }
-    }
+fn field_select_phase(_field_operator: Card, (_id_key, id_val): (String, usize)) {
+    play(Action::FieldOperator {
+        card: selected_card(),
+        half: id_val / 3,
+    });
 }
 
 /// handles multiselect turn phase, player can choose multiple targets of selected operator (Mult/Div)
-fn multi_select_phase(multi_operator: Card, id: RenderId, player_num: u32) {
+fn multi_select_phase(_multi_operator: Card, id: RenderId) {
     let game = unsafe { GAME.as_mut().unwrap() };
-    let player = if &game.turn.number % 2 == 0 {
-        &mut game.player_1
-    } else {
-        &mut game.player_2
-    };
-    let field = &mut game.field;
-    let selected = &mut game.active.selected;
     let (id_key, id_val) = id.key_val();
 
-    // add selected field Basis to list of active selections
-    if id_key == "f"
-        || (id_key == format!("p{}", player_num) && matches!(player[id_val], Card::BasisCard(_)))
-    {
-        selected.push(id);
+    // add field Basis or basis card to list of active selections, if it is one of the next operands
+    // targets leave out operands already selected, empty slots and anything that can't complete the card
+    if game.active.targets.contains(&id) {
+        game.active.selected.push(id);
+        update_targets();
         render::draw();
-        // console::log_1(&JsValue::from(format!("added to multiselect: {}", id)));
     }
 
-    let has_at_least_1_field_basis = selected.iter().find(|sel_id| sel_id.is_field()).is_some();
-    let has_at_least_2_basis = selected.len() >= 3; // add one for operator
-    let has_zero_with_many = selected.len() != 3
-        && selected
-            .iter()
-            .find(|sel_id| {
-                sel_id.is_player() // must be a player card
-                    && matches!( // corresponding player card is zero
-                        player[sel_id.key_val().1],
-                        Card::BasisCard(BasisCard::Zero)
-                    )
-            })
-            .is_some();
-
-    if (id_key == "x" && id_val == 1)
-        && has_at_least_1_field_basis
-        && !has_zero_with_many
-        && has_at_least_2_basis
-    {
-        let result_basis = apply_multi_card(
-            &multi_operator,
-            selected
-                .iter()
-                .filter_map(|sel_id| {
-                    let (sel_key, sel_val) = sel_id.key_val();
-
-                    if sel_key == "f" {
-                        return Some(field[sel_val].basis.as_ref().unwrap().clone());
-                    } else if sel_key == format!("p{}", player_num) {
-                        if let Card::AlgebraicCard(_operator) = player[sel_val] {
-                            // skip the mult_operator
-                            return None;
-                        } else if let Card::BasisCard(basis_card) = player[sel_val] {
-                            return Some(Basis::from(basis_card));
-                        }
-                    }
-                    panic!("invalid card selected! {}", sel_id);
-                })
-                .collect::<Vec<Basis>>(),
-        );
-        // get references to all selected cards
-        let used_field_bases = selected
-            .iter()
-            .filter_map(|sel_id| {
-                let (sel_key, sel_val) = sel_id.key_val();
-                if sel_key == "f" {
-                    return Some(sel_val);
-                }
-                None
-            })
-            .collect::<Vec<usize>>();
-        used_field_bases // clear used field bases
-            .iter()
-            .for_each(|field_index| field[*field_index] = FieldBasis::none());
-        if result_basis.is_num(0) {
-            field[used_field_bases[0]] = FieldBasis::none();
-        } else {
-            field[used_field_bases[0]] = FieldBasis::new(&result_basis); // assign result basis to any newly empty field
-        }
-        end_turn();
+    if id_key == "x" && id_val == 1 {
+        play(Action::MultiCombine {
+            card: selected_card(),
+            operands: selected_operands(),
+        });
     }
 }
 
-/// performs cleanup tasks after turn is over
-fn end_turn() {
-    let game = unsafe { GAME.as_mut().unwrap() };
-    // get vector indices of cards used by player this turn
-    let mut selected_indices = game
-        .active
-        .selected
+/// Mult/Div operands selected so far, the first selection is the operator itself
+fn selected_operands() -> Vec<Operand> {
+    let game = unsafe { GAME.as_ref().unwrap() };
+    game.active.selected[1..]
         .iter()
-        .filter(|card| card.is_player())
-        .map(|card| card.key_val().1)
-        .collect::<Vec<usize>>();
-    selected_indices.sort();
-    selected_indices.reverse();
+        .map(|sel_id| match sel_id.key_val() {
+            (sel_key, sel_val) if sel_key == "f" => Operand::Field(sel_val),
+            (_, sel_val) => Operand::Hand(sel_val),
+        })
+        .collect()
+}
 
-    let player_num = game.get_current_player_num();
-    let player = if player_num == 1 {
-        &mut game.player_1
-    } else {
-        &mut game.player_2
-    };
-    // remove used cards
-    for i in selected_indices.iter() {
-        let used_card = player.remove(*i);
-        game.graveyard.push(used_card);
-    }
+/// hand index of the card that started the current turn phase
+fn selected_card() -> usize {
+    let game = unsafe { GAME.as_ref().unwrap() };
+    game.active.selected[0].key_val().1
+}
 
-    let deck = &mut game.deck;
-    // replenish from deck if possible
-    anim::animate_deal_cards(
-        (player.len()..min(deck.len(), 7))
-            .map(|i| {
-                RenderId::from(format!(
-                    "p{player_num}={val}",
-                    player_num = player_num,
-                    val = i
-                ))
-            })
-            .collect::<Vec<RenderId>>(),
-    );
+/// applies action to game, or cancels turn if the CAS fails
+/// other rule errors are ignored so the player can pick another target, the computer picks again
+fn play(action: Action) {
+    let game = unsafe { GAME.as_mut().unwrap() };
+    match game.apply(action) {
+        Ok(events) => handle_events(events),
+        Err(RuleError::Math(error)) => cancel_turn(error),
+        Err(_) => schedule_ai_turn(),
+    }
+}
 
-    let flag = unsafe { ALLOW_LINEAR_DEPENDENCE };
-    if !flag {
-        let field = &mut game.field;
-        // TODO: animate ?
-        // clear field bases that are linearly dependent
-        for i in 0..=2 {
-            for j in i + 1..=2 {
-                if field[i].basis.is_some()
-                    && field[j].basis.is_some()
-                    && field[i].basis.as_ref().unwrap().with_coefficient(1)
-                        == field[j].basis.as_ref().unwrap().with_coefficient(1)
-                {
-                    field[j] = FieldBasis::none();
-                }
+/// renders the results of an action
+fn handle_events(events: Vec<GameEvent>) {
+    for event in events {
+        match event {
+            GameEvent::BasisSkipped { error, .. } => {
+                js_log!("{}", error);
             }
-        }
-        for i in 3..6 {
-            for j in i + 1..6 {
-                if field[i].basis.is_some()
-                    && field[j].basis.is_some()
-                    && field[i].basis.as_ref().unwrap().with_coefficient(1)
-                        == field[j].basis.as_ref().unwrap().with_coefficient(1)
-                {
-                    field[j] = FieldBasis::none();
+            GameEvent::CardsDealt { player, slots } => anim::animate_deal_cards(
+                slots
+                    .iter()
+                    .map(|i| RenderId::from(format!("p{}={}", player, i)))
+                    .collect::<Vec<RenderId>>(),
+            ),
+            GameEvent::TurnEnded { .. } => {
+                update_targets();
+                render::draw();
+                // autosave for Continue, removed again by game_over
+                #[cfg(feature = "serde")]
+                if let Err(error) = storage::save_game(unsafe { GAME.as_ref().unwrap() }, None) {
+                    js_log!("{}", error);
                 }
+                schedule_ai_turn();
             }
+            GameEvent::GameOver { winner } => unsafe { MENU.as_ref().unwrap() }.game_over(winner),
+            GameEvent::CardsPlayed { .. } | GameEvent::FieldChanged { .. } => {}
         }
     }
+}
 
-    next_turn();
+/// logs CAS error and returns to idle without using the selected cards
+/// the computer picks again rather than waiting for a click
+fn cancel_turn(error: MathError) {
+    let game = unsafe { GAME.as_mut().unwrap() };
+    js_log!("{}", error);
+    game.active.clear();
+    next_phase(TurnPhase::IDLE);
+    schedule_ai_turn();
 }
 
 /// shifts to next turn phase with given selected card
@@ -373,27 +211,88 @@ fn next_phase(phase: TurnPhase) {
         number: game.turn.number,
         phase: phase,
     };
+    update_targets();
     render::draw();
 }
 
-/// finalises turn and increments turn, checking if game is in terminal state
-pub fn next_turn() {
+/// highlights the items the current player can click next, from the legal actions
+pub fn update_targets() {
     let game = unsafe { GAME.as_mut().unwrap() };
+    // nothing to click while the computer plays
+    if is_ai_turn(game) {
+        game.active.targets = Vec::default();
+        return;
+    }
+    let player_num = game.get_current_player_num();
+    let hand_id = |i: usize| RenderId::from(format!("p{}={}", player_num, i));
+    let field_id = |i: usize| RenderId::from(format!("f={}", i));
 
-    // console::log_1(&JsValue::from(format!("entering turn: {}", game.turn.number + 1)));
-    game.turn = Turn {
-        number: game.turn.number + 1,
-        phase: TurnPhase::IDLE,
+    let targets = match game.turn.phase {
+        // any Mult/Div that can be played can be played on 2 operands, listing more is exponential
+        TurnPhase::IDLE => {
+            let mut targets = game
+                .playable_actions(2)
+                .iter()
+                .filter_map(|action| action.card().map(hand_id))
+                .collect::<Vec<RenderId>>();
+            // runs every card in the CAS when the turn starts, later events find it cached for the position
+            if game.check(&Action::Pass).is_ok() {
+                targets.push(RenderId::Deck);
+            }
+            targets
+        }
+        // selection is cleared by a resumed game or a cancelled card
+        _ if game.active.selected.is_empty() => Vec::default(),
+        // operands that keep the selection playable, rather than every Mult/Div that completes it
+        TurnPhase::MULTISELECT(_) => game
+            .next_operands(selected_card(), &selected_operands())
+            .iter()
+            .map(|operand| match *operand {
+                Operand::Field(slot) => field_id(slot),
+                Operand::Hand(i) => hand_id(i),
+            })
+            .collect(),
+        _ => {
+            let card = selected_card();
+            game.playable_actions(2)
+                .iter()
+                .filter(|action| action.card() == Some(card))
+                .flat_map(|action| match action {
+                    Action::PlayBasis { slot, .. } | Action::ApplyOperator { slot, .. } => {
+                        vec![field_id(*slot)]
+                    }
+                    Action::FieldOperator { half, .. } => {
+                        (half * 3..half * 3 + 3).map(field_id).collect()
+                    }
+                    Action::MultiCombine { .. } | Action::Pass => vec![],
+                })
+                .collect()
+        }
     };
-    game.active.clear();
-    render::draw();
+    game.active.targets = targets;
+}
 
-    let field = game.field.basis.iter();
-    if field.clone().take(3).all(|f| f.basis.is_none()) {
-        // player 1 wins
-        game.game_over(1);
-    } else if field.clone().skip(3).all(|f| f.basis.is_none()) {
-        // player 2 wins
-        game.game_over(2);
+/// checks if the computer is to play in Play vs AI
+fn is_ai_turn(game: &Game) -> bool {
+    game.state == GameState::PLAYAI
+        && game.get_current_player_num() == AI_PLAYER
+        && game.winner().is_none()
+}
+
+/// plays the computer's turn after a short delay, if it is to play
+pub fn schedule_ai_turn() {
+    if !is_ai_turn(unsafe { GAME.as_ref().unwrap() }) {
+        return;
     }
+    Timeout::new(AI_DELAY_MS, || {
+        let game = unsafe { GAME.as_ref().unwrap() };
+        // player may have left for the menu while waiting
+        if !is_ai_turn(game) {
+            return;
+        }
+        if let Some(action) = ai::choose_action(game, unsafe { AI_DIFFICULTY }) {
+            play(action);
+        }
+    })
+    .forget();
 }
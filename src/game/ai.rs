@@ -0,0 +1,420 @@
+// std imports
+use std::cmp::{max, min};
+use std::collections::HashSet;
+use std::ops::Range;
+// outer crate imports
+use crate::basis::structs::*;
+use crate::math::budget::{budget, set_budget, Budget};
+use crate::math::cache::{cached_derivative, cached_limit, cached_logarithm};
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+// local imports
+use super::cards::*;
+use super::engine::{clears_slot, Action, Operand};
+use super::structs::*;
+
+/// player controlled by the computer in Play vs AI
+pub const AI_PLAYER: u32 = 2;
+/// score of a won game, larger than any heuristic evaluation
+pub const WIN_SCORE: i32 = 100_000;
+/// most operands of Mult/Div the search considers, more only grow the search exponentially
+const MAX_SEARCH_OPERANDS: usize = 3;
+/// most actions searched from each position, best first by evaluation
+const BEAM_WIDTH: usize = 10;
+/// most actions tried in the CAS for one move, the search runs on the main thread and has to stay responsive
+const MAX_SEARCH_NODES: usize = 300;
+/// most CAS calls of evaluations for one move, each evaluation checks every card in both hands against the field
+const MAX_SEARCH_CAS_CALLS: usize = 3000;
+/// milliseconds after which no more actions are tried for one move
+const MAX_SEARCH_MS: f64 = 400.0;
+/// CAS budget while searching, smaller than the default so no single limit or domain holds up a move
+const SEARCH_BUDGET: Budget = Budget {
+    max_depth: 32,
+    max_nodes: 512,
+    max_steps: 256,
+};
+
+/// strength of the computer player, deeper search and less randomness are harder
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// turns searched ahead, including the computer's own
+    pub fn depth(&self) -> u32 {
+        match self {
+            Difficulty::Easy => 1,
+            Difficulty::Medium => 2,
+            Difficulty::Hard => 3,
+        }
+    }
+
+    /// largest random score added to each action, makes weaker players miss good moves
+    pub fn randomness(&self) -> i32 {
+        match self {
+            Difficulty::Easy => 300,
+            Difficulty::Medium => 60,
+            Difficulty::Hard => 0,
+        }
+    }
+}
+
+/// parses a settings value, unknown values fall back to the default difficulty
+impl From<&str> for Difficulty {
+    fn from(input: &str) -> Self {
+        match input {
+            "EASY" => Self::Easy,
+            "MEDIUM" => Self::Medium,
+            "HARD" => Self::Hard,
+            _ => Self::default(),
+        }
+    }
+}
+
+/// picks an action for the current player that `apply` accepts, Pass only if no card can be computed
+/// None if the game is over
+pub fn choose_action(game: &Game, difficulty: Difficulty) -> Option<Action> {
+    choose_action_with(game, difficulty, &mut thread_rng())
+}
+
+/// picks an action for the current player with given random source
+pub fn choose_action_with<R: Rng>(
+    game: &Game,
+    difficulty: Difficulty,
+    rng: &mut R,
+) -> Option<Action> {
+    let previous = budget();
+    set_budget(SEARCH_BUDGET);
+    let action = search_action(game, difficulty, rng);
+    set_budget(previous);
+    // the search may run out of time before trying any action, or pass on cards only the full budget computes
+    action
+        .filter(|action| game.clone().apply(action.clone()).is_ok())
+        .or_else(|| first_action(game))
+}
+
+/// first action of the current player that `apply` accepts, Pass if no card can be computed
+fn first_action(game: &Game) -> Option<Action> {
+    if game.winner().is_some() {
+        return None;
+    }
+    // any Mult/Div that can be played can be played on 2 operands
+    let action = game
+        .playable_actions(2)
+        .into_iter()
+        .find(|action| game.clone().apply(action.clone()).is_ok());
+    if action.is_none() {
+        game.set_playable(false);
+    }
+    Some(action.unwrap_or(Action::Pass))
+}
+
+/// best action found by searching `difficulty.depth()` turns ahead
+fn search_action<R: Rng>(game: &Game, difficulty: Difficulty, rng: &mut R) -> Option<Action> {
+    let player = game.get_current_player_num();
+    // search a copy where the opponent holds a sample of the cards not seen, as their hand is hidden
+    // the deck is reshuffled from the rest so its order isn't known ahead either
+    let mut root = game.clone();
+    let opponent = hand_mut(&mut root, 3 - player);
+    let hand_size = opponent.len();
+    let mut unseen = opponent.split_off(0);
+    unseen.append(&mut root.deck);
+    unseen.shuffle(rng);
+    root.deck = unseen.split_off(hand_size);
+    *hand_mut(&mut root, 3 - player) = unseen;
+
+    let mut best: Option<(i32, Action)> = None;
+    let (mut alpha, beta) = (-2 * WIN_SCORE, 2 * WIN_SCORE);
+    let mut limit = SearchLimit::new();
+    for (action, child, score) in children(&root, player, true, &mut limit) {
+        let value = if difficulty.depth() > 1 {
+            search(
+                &child,
+                difficulty.depth() - 1,
+                alpha,
+                beta,
+                player,
+                &mut limit,
+            )
+        } else {
+            score
+        } + rng.gen_range(0..=difficulty.randomness());
+        if best
+            .as_ref()
+            .is_none_or(|(best_value, _)| value > *best_value)
+        {
+            best = Some((value, action));
+        }
+        // random scores can reorder any action, only prune without them
+        if difficulty.randomness() == 0 {
+            alpha = max(alpha, value);
+        }
+    }
+    best.map(|(_, action)| action)
+}
+
+/// work left for one move, in actions tried, CAS calls of evaluations and time
+struct SearchLimit {
+    nodes: usize,
+    cas_calls: usize,
+    deadline: f64,
+}
+
+impl SearchLimit {
+    fn new() -> SearchLimit {
+        SearchLimit {
+            nodes: 0,
+            cas_calls: 0,
+            deadline: now_ms() + MAX_SEARCH_MS,
+        }
+    }
+
+    /// counts an action about to be tried, false if the limit is exhausted
+    fn try_node(&mut self) -> bool {
+        if self.nodes >= MAX_SEARCH_NODES
+            || self.cas_calls >= MAX_SEARCH_CAS_CALLS
+            || now_ms() >= self.deadline
+        {
+            return false;
+        }
+        self.nodes += 1;
+        true
+    }
+
+    /// counts a CAS call of an evaluation about to be made, false if the limit is exhausted
+    fn try_cas_call(&mut self) -> bool {
+        if self.cas_calls >= MAX_SEARCH_CAS_CALLS || now_ms() >= self.deadline {
+            return false;
+        }
+        self.cas_calls += 1;
+        true
+    }
+}
+
+/// milliseconds since the epoch, from JS on wasm where std has no clock
+fn now_ms() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    return js_sys::Date::now();
+    #[cfg(not(target_arch = "wasm32"))]
+    return std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |elapsed| elapsed.as_secs_f64() * 1000.0);
+}
+
+/// minimax value of game for `player`, with alpha-beta pruning
+/// positions are evaluated as they are once `limit` is exhausted
+fn search(
+    game: &Game,
+    depth: u32,
+    mut alpha: i32,
+    mut beta: i32,
+    player: u32,
+    limit: &mut SearchLimit,
+) -> i32 {
+    if game.winner().is_some() {
+        return evaluate_within(game, player, limit);
+    }
+    let maximising = game.get_current_player_num() == player;
+    let children = children(game, player, maximising, limit);
+    if children.is_empty() {
+        return evaluate_within(game, player, limit);
+    }
+
+    let mut value = if maximising { i32::MIN } else { i32::MAX };
+    for (_, child, score) in children {
+        // children are already evaluated for ordering
+        let child_value = if depth > 1 {
+            search(&child, depth - 1, alpha, beta, player, limit)
+        } else {
+            score
+        };
+        if maximising {
+            value = max(value, child_value);
+            alpha = max(alpha, value);
+        } else {
+            value = min(value, child_value);
+            beta = min(beta, value);
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+    value
+}
+
+/// most promising actions of the current player with the game after each and its evaluation for `player`
+/// actions the CAS rejects are skipped, identical cards at different hand indices are searched once
+/// no more actions are tried once `limit` is exhausted, every CAS call of an action is counted against it
+fn children(
+    game: &Game,
+    player: u32,
+    maximising: bool,
+    limit: &mut SearchLimit,
+) -> Vec<(Action, Game, i32)> {
+    if game.winner().is_some() {
+        return vec![];
+    }
+    let mut seen = HashSet::new();
+    let mut children = vec![];
+    let mut tried_all = true;
+    for action in game.playable_actions(MAX_SEARCH_OPERANDS) {
+        if !seen.insert(signature(game, &action)) {
+            continue;
+        }
+        if !limit.try_node() {
+            tried_all = false;
+            break;
+        }
+        let mut child = game.clone();
+        if child.apply(action.clone()).is_ok() {
+            let score = evaluate_within(&child, player, limit);
+            children.push((action, child, score));
+        }
+    }
+    // Pass only if every card failed in the CAS, which `apply` would otherwise try all over again
+    if children.is_empty() && tried_all && limit.try_node() {
+        game.set_playable(false);
+        let mut child = game.clone();
+        if child.apply(Action::Pass).is_ok() {
+            let score = evaluate_within(&child, player, limit);
+            children.push((Action::Pass, child, score));
+        }
+    }
+
+    // best for the player to move first
+    children.sort_by_key(|(_, _, score)| if maximising { -score } else { *score });
+    children.truncate(BEAM_WIDTH);
+    children
+}
+
+/// action with hand indices replaced by the cards they hold
+fn signature(game: &Game, action: &Action) -> String {
+    let hand = game.get_current_player();
+    match action {
+        Action::PlayBasis { card, slot } | Action::ApplyOperator { card, slot } => {
+            format!("{:?} f{}", hand[*card], slot)
+        }
+        Action::FieldOperator { card, half } => format!("{:?} half {}", hand[*card], half),
+        Action::MultiCombine { card, operands } => format!(
+            "{:?} {:?}",
+            hand[*card],
+            operands
+                .iter()
+                .map(|operand| match *operand {
+                    Operand::Field(slot) => format!("f{}", slot),
+                    Operand::Hand(i) => format!("{:?}", hand[i]),
+                })
+                .collect::<Vec<String>>()
+        ),
+        Action::Pass => "Pass".to_string(),
+    }
+}
+
+/// heuristic score of game for `player`, positive if they are ahead
+/// weighs bases left on each half, bases the hands can clear in one move and hand strength
+pub fn evaluate(game: &Game, player: u32) -> i32 {
+    evaluate_within(game, player, &mut SearchLimit::new())
+}
+
+/// `evaluate` with its CAS calls counted against `limit`, bases are not threatened once it is exhausted
+fn evaluate_within(game: &Game, player: u32, limit: &mut SearchLimit) -> i32 {
+    match game.winner() {
+        Some(winner) if winner == player => return WIN_SCORE,
+        Some(_) => return -WIN_SCORE,
+        None => {}
+    }
+    side_score(game, player, limit) - side_score(game, 3 - player, limit)
+}
+
+/// score of one player on its own, see `evaluate`
+fn side_score(game: &Game, player: u32, limit: &mut SearchLimit) -> i32 {
+    let hand = if player == 1 {
+        &game.player_1
+    } else {
+        &game.player_2
+    };
+    let bases = defended_half(player)
+        .filter(|slot| game.field[*slot].basis.is_some())
+        .count() as i32;
+    let strength = hand.iter().map(card_value).sum::<i32>();
+
+    100 * bases + 40 * threats(game, player, limit) + strength
+}
+
+/// hand of `player`, to deal them other cards
+fn hand_mut(game: &mut Game, player: u32) -> &mut Vec<Card> {
+    if player == 1 {
+        &mut game.player_1
+    } else {
+        &mut game.player_2
+    }
+}
+
+/// field slots `player` has to keep, the opponent wins by clearing them
+fn defended_half(player: u32) -> Range<usize> {
+    if player == 1 {
+        3..6
+    } else {
+        0..3
+    }
+}
+
+/// number of opponent bases `player` could clear with one card from their hand
+fn threats(game: &Game, player: u32, limit: &mut SearchLimit) -> i32 {
+    let hand = if player == 1 {
+        &game.player_1
+    } else {
+        &game.player_2
+    };
+    // 0 times or over anything clears it
+    let has_zero = hand.contains(&Card::BasisCard(BasisCard::Zero))
+        && hand.iter().any(|card| {
+            matches!(
+                card,
+                Card::AlgebraicCard(AlgebraicCard::Mult | AlgebraicCard::Div)
+            )
+        });
+
+    defended_half(3 - player)
+        .filter_map(|slot| game.field[slot].basis.as_ref())
+        .filter(|basis| has_zero || hand.iter().any(|card| clears(card, basis, limit)))
+        .count() as i32
+}
+
+/// checks if a single target card removes basis from the field, false once `limit` is exhausted
+/// skips the domain check of `apply_card`, results that clear a slot are defined everywhere
+fn clears(card: &Card, basis: &Basis, limit: &mut SearchLimit) -> bool {
+    let result = match card {
+        Card::DerivativeCard(DerivativeCard::Derivative | DerivativeCard::Nabla)
+            if limit.try_cas_call() =>
+        {
+            cached_derivative(basis)
+        }
+        Card::DerivativeCard(DerivativeCard::Laplacian) if limit.try_cas_call() => {
+            cached_derivative(basis).and_then(|derivative| cached_derivative(&derivative))
+        }
+        Card::AlgebraicCard(AlgebraicCard::Log) if limit.try_cas_call() => cached_logarithm(basis),
+        Card::LimitCard(limit_card) if limit.try_cas_call() => cached_limit(limit_card, basis),
+        // integrals, inverses and roots never give 0, and nothing is computed once `limit` is exhausted
+        _ => return false,
+    };
+    result.is_ok_and(|result| clears_slot(&result))
+}
+
+/// rough usefulness of a card in hand
+fn card_value(card: &Card) -> i32 {
+    match card {
+        Card::BasisCard(BasisCard::Zero) => 3,
+        Card::BasisCard(_) => 2,
+        Card::DerivativeCard(DerivativeCard::Integral) => 2,
+        Card::DerivativeCard(DerivativeCard::Laplacian) => 5,
+        Card::DerivativeCard(_) => 4,
+        Card::AlgebraicCard(AlgebraicCard::Mult | AlgebraicCard::Div) => 3,
+        Card::AlgebraicCard(_) => 2,
+        Card::LimitCard(_) => 4,
+    }
+}
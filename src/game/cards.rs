@@ -3,37 +3,74 @@ use std::fmt::{Display, Formatter, Result};
 // outer crate imports
 use crate::basis::{builders::*, structs::*};
 use crate::game::flags::DISPLAY_LN_FOR_LOG;
-use crate::math::{
-    derivative::derivative, integral::integral, inverse::inverse, limits::limit,
-    logarithm::logarithm,
+use crate::math::cache::{
+    cached_derivative, cached_integral, cached_inverse, cached_limit, cached_logarithm, CasResult,
 };
+use crate::math::domain::{domain_with, ROOT_WINDOW};
+use crate::math::error::{validate, MathError};
+use crate::math::fraction::Fraction;
 // util imports
-use crate::util::ToLatex;
+use crate::util::{Language, MathMLFlags, ToLatex, ToMathML, ToSpeech};
 
-/// apply effect of `card` onto Basis `basis`
-pub fn apply_card(card: &Card) -> impl Fn(&Basis) -> Basis {
+/// apply effect of `card` onto Basis `basis`, returns MathError if the CAS cannot compute the result
+pub fn apply_card(card: &Card) -> impl Fn(&Basis) -> CasResult {
     let card = card.clone();
-    return move |basis| match card {
-        Card::DerivativeCard(
-            DerivativeCard::Derivative | DerivativeCard::Nabla | DerivativeCard::Laplacian,
-        ) => derivative(basis),
-        Card::DerivativeCard(DerivativeCard::Integral) => integral(basis),
-        Card::AlgebraicCard(AlgebraicCard::Sqrt) => SqrtBasisNode(1, basis),
-        Card::AlgebraicCard(AlgebraicCard::Inverse) => inverse(basis),
-        Card::AlgebraicCard(AlgebraicCard::Log) => logarithm(&basis),
-        Card::LimitCard(limit_card) => {
-            let basis_limit = limit(&limit_card)(&basis).unwrap_or(
-                basis.clone(), // return original if limit is invalid, // TODO: points?
-            );
-            basis_limit
-        }
-        _ => Basis::from(0),
+    return move |basis| {
+        defined(match card {
+            Card::DerivativeCard(
+                DerivativeCard::Derivative | DerivativeCard::Nabla | DerivativeCard::Laplacian,
+            ) => cached_derivative(basis),
+            Card::DerivativeCard(DerivativeCard::Integral) => cached_integral(basis),
+            Card::AlgebraicCard(AlgebraicCard::Sqrt) => {
+                validate(basis)?;
+                Ok(SqrtBasisNode(1, basis))
+            }
+            Card::AlgebraicCard(AlgebraicCard::Inverse) => cached_inverse(basis),
+            Card::AlgebraicCard(AlgebraicCard::Log) => cached_logarithm(&basis),
+            Card::LimitCard(limit_card) => match cached_limit(&limit_card, &basis) {
+                Ok(basis_limit) => Ok(basis_limit),
+                // return original if limit does not exist, // TODO: points?
+                Err(MathError::DomainError(_)) => Ok(basis.clone()),
+                Err(error) => Err(error),
+            },
+            _ => Ok(Basis::from(0)),
+        })
     };
 }
 
+/// rejects results that are defined nowhere, ie. log(-x^2)
+fn defined(result: CasResult) -> CasResult {
+    let basis = result?;
+    // being defined anywhere near 0 is enough, so periodic restrictions needn't be exact
+    if domain_with(&basis, true, Some(ROOT_WINDOW))?.is_empty() {
+        return Err(MathError::DomainError(format!(
+            "{} is defined nowhere",
+            basis
+        )));
+    }
+    Ok(basis)
+}
+
 // used for Mult and Div cards, coalesces selected operands and applies the operator
-pub fn apply_multi_card(card: &Card, bases: Vec<Basis>) -> Basis {
-    match card {
+pub fn apply_multi_card(card: &Card, bases: Vec<Basis>) -> CasResult {
+    bases.iter().try_for_each(validate)?;
+    // the builders multiply coefficients unchecked, so check their product fits first
+    let overflow = || MathError::Overflow(format!("coefficients of {:?} in {}", bases, card));
+    bases
+        .iter()
+        .enumerate()
+        .try_fold(Fraction::from(1), |acc, (i, basis)| {
+            let scale = scale(basis)?;
+            match card {
+                Card::AlgebraicCard(AlgebraicCard::Div) if i % 2 == 1 && scale != 0 => {
+                    acc.checked_mul(!scale)
+                }
+                _ => acc.checked_mul(scale),
+            }
+        })
+        .ok_or_else(overflow)?;
+
+    let result = match card {
         Card::AlgebraicCard(AlgebraicCard::Mult) => MultBasisNode(bases),
         Card::AlgebraicCard(AlgebraicCard::Div) => {
             let mut numerator = vec![];
@@ -45,14 +82,56 @@ pub fn apply_multi_card(card: &Card, bases: Vec<Basis>) -> Basis {
                     denominator.push(bases[i].clone());
                 }
             }
+            if denominator.iter().any(|basis| basis.is_num(0)) {
+                return Err(MathError::DomainError(format!(
+                    "division by zero in {}",
+                    card
+                )));
+            }
             MultBasisNode(numerator) / MultBasisNode(denominator)
         }
-        _ => panic!("Unknown MULTISELECT card: {}!", card),
+        _ => {
+            return Err(MathError::Unsupported(format!(
+                "unknown MULTISELECT card: {}",
+                card
+            )))
+        }
+    };
+    // product may still be too large to compute with further
+    validate(&result)?;
+    Ok(result)
+}
+
+/// product of the coefficients multiplied together when building a Mult of basis, None if it overflows
+fn scale(basis: &Basis) -> Option<Fraction> {
+    match basis {
+        Basis::BasisNode(BasisNode {
+            coefficient,
+            operator: BasisOperator::Mult,
+            operands,
+        }) => operands
+            .iter()
+            .try_fold(*coefficient, |acc, op| acc.checked_mul(scale(op)?)),
+        Basis::BasisNode(BasisNode {
+            coefficient,
+            operator: BasisOperator::Div,
+            operands,
+        }) => {
+            let denominator = scale(&operands[1])?;
+            if denominator == 0 {
+                return None;
+            }
+            coefficient
+                .checked_mul(scale(&operands[0])?)?
+                .checked_mul(!denominator)
+        }
+        _ => Some(basis.coefficient()),
     }
 }
 
 /// type union of basis cards or operator cards
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Card {
     BasisCard(BasisCard),
     LimitCard(LimitCard),
@@ -92,9 +171,32 @@ impl ToLatex for Card {
         }
     }
 }
+/// MathML representation of Card, defers to enum variants
+impl ToMathML for Card {
+    fn to_mathml_with(&self, flags: MathMLFlags) -> String {
+        match self {
+            Card::BasisCard(basis_card) => basis_card.to_mathml_with(flags),
+            Card::LimitCard(limit_card) => limit_card.to_mathml_with(flags),
+            Card::AlgebraicCard(algebraic_card) => algebraic_card.to_mathml_with(flags),
+            Card::DerivativeCard(derivative_card) => derivative_card.to_mathml_with(flags),
+        }
+    }
+}
+/// spoken Card, defers to enum variants
+impl ToSpeech for Card {
+    fn to_speech(&self, language: Language) -> String {
+        match self {
+            Card::BasisCard(basis_card) => basis_card.to_speech(language),
+            Card::LimitCard(limit_card) => limit_card.to_speech(language),
+            Card::AlgebraicCard(algebraic_card) => algebraic_card.to_speech(language),
+            Card::DerivativeCard(derivative_card) => derivative_card.to_speech(language),
+        }
+    }
+}
 
 /// card that represents a Basis
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BasisCard {
     Zero,
     One,
@@ -133,9 +235,31 @@ impl ToLatex for BasisCard {
         format!("{}", string)
     }
 }
+/// MathML representation of BasisCard, read by screen readers
+impl ToMathML for BasisCard {
+    fn to_mathml_with(&self, _flags: MathMLFlags) -> String {
+        let string = match self {
+            BasisCard::Zero => "<mn>0</mn>",
+            BasisCard::One => "<mn>1</mn>",
+            BasisCard::X => "<mi>x</mi>",
+            BasisCard::X2 => "<msup><mi>x</mi><mn>2</mn></msup>",
+            BasisCard::Cos => "<mrow><mi>cos</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow></mrow>",
+            BasisCard::Sin => "<mrow><mi>sin</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow></mrow>",
+            BasisCard::E => "<msup><mi>e</mi><mi>x</mi></msup>",
+        };
+        string.to_string()
+    }
+}
+/// spoken BasisCard, reads the Basis it represents
+impl ToSpeech for BasisCard {
+    fn to_speech(&self, language: Language) -> String {
+        Basis::from(*self).to_speech(language)
+    }
+}
 
 /// enum representing the various limit operator cards
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LimitCard {
     LimPosInf,
     LimNegInf,
@@ -169,9 +293,52 @@ impl ToLatex for LimitCard {
         format!("{}", string)
     }
 }
+/// MathML representation of LimitCard, read by screen readers
+impl ToMathML for LimitCard {
+    fn to_mathml_with(&self, _flags: MathMLFlags) -> String {
+        let (operator, point) = match self {
+            LimitCard::LimPosInf => ("lim", "<mo>+</mo><mi>∞</mi>"),
+            LimitCard::LimNegInf => ("lim", "<mo>-</mo><mi>∞</mi>"),
+            LimitCard::Lim0 => ("lim", "<mn>0</mn>"),
+            LimitCard::Liminf => ("lim inf", "<mo>+</mo><mi>∞</mi>"),
+            LimitCard::Limsup => ("lim sup", "<mo>+</mo><mi>∞</mi>"),
+        };
+        format!(
+            "<munder><mo>{}</mo><mrow><mi>x</mi><mo>→</mo>{}</mrow></munder>",
+            operator, point
+        )
+    }
+}
+/// spoken LimitCard, ie. limit as x approaches positive infinity
+impl ToSpeech for LimitCard {
+    fn to_speech(&self, language: Language) -> String {
+        let string = match (self, language) {
+            (LimitCard::LimPosInf, Language::English) => "limit as x approaches positive infinity",
+            (LimitCard::LimNegInf, Language::English) => "limit as x approaches negative infinity",
+            (LimitCard::Lim0, Language::English) => "limit as x approaches zero",
+            (LimitCard::Liminf, Language::English) => {
+                "limit inferior as x approaches positive infinity"
+            }
+            (LimitCard::Limsup, Language::English) => {
+                "limit superior as x approaches positive infinity"
+            }
+            (LimitCard::LimPosInf, Language::Japanese) => {
+                "エックスを正の無限大に近づけたときの極限"
+            }
+            (LimitCard::LimNegInf, Language::Japanese) => {
+                "エックスを負の無限大に近づけたときの極限"
+            }
+            (LimitCard::Lim0, Language::Japanese) => "エックスをゼロに近づけたときの極限",
+            (LimitCard::Liminf, Language::Japanese) => "エックスを正の無限大に近づけたときの下極限",
+            (LimitCard::Limsup, Language::Japanese) => "エックスを正の無限大に近づけたときの上極限",
+        };
+        string.to_string()
+    }
+}
 
 /// enum representing the various derivative operator cards
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DerivativeCard {
     Integral,
     Derivative,
@@ -202,9 +369,40 @@ impl ToLatex for DerivativeCard {
         format!("{}", string)
     }
 }
+/// MathML representation of DerivativeCard, read by screen readers
+impl ToMathML for DerivativeCard {
+    fn to_mathml_with(&self, _flags: MathMLFlags) -> String {
+        let string = match self {
+            DerivativeCard::Derivative => {
+                "<mfrac><mi>d</mi><mrow><mi>d</mi><mi>x</mi></mrow></mfrac>"
+            }
+            DerivativeCard::Nabla => "<mo>∇</mo>",
+            DerivativeCard::Laplacian => "<mi mathvariant=\"normal\">Δ</mi>",
+            DerivativeCard::Integral => "<mo>∫</mo>",
+        };
+        string.to_string()
+    }
+}
+/// spoken DerivativeCard, ie. derivative with respect to x
+impl ToSpeech for DerivativeCard {
+    fn to_speech(&self, language: Language) -> String {
+        let string = match (self, language) {
+            (DerivativeCard::Derivative, Language::English) => "derivative with respect to x",
+            (DerivativeCard::Nabla, Language::English) => "nabla",
+            (DerivativeCard::Laplacian, Language::English) => "Laplacian",
+            (DerivativeCard::Integral, Language::English) => "integral",
+            (DerivativeCard::Derivative, Language::Japanese) => "エックスで微分",
+            (DerivativeCard::Nabla, Language::Japanese) => "ナブラ",
+            (DerivativeCard::Laplacian, Language::Japanese) => "ラプラシアン",
+            (DerivativeCard::Integral, Language::Japanese) => "積分",
+        };
+        string.to_string()
+    }
+}
 
 /// enum representing the various algebraic operator cards
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AlgebraicCard {
     Div,
     Mult,
@@ -245,3 +443,33 @@ impl ToLatex for AlgebraicCard {
         format!("{}", string)
     }
 }
+/// MathML representation of AlgebraicCard, read by screen readers
+impl ToMathML for AlgebraicCard {
+    fn to_mathml_with(&self, flags: MathMLFlags) -> String {
+        match self {
+            AlgebraicCard::Div => "<mo>÷</mo>".to_string(),
+            AlgebraicCard::Mult => "<mo>×</mo>".to_string(),
+            AlgebraicCard::Sqrt => "<msqrt><mrow></mrow></msqrt>".to_string(),
+            AlgebraicCard::Inverse => BasisOperator::Inv.to_mathml_with(flags),
+            AlgebraicCard::Log => BasisOperator::Log.to_mathml_with(flags),
+        }
+    }
+}
+/// spoken AlgebraicCard, ie. square root
+impl ToSpeech for AlgebraicCard {
+    fn to_speech(&self, language: Language) -> String {
+        let string = match (self, language) {
+            (AlgebraicCard::Div, Language::English) => "divide",
+            (AlgebraicCard::Mult, Language::English) => "multiply",
+            (AlgebraicCard::Sqrt, Language::English) => "square root",
+            (AlgebraicCard::Inverse, Language::English) => "inverse",
+            (AlgebraicCard::Log, Language::English) => "natural log",
+            (AlgebraicCard::Div, Language::Japanese) => "割り算",
+            (AlgebraicCard::Mult, Language::Japanese) => "掛け算",
+            (AlgebraicCard::Sqrt, Language::Japanese) => "平方根",
+            (AlgebraicCard::Inverse, Language::Japanese) => "逆関数",
+            (AlgebraicCard::Log, Language::Japanese) => "自然対数",
+        };
+        string.to_string()
+    }
+}
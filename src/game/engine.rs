@@ -0,0 +1,667 @@
+// std imports
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+// outer crate imports
+use crate::basis::structs::*;
+use crate::math::budget::{budget, Budget};
+use crate::math::equivalence::{scalar_multiple, Equivalence};
+use crate::math::error::MathError;
+use crate::math::linear_algebra::independent_subset;
+// local imports
+use super::cards::*;
+use super::field::{Field, FieldBasis};
+use super::structs::*;
+
+/// most cards a player can hold, hands are refilled to this size after every turn
+pub const HAND_SIZE: usize = 7;
+
+/// complete move of the current player, cards are indices into their hand
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    PlayBasis { card: usize, slot: usize }, // basis card onto an empty field slot
+    ApplyOperator { card: usize, slot: usize }, // single target operator onto a field basis
+    FieldOperator { card: usize, half: usize }, // nabla or laplacian onto slots 0-2 (half 0) or 3-5 (half 1)
+    MultiCombine { card: usize, operands: Vec<Operand> }, // mult or div over field bases and basis cards
+    Pass, // end turn without playing, only if no card can be played
+}
+
+/// operand of a Mult or Div card, in the order it was selected
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Operand {
+    Field(usize), // basis on field slot
+    Hand(usize),  // basis card from current player hand
+}
+
+/// observable results of an action, in the order they happened
+#[derive(Clone, Debug, PartialEq)]
+pub enum GameEvent {
+    CardsPlayed { player: u32, cards: Vec<Card> }, // cards moved from hand to graveyard
+    FieldChanged { slot: usize },                  // basis on slot was placed, replaced or cleared
+    BasisSkipped { slot: usize, error: MathError }, // field operator left slot unchanged
+    CardsDealt { player: u32, slots: Vec<usize> }, // hand indices of cards drawn from deck
+    TurnEnded { next_player: u32 },
+    GameOver { winner: u32 },
+}
+
+/// optional rules applied at the end of every turn, set from the settings flags by the browser
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rules {
+    pub allow_linear_dependence: bool, // keep field bases that are scalar multiples of each other
+    pub remove_dependent_bases: bool, // clear field bases that are linear combinations of the others
+}
+
+/// same as the defaults of ALLOW_LINEAR_DEPENDENCE and REMOVE_DEPENDENT_BASES
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            allow_linear_dependence: true,
+            remove_dependent_bases: false,
+        }
+    }
+}
+
+/// reasons an action is rejected, game is left unchanged
+#[derive(Clone, Debug, PartialEq)]
+pub enum RuleError {
+    GameOver,            // game already has a winner
+    InvalidCard(usize),  // no card at hand index
+    InvalidSlot(usize),  // no field slot (or half) at index
+    WrongCard(Card),     // card can't be played with this action
+    EmptySlot(usize),    // operator targets empty field slot
+    OccupiedSlot(usize), // basis card targets occupied field slot
+    IllegalMove(String), // action breaks a rule of the game
+    Math(MathError),     // CAS could not compute the result
+}
+
+/// string representation of RuleError, used for logging
+impl Display for RuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            RuleError::GameOver => write!(f, "Game is already over"),
+            RuleError::InvalidCard(card) => write!(f, "No card at hand index {}", card),
+            RuleError::InvalidSlot(slot) => write!(f, "No field slot {}", slot),
+            RuleError::WrongCard(card) => write!(f, "{} can't be played this way", card),
+            RuleError::EmptySlot(slot) => write!(f, "Field slot {} is empty", slot),
+            RuleError::OccupiedSlot(slot) => write!(f, "Field slot {} is occupied", slot),
+            RuleError::IllegalMove(message) => write!(f, "Illegal move: {}", message),
+            RuleError::Math(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl Error for RuleError {}
+
+impl From<MathError> for RuleError {
+    fn from(error: MathError) -> Self {
+        RuleError::Math(error)
+    }
+}
+
+/// turn number, current hand and field, which decide if any card can be played
+/// with the CAS budget, cards may only be computed within a larger one
+type Position = (u32, Vec<Card>, Field, Budget);
+
+/// result of `can_play` with the position it was computed in
+/// cached since it runs the CAS on every card, and is needed for every check of Pass
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PlayableCache(RefCell<Option<(Position, bool)>>);
+
+/// checks if result of an operator removes the basis from the field
+pub(crate) fn clears_slot(basis: &Basis) -> bool {
+    basis.is_num(0) || basis.is_inf(1) || basis.is_inf(-1)
+}
+
+impl Action {
+    /// hand index of the card played, None for Pass
+    pub fn card(&self) -> Option<usize> {
+        match self {
+            Action::PlayBasis { card, .. }
+            | Action::ApplyOperator { card, .. }
+            | Action::FieldOperator { card, .. }
+            | Action::MultiCombine { card, .. } => Some(*card),
+            Action::Pass => None,
+        }
+    }
+}
+
+impl Game {
+    /// every legal action of the current player, Pass only if no card can be played or computed
+    /// cards follow the rules without running the CAS, so `apply` can still fail with RuleError::Math,
+    /// but deciding on Pass applies every card in the CAS once per position, see `can_play`
+    /// Mult operands are listed in one order, Div operands once per numerator/denominator split
+    pub fn legal_actions(&self) -> Vec<Action> {
+        self.legal_actions_within(usize::MAX)
+    }
+
+    /// legal actions with at most `max_operands` for Mult/Div, their number grows exponentially with more
+    pub fn legal_actions_within(&self, max_operands: usize) -> Vec<Action> {
+        let mut actions = self.playable_actions(max_operands);
+        if self.winner().is_none() && !self.can_play() {
+            actions.push(Action::Pass);
+        }
+        actions
+    }
+
+    /// checks action against the rules for the current player, without computing its result
+    pub fn check(&self, action: &Action) -> std::result::Result<(), RuleError> {
+        if self.winner().is_some() {
+            return Err(RuleError::GameOver);
+        }
+        match action {
+            Action::PlayBasis { card, slot } => {
+                match self.hand_card(*card)? {
+                    Card::BasisCard(BasisCard::Zero) => {
+                        return Err(RuleError::IllegalMove(
+                            "0 can't be played onto the field".into(),
+                        ))
+                    }
+                    Card::BasisCard(_) => {}
+                    other => return Err(RuleError::WrongCard(other)),
+                }
+                if self.field_basis(*slot)?.is_some() {
+                    return Err(RuleError::OccupiedSlot(*slot));
+                }
+            }
+            Action::ApplyOperator { card, slot } => {
+                match self.hand_card(*card)? {
+                    Card::DerivativeCard(DerivativeCard::Derivative | DerivativeCard::Integral)
+                    | Card::AlgebraicCard(
+                        AlgebraicCard::Inverse | AlgebraicCard::Sqrt | AlgebraicCard::Log,
+                    )
+                    | Card::LimitCard(_) => {}
+                    other => return Err(RuleError::WrongCard(other)),
+                }
+                if self.field_basis(*slot)?.is_none() {
+                    return Err(RuleError::EmptySlot(*slot));
+                }
+            }
+            Action::FieldOperator { card, half } => {
+                match self.hand_card(*card)? {
+                    Card::DerivativeCard(DerivativeCard::Nabla) => {}
+                    // prevent player from playing laplacian on first turn for each player
+                    Card::DerivativeCard(DerivativeCard::Laplacian) if self.turn.number < 2 => {
+                        return Err(RuleError::IllegalMove(
+                            "Laplacian can't be played on the first turn".into(),
+                        ))
+                    }
+                    Card::DerivativeCard(DerivativeCard::Laplacian) => {}
+                    other => return Err(RuleError::WrongCard(other)),
+                }
+                if *half > 1 {
+                    return Err(RuleError::InvalidSlot(*half));
+                }
+            }
+            Action::MultiCombine { card, operands } => {
+                let operator = self.hand_card(*card)?;
+                if !matches!(
+                    operator,
+                    Card::AlgebraicCard(AlgebraicCard::Mult | AlgebraicCard::Div)
+                ) {
+                    return Err(RuleError::WrongCard(operator));
+                }
+                self.check_operands(*card, operands)?;
+            }
+            Action::Pass => {
+                if self.can_play() {
+                    return Err(RuleError::IllegalMove(
+                        "can only pass without any playable card".into(),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// plays action for the current player and ends their turn, returns what changed
+    /// rejected actions (including CAS errors) leave the game and the hand untouched
+    pub fn apply(&mut self, action: Action) -> std::result::Result<Vec<GameEvent>, RuleError> {
+        self.check(&action)?;
+
+        let mut events = vec![];
+        let used = match action {
+            Action::PlayBasis { card, slot } => {
+                let basis_card = match self.get_current_player()[card] {
+                    Card::BasisCard(basis_card) => basis_card,
+                    _ => unreachable!("checked PlayBasis with a basis card"),
+                };
+                self.field[slot] = FieldBasis::new(&Basis::from(basis_card));
+                events.push(GameEvent::FieldChanged { slot });
+                vec![card]
+            }
+            Action::ApplyOperator { card, slot } => {
+                let operator = self.get_current_player()[card];
+                let basis = self.field[slot].basis.clone().unwrap();
+                match operator {
+                    Card::DerivativeCard(_) => self.derivative_card(operator, slot)?,
+                    // shortcut if inverted before, inverting back gives the played basis
+                    Card::AlgebraicCard(AlgebraicCard::Inverse)
+                        if self.field.inverses.contains_key(&basis) =>
+                    {
+                        self.field.inverse(slot, None);
+                    }
+                    Card::AlgebraicCard(AlgebraicCard::Inverse) => {
+                        let result = apply_card(&operator)(&basis)?;
+                        self.field.inverse(slot, Some(result));
+                    }
+                    _ => {
+                        let result = apply_card(&operator)(&basis)?;
+                        self.field[slot] = if clears_slot(&result) {
+                            FieldBasis::none()
+                        } else {
+                            FieldBasis::new(&result)
+                        };
+                    }
+                }
+                events.push(GameEvent::FieldChanged { slot });
+                vec![card]
+            }
+            Action::FieldOperator { card, half } => {
+                let operator = self.get_current_player()[card];
+                // for each basis on one half of the field
+                for slot in half * 3..half * 3 + 3 {
+                    if self.field[slot].basis.is_none() {
+                        continue;
+                    }
+                    // skip bases the CAS cannot handle, the rest of the field is still affected
+                    match self.derivative_card(operator, slot) {
+                        Ok(()) => events.push(GameEvent::FieldChanged { slot }),
+                        Err(error) => events.push(GameEvent::BasisSkipped { slot, error }),
+                    }
+                }
+                vec![card]
+            }
+            Action::MultiCombine { card, operands } => {
+                let operator = self.get_current_player()[card];
+                let bases = operands
+                    .iter()
+                    .map(|operand| match *operand {
+                        Operand::Field(slot) => self.field[slot].basis.clone().unwrap(),
+                        Operand::Hand(index) => match self.get_current_player()[index] {
+                            Card::BasisCard(basis_card) => Basis::from(basis_card),
+                            _ => unreachable!("checked operand is a basis card"),
+                        },
+                    })
+                    .collect::<Vec<Basis>>();
+                let result = apply_multi_card(&operator, bases)?;
+
+                let used_slots = operands
+                    .iter()
+                    .filter_map(|operand| match operand {
+                        Operand::Field(slot) => Some(*slot),
+                        Operand::Hand(_) => None,
+                    })
+                    .collect::<Vec<usize>>();
+                // clear used field bases, result takes the first one
+                for slot in used_slots.iter() {
+                    self.field[*slot] = FieldBasis::none();
+                    events.push(GameEvent::FieldChanged { slot: *slot });
+                }
+                if !result.is_num(0) {
+                    self.field[used_slots[0]] = FieldBasis::new(&result);
+                }
+                let mut used = vec![card];
+                used.extend(operands.iter().filter_map(|operand| match operand {
+                    Operand::Hand(index) => Some(*index),
+                    Operand::Field(_) => None,
+                }));
+                used
+            }
+            Action::Pass => vec![],
+        };
+        events.extend(self.end_turn(used));
+        Ok(events)
+    }
+
+    /// winning player, if one half of the field has been cleared
+    pub fn winner(&self) -> Option<u32> {
+        let field = self.field.basis.iter();
+        if field.clone().take(3).all(|f| f.basis.is_none()) {
+            Some(1)
+        } else if field.clone().skip(3).all(|f| f.basis.is_none()) {
+            Some(2)
+        } else {
+            None
+        }
+    }
+
+    /// checks if any legal action playing a card can be computed, trying each on a copy of the game
+    /// a player whose every move fails in the CAS would otherwise be stuck
+    fn can_play(&self) -> bool {
+        if let Some((cached, playable)) = &*self.playable.0.borrow() {
+            if *cached == self.position() {
+                return *playable;
+            }
+        }
+        // any Mult/Div with more operands can also be played on just 2 of them
+        let playable = self
+            .playable_actions(2)
+            .into_iter()
+            .any(|action| self.clone().apply(action).is_ok());
+        self.set_playable(playable);
+        playable
+    }
+
+    /// records the result of `can_play` for the current position
+    /// for callers that already tried every action playing a card, ie. the computer's search
+    pub(crate) fn set_playable(&self, playable: bool) {
+        *self.playable.0.borrow_mut() = Some((self.position(), playable));
+    }
+
+    /// position `can_play` is cached for
+    fn position(&self) -> Position {
+        (
+            self.turn.number,
+            self.get_current_player().clone(),
+            self.field.clone(),
+            budget(),
+        )
+    }
+
+    /// operands that can be added to the Mult/Div `selected` so far for the card at hand index `card`,
+    /// so that the selection can still be completed to a legal action
+    pub fn next_operands(&self, card: usize, selected: &[Operand]) -> Vec<Operand> {
+        let items = self.multi_items(card);
+        // at most one more item is needed, to reach 2 operands or to add a field basis
+        let completable = |operands: &[Operand]| {
+            self.check_operands(card, operands).is_ok()
+                || items.iter().any(|item| {
+                    self.check_operands(card, &[operands, &[*item]].concat())
+                        .is_ok()
+                })
+        };
+        items
+            .iter()
+            .filter(|item| !selected.contains(item))
+            .filter(|item| completable(&[selected, &[**item]].concat()))
+            .copied()
+            .collect()
+    }
+
+    /// legal actions that play a card, by the rules alone so `apply` can still fail with RuleError::Math
+    /// cheap enough for every UI event, unlike `legal_actions`
+    pub(crate) fn playable_actions(&self, max_operands: usize) -> Vec<Action> {
+        let mut candidates = vec![];
+        for (card, hand_card) in self.get_current_player().iter().enumerate() {
+            match hand_card {
+                Card::BasisCard(_) => {
+                    candidates.extend((0..6).map(|slot| Action::PlayBasis { card, slot }))
+                }
+                Card::DerivativeCard(DerivativeCard::Nabla | DerivativeCard::Laplacian) => {
+                    candidates.extend((0..2).map(|half| Action::FieldOperator { card, half }))
+                }
+                Card::AlgebraicCard(AlgebraicCard::Mult | AlgebraicCard::Div) => {
+                    candidates.extend(self.multi_candidates(card, *hand_card, max_operands))
+                }
+                _ => candidates.extend((0..6).map(|slot| Action::ApplyOperator { card, slot })),
+            }
+        }
+        candidates
+            .into_iter()
+            .filter(|action| self.check(action).is_ok())
+            .collect()
+    }
+
+    /// every subset of field bases and basis cards, up to `max_operands`, for Mult/Div at hand index `card`
+    fn multi_candidates(&self, card: usize, operator: Card, max_operands: usize) -> Vec<Action> {
+        let items = self.multi_items(card);
+        let mut candidates = vec![];
+        for subset in 0..1u32 << items.len() {
+            let operands = (0..items.len())
+                .filter(|i| subset & 1 << i != 0)
+                .map(|i| items[i])
+                .collect::<Vec<Operand>>();
+            if operands.len() < 2 || operands.len() > max_operands {
+                continue;
+            }
+            if matches!(operator, Card::AlgebraicCard(AlgebraicCard::Mult)) {
+                candidates.push(Action::MultiCombine { card, operands });
+                continue;
+            }
+            // Div alternates numerator and denominator, pick which operands go on top
+            let numerator_size = operands.len().div_ceil(2);
+            for split in 0..1u32 << operands.len() {
+                if split.count_ones() as usize != numerator_size {
+                    continue;
+                }
+                let (numerator, denominator): (Vec<_>, Vec<_>) =
+                    (0..operands.len()).partition(|i| split & 1 << i != 0);
+                let mut ordered = vec![];
+                for i in 0..numerator_size {
+                    ordered.push(operands[numerator[i]]);
+                    if i < denominator.len() {
+                        ordered.push(operands[denominator[i]]);
+                    }
+                }
+                candidates.push(Action::MultiCombine {
+                    card,
+                    operands: ordered,
+                });
+            }
+        }
+        candidates
+    }
+
+    /// field bases and basis cards that Mult/Div at hand index `card` can take as operands
+    fn multi_items(&self, card: usize) -> Vec<Operand> {
+        let mut items = (0..6)
+            .filter(|slot| self.field[*slot].basis.is_some())
+            .map(Operand::Field)
+            .collect::<Vec<Operand>>();
+        items.extend(
+            self.get_current_player()
+                .iter()
+                .enumerate()
+                .filter(|(i, hand_card)| *i != card && matches!(hand_card, Card::BasisCard(_)))
+                .map(|(i, _)| Operand::Hand(i)),
+        );
+        items
+    }
+
+    /// card at index of current player hand
+    fn hand_card(&self, index: usize) -> std::result::Result<Card, RuleError> {
+        self.get_current_player()
+            .get(index)
+            .copied()
+            .ok_or(RuleError::InvalidCard(index))
+    }
+
+    /// basis on field slot, None if slot is empty
+    fn field_basis(&self, slot: usize) -> std::result::Result<Option<Basis>, RuleError> {
+        self.field
+            .basis
+            .get(slot)
+            .map(|field_basis| field_basis.basis.clone())
+            .ok_or(RuleError::InvalidSlot(slot))
+    }
+
+    /// checks Mult/Div operands are valid for operator at hand index `card`
+    fn check_operands(
+        &self,
+        card: usize,
+        operands: &[Operand],
+    ) -> std::result::Result<(), RuleError> {
+        if operands.len() < 2 {
+            return Err(RuleError::IllegalMove("needs at least 2 operands".into()));
+        }
+        if !operands
+            .iter()
+            .any(|operand| matches!(operand, Operand::Field(_)))
+        {
+            return Err(RuleError::IllegalMove(
+                "needs at least 1 field basis".into(),
+            ));
+        }
+        for (i, operand) in operands.iter().enumerate() {
+            if operands[..i].contains(operand) || *operand == Operand::Hand(card) {
+                return Err(RuleError::IllegalMove(format!(
+                    "{:?} is used twice",
+                    operand
+                )));
+            }
+            match *operand {
+                Operand::Field(slot) => {
+                    if self.field_basis(slot)?.is_none() {
+                        return Err(RuleError::EmptySlot(slot));
+                    }
+                }
+                Operand::Hand(index) => match self.hand_card(index)? {
+                    // 0 only combines with a single field basis
+                    Card::BasisCard(BasisCard::Zero) if operands.len() != 2 => {
+                        return Err(RuleError::IllegalMove(
+                            "0 can only be combined with one basis".into(),
+                        ))
+                    }
+                    Card::BasisCard(_) => {}
+                    other => return Err(RuleError::WrongCard(other)),
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// manages derivatives of FieldBasis, looks up history of derivatives/integrals and applies if possible
+    fn derivative_card(&mut self, card: Card, i: usize) -> std::result::Result<(), MathError> {
+        let is_laplacian = matches!(card, Card::DerivativeCard(DerivativeCard::Laplacian));
+        let is_integral = matches!(card, Card::DerivativeCard(DerivativeCard::Integral));
+        let is_derivative = matches!(
+            card,
+            Card::DerivativeCard(DerivativeCard::Derivative | DerivativeCard::Nabla)
+        );
+
+        let selected_field_basis = &self.field[i];
+        if selected_field_basis.basis.is_none() {
+            return Ok(());
+        }
+
+        // shortcut if already in history
+        if selected_field_basis.has_value(&card) {
+            if is_derivative || is_laplacian {
+                self.field.derivative(i, None);
+            } else if is_integral {
+                self.field.integral(i, None);
+            }
+            if is_laplacian {
+                self.field.derivative(i, None);
+            }
+            return Ok(());
+        }
+
+        // calculate derivative/integral, and second derivative if laplacian
+        let result_basis = apply_card(&card)(selected_field_basis.basis.as_ref().unwrap())?;
+        let second_derivative = if is_laplacian && !result_basis.is_num(0) {
+            Some(apply_card(&card)(&result_basis)?)
+        } else {
+            None
+        };
+
+        if result_basis.is_num(0) {
+            self.field[i] = FieldBasis::none();
+            return Ok(());
+        } else if is_derivative || is_laplacian {
+            self.field.derivative(i, Some(result_basis));
+        } else if is_integral {
+            self.field.integral(i, Some(result_basis));
+        }
+        if let Some(second_derivative) = second_derivative {
+            if second_derivative.is_num(0) {
+                self.field[i] = FieldBasis::none();
+            } else {
+                self.field.derivative(i, Some(second_derivative));
+            }
+        }
+        Ok(())
+    }
+
+    /// moves used cards to graveyard, refills hand, applies field settings and advances turn
+    fn end_turn(&mut self, mut used: Vec<usize>) -> Vec<GameEvent> {
+        let mut events = vec![];
+        let player_num = self.get_current_player_num();
+        let player = if player_num == 1 {
+            &mut self.player_1
+        } else {
+            &mut self.player_2
+        };
+
+        // remove used cards, highest index first so the rest stay in place
+        used.sort();
+        used.reverse();
+        let mut played = vec![];
+        for i in used.iter() {
+            let used_card = player.remove(*i);
+            self.graveyard.push(used_card);
+            played.push(used_card);
+        }
+        played.reverse();
+        if !played.is_empty() {
+            events.push(GameEvent::CardsPlayed {
+                player: player_num,
+                cards: played,
+            });
+        }
+
+        // replenish from deck if possible
+        let first_dealt = player.len();
+        while player.len() < HAND_SIZE && !self.deck.is_empty() {
+            player.push(self.deck.pop().unwrap());
+        }
+        if player.len() > first_dealt {
+            events.push(GameEvent::CardsDealt {
+                player: player_num,
+                slots: (first_dealt..player.len()).collect(),
+            });
+        }
+
+        if !self.rules.allow_linear_dependence {
+            let field = &mut self.field;
+            // clear field bases that are linearly dependent, on each half of the field
+            for half in [0..3, 3..6] {
+                for i in half.clone() {
+                    for j in i + 1..half.end {
+                        if let (Some(a), Some(b)) = (&field[i].basis, &field[j].basis) {
+                            if scalar_multiple(a, b) != Equivalence::Different {
+                                field[j] = FieldBasis::none();
+                                events.push(GameEvent::FieldChanged { slot: j });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.rules.remove_dependent_bases {
+            let field = &mut self.field;
+            // clear field bases that are linear combinations of earlier ones, on each half of the field
+            for half in [0..3, 3..6] {
+                let occupied = half
+                    .filter(|i| field[*i].basis.is_some())
+                    .collect::<Vec<usize>>();
+                let bases = occupied
+                    .iter()
+                    .map(|i| field[*i].basis.clone().unwrap())
+                    .collect::<Vec<Basis>>();
+                let kept = independent_subset(&bases);
+                for (k, i) in occupied.iter().enumerate() {
+                    if !kept.contains(&k) {
+                        field[*i] = FieldBasis::none();
+                        events.push(GameEvent::FieldChanged { slot: *i });
+                    }
+                }
+            }
+        }
+
+        self.turn = Turn {
+            number: self.turn.number + 1,
+            phase: TurnPhase::IDLE,
+        };
+        self.active.clear();
+        events.push(GameEvent::TurnEnded {
+            next_player: self.get_current_player_num(),
+        });
+
+        if let Some(winner) = self.winner() {
+            events.push(GameEvent::GameOver { winner });
+        }
+        events
+    }
+}
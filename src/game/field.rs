@@ -3,14 +3,19 @@ use std::collections::HashMap;
 use std::ops::{Index, IndexMut};
 use std::slice::SliceIndex;
 // outer crate imports
+use crate::basis::export::{export_all, ExportFormat};
 use crate::basis::structs::*;
 // local imports
 use super::cards::*;
 
 /// Controller for Game Field
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Field {
     pub basis: [FieldBasis; 6], // [0-2] for player_1, [3-5] for player_2
+    // inverses played this game both ways round, so inverting twice gives back the played basis
+    // game state saved with the game rather than a cache, the CAS may give an equivalent but different form
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialize::pairs"))]
     pub inverses: HashMap<Basis, Basis>,
 }
 
@@ -54,7 +59,7 @@ impl Field {
     }
 
     /// finds inverse of Basis at given index, uses value from history if available
-    /// pass None for `basis` to use inverse from history, else pass new inverse
+    /// pass None for `basis` to use inverse from history, else pass new inverse from `cached_inverse`
     pub fn inverse(&mut self, i: usize, inverse_basis: Option<Basis>) {
         let basis = self.basis[i].basis.as_ref().unwrap();
         // assumes that basis is not None
@@ -71,6 +76,15 @@ impl Field {
 
         self.basis[i].history = HashMap::default();
     }
+
+    /// exports every Basis on Field in given format, one per line, skipping empty slots
+    pub fn export(&self, format: ExportFormat) -> String {
+        let bases = self
+            .basis
+            .iter()
+            .filter_map(|field_basis| field_basis.basis.as_ref());
+        export_all(bases, format)
+    }
 }
 
 impl<Idx> Index<Idx> for Field
@@ -90,11 +104,14 @@ impl IndexMut<usize> for Field {
 }
 
 /// individual Basis of Field
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FieldBasis {
     pub basis: Option<Basis>,
     pub index: i32,
-    pub history: HashMap<i32, Basis>, // lookup for derivative/integral
+    // lookup for derivative/integral, game state rather than a cache
+    // integrating a derivative gives back the basis with its constant, which the CAS can't know
+    pub history: HashMap<i32, Basis>,
 }
 
 impl FieldBasis {
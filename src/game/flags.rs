@@ -1,7 +1,12 @@
+// local imports
+use super::ai::Difficulty;
+
 /// used in LaTeX formatting to use LN instead of LOG for the natural logarithm
 pub static mut DISPLAY_LN_FOR_LOG: bool = false;
-/// allow multiple field Basis with same base but different coefficients
+/// allow multiple field Basis that are scalar multiples of each other
 pub static mut ALLOW_LINEAR_DEPENDENCE: bool = true;
+/// remove field Basis that are linear combinations of the others on the same half (ie. sin, cos and sin+cos)
+pub static mut REMOVE_DEPENDENT_BASES: bool = false;
 /// allow limits of inverse trigonometric functions beyond the range of the function (ie. lim→INF)
 pub static mut ALLOW_LIMITS_BEYOND_BOUNDS: bool = true;
 /// fully expand all functions (ie. integrals, inverse)
@@ -10,3 +15,5 @@ pub static mut FULL_COMPUTE: bool = false;
 pub static mut USE_FRACTIONAL_EXPONENTS: bool = true;
 /// restrict field to maximum 3 Basis
 pub static mut LIMIT_FIELD_BASIS: bool = true;
+/// strength of the computer player in Play vs AI
+pub static mut AI_DIFFICULTY: Difficulty = Difficulty::Medium;
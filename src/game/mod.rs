@@ -1,4 +1,6 @@
+pub mod ai;
 pub mod cards;
+pub mod engine;
 pub mod field;
 pub mod flags;
 pub mod structs;
@@ -2,12 +2,10 @@
 use rand::seq::SliceRandom;
 use rand::thread_rng;
 // outer crate imports
-use crate::render::render;
 use crate::render::util::RenderId;
-// root imports
-use crate::MENU;
 // local imports
 use super::cards::*;
+use super::engine::{PlayableCache, Rules};
 use super::field::*;
 
 /// helper function to create new deck Vec
@@ -60,16 +58,25 @@ fn create_players(deck: &mut Vec<Card>) -> (Vec<Card>, Vec<Card>) {
 }
 
 /// main Game struct, holds all game state
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     pub state: GameState,
+    // PLAYAI or PLAYVS, kept while state shows a menu so saves resume against the same opponent
+    #[cfg_attr(feature = "serde", serde(default = "hot_seat"))]
+    pub mode: GameState,
     pub turn: Turn, // turn counter
     pub field: Field,
     pub player_1: Vec<Card>, // up to 7 cards in hand (<7 if deck running low)
     pub player_2: Vec<Card>,
     pub deck: Vec<Card>,
     pub graveyard: Vec<Card>,
+    #[cfg_attr(feature = "serde", serde(skip))] // settings, set again when the game is resumed
+    pub rules: Rules,
+    #[cfg_attr(feature = "serde", serde(skip))] // selection and hover are UI state
     pub active: ActiveCards,
+    #[cfg_attr(feature = "serde", serde(skip))] // recomputed after loading
+    pub(crate) playable: PlayableCache,
 }
 
 impl Game {
@@ -81,6 +88,7 @@ impl Game {
         let (player_1, player_2) = create_players(&mut deck);
         return Game {
             state: GameState::MENU,
+            mode: GameState::PLAYVS,
             turn: Turn {
                 number: 0,
                 phase: TurnPhase::IDLE,
@@ -90,10 +98,9 @@ impl Game {
             player_2: player_2,
             deck: deck,
             graveyard: vec![],
-            active: ActiveCards {
-                selected: Vec::default(),
-                hover: None,
-            },
+            rules: Rules::default(),
+            active: ActiveCards::default(),
+            playable: PlayableCache::default(),
         };
     }
 
@@ -113,40 +120,40 @@ impl Game {
             _ => unreachable!("No Active Player"),
         }
     }
-
-    /// set new game state
-    pub fn set_state(&mut self, state: GameState) {
-        let menu = unsafe { MENU.as_ref().unwrap() };
-
-        self.state = state;
-        match self.state {
-            GameState::PLAYAI | GameState::PLAYVS => {
-                menu.close();
-                render::draw();
+    /// rebuilds the selection of a loaded turn phase from the card it started with, as selections aren't saved
+    /// MULTISELECT and phases whose card is no longer in the current hand go back to IDLE
+    pub fn restore_selection(&mut self) {
+        self.active.clear();
+        let card = match self.turn.phase {
+            TurnPhase::IDLE => return,
+            TurnPhase::SELECT(card) | TurnPhase::FIELD_SELECT(card) => card,
+            // the Mult/Div operands picked so far are lost, start the card over
+            TurnPhase::MULTISELECT(_) => {
+                self.turn.phase = TurnPhase::IDLE;
+                return;
             }
-            GameState::MENU => {
-                menu.open();
+        };
+        match self.get_current_player().iter().position(|c| *c == card) {
+            Some(i) => {
+                let player_num = self.get_current_player_num();
+                self.active
+                    .selected
+                    .push(RenderId::from(format!("p{}={}", player_num, i)));
             }
-            _ => {}
+            None => self.turn.phase = TurnPhase::IDLE,
         }
     }
+}
 
-    /// handle losing state
-    pub fn game_over(&self, winner: u32) {
-        let menu = unsafe { MENU.as_ref().unwrap() };
-
-        menu.game_over_menu
-            .get_elements_by_tag_name("h2")
-            .item(0)
-            .unwrap()
-            .set_text_content(Some(format!("Player {} wins!", winner).as_str()));
-        menu.open();
-        menu.activate("GAMEOVER".to_string());
-    }
+/// mode of games saved before it was, they all resume as hot-seat games
+#[cfg(feature = "serde")]
+fn hot_seat() -> GameState {
+    GameState::PLAYVS
 }
 
 /// turn struct to manager turn number and turn phases
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Turn {
     pub number: u32,
     pub phase: TurnPhase,
@@ -154,7 +161,8 @@ pub struct Turn {
 
 /// turn phases for the steps required in various cards
 #[allow(non_camel_case_types)]
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TurnPhase {
     IDLE,               // start of turn
     SELECT(Card),       // single-basis operators or playing new operators with a blank slot
@@ -163,27 +171,31 @@ pub enum TurnPhase {
 }
 
 /// struct to store currently selected cards
-#[derive(Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct ActiveCards {
     pub selected: Vec<RenderId>,
     pub hover: Option<RenderId>,
+    pub targets: Vec<RenderId>, // items that continue a legal action, highlighted
 }
 
 impl ActiveCards {
     pub fn clear(&mut self) {
         self.selected = Vec::default();
         self.hover = None;
+        self.targets = Vec::default();
     }
 }
 
 /// different possible states of game and UI
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameState {
     MENU,
     PLAYAI,
     PLAYVS,
     TUTORIAL,
     SETTINGS,
+    SAVES,
     CREDITS,
 }
 
@@ -195,6 +207,7 @@ impl From<&str> for GameState {
             "PLAYVS" => Self::PLAYVS,
             "TUTORIAL" => Self::TUTORIAL,
             "SETTINGS" => Self::SETTINGS,
+            "SAVES" => Self::SAVES,
             "CREDITS" => Self::CREDITS,
             _ => unreachable!("{} is not a valid GameState", input),
         }
@@ -15,7 +15,11 @@ mod canvas;
 use canvas::*;
 mod render;
 
-mod util;
+#[cfg(feature = "serde")]
+pub mod serialize;
+#[cfg(feature = "serde")]
+mod storage;
+pub mod util;
 
 // When the `wee_alloc` feature is enabled, this uses `wee_alloc` as the global
 // allocator.
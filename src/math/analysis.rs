@@ -0,0 +1,318 @@
+// std imports
+use std::fmt::{Display, Formatter, Result};
+// outer crate imports
+use crate::basis::structs::*;
+use crate::game::cards::LimitCard;
+// local imports
+use super::derivative::derivative;
+use super::domain::{domain, sign_pieces};
+use super::eval::evaluate;
+use super::interval::{endpoint_string, Interval, IntervalSet};
+use super::limits::try_limit;
+
+/// distances from a domain endpoint where Basis is sampled to find vertical asymptotes
+const POLE_STEPS: [f64; 4] = [1e-2, 1e-4, 1e-6, 1e-8];
+/// |x| where asymptotes at ±∞ are checked numerically
+const FAR_X: f64 = 1e6;
+/// largest distance from an asymptote at FAR_X
+const ASYMPTOTE_TOLERANCE: f64 = 1e-3;
+
+/// direction Basis moves in on an interval
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Monotonicity {
+    Increasing,
+    Decreasing,
+    Constant,
+}
+
+/// kind of a point where the derivative or second derivative changes sign
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PointKind {
+    Minimum,
+    Maximum,
+    Inflection,
+}
+
+/// local extremum or inflection point (x, y)
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CriticalPoint {
+    pub x: f64,
+    pub y: f64,
+    pub kind: PointKind,
+}
+
+/// asymptote of Basis, `side` is 1 as x → ∞ and -1 as x → -∞
+#[derive(Clone, Debug, PartialEq)]
+pub enum Asymptote {
+    Horizontal {
+        y: Basis,
+        side: i32,
+    },
+    Vertical {
+        x: f64,
+    },
+    Oblique {
+        slope: Basis,
+        intercept: Basis,
+        side: i32,
+    },
+}
+
+/// shape of Basis over its domain
+#[derive(Clone, Debug, PartialEq)]
+pub struct Analysis {
+    pub domain: IntervalSet,
+    pub monotonicity: Vec<(Interval, Monotonicity)>,
+    pub extrema: Vec<CriticalPoint>,
+    pub inflections: Vec<CriticalPoint>,
+    pub asymptotes: Vec<Asymptote>,
+}
+
+/// analyses domain, monotonicity, extrema, inflection points and asymptotes of Basis
+pub fn analyze(basis: &Basis) -> Analysis {
+    Analysis {
+        domain: domain(basis),
+        monotonicity: monotonicity(basis),
+        extrema: extrema(basis),
+        inflections: inflections(basis),
+        asymptotes: asymptotes(basis),
+    }
+}
+
+/// maximal open intervals of the domain where Basis is increasing, decreasing or constant
+pub fn monotonicity(basis: &Basis) -> Vec<(Interval, Monotonicity)> {
+    slope_signs(basis, &derivative(basis))
+        .into_iter()
+        .map(|(interval, sign)| {
+            let monotonicity = match sign {
+                1 => Monotonicity::Increasing,
+                -1 => Monotonicity::Decreasing,
+                _ => Monotonicity::Constant,
+            };
+            (interval, monotonicity)
+        })
+        .collect()
+}
+
+/// local minima and maxima, where the derivative changes sign
+pub fn extrema(basis: &Basis) -> Vec<CriticalPoint> {
+    sign_changes(basis, &derivative(basis), |left, right| {
+        match (left, right) {
+            (1, -1) => Some(PointKind::Maximum),
+            (-1, 1) => Some(PointKind::Minimum),
+            _ => None,
+        }
+    })
+}
+
+/// inflection points, where the second derivative changes sign
+pub fn inflections(basis: &Basis) -> Vec<CriticalPoint> {
+    let second_derivative = derivative(&derivative(basis));
+    sign_changes(basis, &second_derivative, |left, right| {
+        (left * right == -1).then_some(PointKind::Inflection)
+    })
+}
+
+/// vertical asymptotes at the finite ends of the domain, horizontal or oblique asymptotes at ±∞
+pub fn asymptotes(basis: &Basis) -> Vec<Asymptote> {
+    let basis_domain = domain(basis);
+    let mut asymptotes: Vec<Asymptote> = vec![];
+    for interval in basis_domain.intervals() {
+        for (x, side) in [(interval.lower, 1.0), (interval.upper, -1.0)] {
+            let known = asymptotes.contains(&Asymptote::Vertical { x });
+            if x.is_finite() && !known && is_pole(basis, x, side) {
+                asymptotes.push(Asymptote::Vertical { x });
+            }
+        }
+    }
+
+    let intervals = basis_domain.intervals();
+    if intervals
+        .first()
+        .is_some_and(|interval| interval.lower == f64::NEG_INFINITY)
+    {
+        asymptotes.extend(asymptote_at_infinity(basis, -1));
+    }
+    if intervals
+        .last()
+        .is_some_and(|interval| interval.upper == f64::INFINITY)
+    {
+        asymptotes.extend(asymptote_at_infinity(basis, 1));
+    }
+    asymptotes
+}
+
+/// pieces of the domain of Basis where `slope` has a constant sign, joined over isolated zeros of `slope`
+fn slope_signs(basis: &Basis, slope: &Basis) -> Vec<(Interval, i32)> {
+    let basis_domain = domain(basis);
+    let set = basis_domain.intersect(&domain(slope));
+    let mut merged: Vec<(Interval, i32)> = vec![];
+    for (interval, sign) in sign_pieces(slope, &set) {
+        match merged.last_mut() {
+            Some((last, last_sign))
+                if *last_sign == sign
+                    && last.upper == interval.lower
+                    && basis_domain.contains(last.upper) =>
+            {
+                last.upper = interval.upper;
+            }
+            _ => merged.push((interval, sign)),
+        }
+    }
+    merged
+}
+
+/// points of the domain between pieces where `slope` changes sign, classified by `kind`
+fn sign_changes(
+    basis: &Basis,
+    slope: &Basis,
+    kind: impl Fn(i32, i32) -> Option<PointKind>,
+) -> Vec<CriticalPoint> {
+    let basis_domain = domain(basis);
+    slope_signs(basis, slope)
+        .windows(2)
+        .filter_map(|pieces| {
+            let ((left, left_sign), (right, right_sign)) = (pieces[0], pieces[1]);
+            let x = left.upper;
+            if x != right.lower || !basis_domain.contains(x) {
+                return None;
+            }
+            Some(CriticalPoint {
+                x,
+                y: evaluate(basis, x)?,
+                kind: kind(left_sign, right_sign)?,
+            })
+        })
+        .collect()
+}
+
+/// checks if |Basis| grows without bound as x approaches `x` from `side`
+fn is_pole(basis: &Basis, x: f64, side: f64) -> bool {
+    let magnitudes = POLE_STEPS
+        .iter()
+        .map(|step| evaluate(basis, x + side * step).map(f64::abs))
+        .collect::<Option<Vec<f64>>>();
+    let magnitudes = match magnitudes {
+        Some(magnitudes) => magnitudes,
+        None => return false,
+    };
+    if !magnitudes
+        .windows(2)
+        .all(|pair| pair[0] < pair[1] || pair[1].is_infinite())
+    {
+        return false;
+    }
+    let last = magnitudes[magnitudes.len() - 1];
+    if last.is_infinite() {
+        return true;
+    }
+    // a converging sequence has shrinking steps, a pole like log(x) does not
+    let first_step = magnitudes[1] - magnitudes[0];
+    let last_step = last - magnitudes[magnitudes.len() - 2];
+    last > 1.0 && last_step >= first_step / 2.0
+}
+
+/// horizontal asymptote if Basis has a finite limit, oblique asymptote if its derivative does
+fn asymptote_at_infinity(basis: &Basis, side: i32) -> Option<Asymptote> {
+    let limit_card = if side == 1 {
+        LimitCard::LimPosInf
+    } else {
+        LimitCard::LimNegInf
+    };
+    let far_x = side as f64 * FAR_X;
+    let close_at_far_x = |line: &Basis| match (evaluate(basis, far_x), evaluate(line, far_x)) {
+        (Some(y), Some(line_y)) => (y - line_y).abs() < ASYMPTOTE_TOLERANCE,
+        _ => false,
+    };
+    let finite = |limit: &Basis| !limit.is_inf(1) && !limit.is_inf(-1);
+
+    let limit = try_limit(&limit_card, basis).ok()?;
+    if finite(&limit) {
+        return close_at_far_x(&limit).then_some(Asymptote::Horizontal { y: limit, side });
+    }
+    let slope = try_limit(&limit_card, &derivative(basis)).ok()?;
+    if !finite(&slope) || slope.is_num(0) {
+        return None;
+    }
+    let intercept = try_limit(&limit_card, &(basis.clone() - slope.clone() * Basis::x())).ok()?;
+    if !finite(&intercept) {
+        return None;
+    }
+    close_at_far_x(&(slope.clone() * Basis::x() + intercept.clone())).then_some(
+        Asymptote::Oblique {
+            slope,
+            intercept,
+            side,
+        },
+    )
+}
+
+/// string representation of Monotonicity, ie. increasing
+impl Display for Monotonicity {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            Monotonicity::Increasing => "increasing",
+            Monotonicity::Decreasing => "decreasing",
+            Monotonicity::Constant => "constant",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// string representation of CriticalPoint, ie. minimum at (0, 1)
+impl Display for CriticalPoint {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self.kind {
+            PointKind::Minimum => "minimum",
+            PointKind::Maximum => "maximum",
+            PointKind::Inflection => "inflection",
+        };
+        write!(
+            f,
+            "{} at ({}, {})",
+            name,
+            endpoint_string(self.x),
+            endpoint_string(self.y)
+        )
+    }
+}
+
+/// string representation of Asymptote, ie. x = 0, y = 2x + 1 as x → ∞
+impl Display for Asymptote {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let direction = |side: &i32| if *side == 1 { "∞" } else { "-∞" };
+        match self {
+            Asymptote::Vertical { x } => write!(f, "x = {}", endpoint_string(*x)),
+            Asymptote::Horizontal { y, side } => {
+                write!(f, "y = {} as x → {}", y, direction(side))
+            }
+            Asymptote::Oblique {
+                slope,
+                intercept,
+                side,
+            } => write!(
+                f,
+                "y = {} as x → {}",
+                slope.clone() * Basis::x() + intercept.clone(),
+                direction(side)
+            ),
+        }
+    }
+}
+
+/// string representation of Analysis, one property per line
+impl Display for Analysis {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        writeln!(f, "domain: {}", self.domain)?;
+        for (interval, monotonicity) in &self.monotonicity {
+            writeln!(f, "{} on {}", monotonicity, interval)?;
+        }
+        for point in self.extrema.iter().chain(self.inflections.iter()) {
+            writeln!(f, "{}", point)?;
+        }
+        for asymptote in &self.asymptotes {
+            writeln!(f, "asymptote {}", asymptote)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+// std imports
+use std::cell::{Cell, RefCell};
+// outer crate imports
+use crate::basis::structs::*;
+// local imports
+use super::cache::clear_budget_errors;
+use super::error::MathError;
+
+/// limits on how much work a single CAS operation may do before giving up
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Budget {
+    pub max_depth: usize, // nested budgeted calls, ie. integral by parts or domains of operands
+    pub max_nodes: usize, // nodes in any intermediate result
+    pub max_steps: usize, // total budgeted calls per operation
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget {
+            max_depth: 32,
+            max_nodes: 2048,
+            max_steps: 4096,
+        }
+    }
+}
+
+/// work done by the current CAS operation, reset when the outermost call starts
+#[derive(Default)]
+struct Meter {
+    depth: Cell<usize>,
+    steps: Cell<usize>,
+    exceeded: RefCell<Option<MathError>>, // first limit hit, reported by `metered`
+}
+
+// thread local rather than static mut since native tests call the CAS from parallel threads
+thread_local! {
+    static BUDGET: Cell<Budget> = Cell::new(Budget::default());
+    static METER: Meter = Meter::default();
+}
+
+/// getter for budget on this thread
+pub fn budget() -> Budget {
+    BUDGET.with(|budget| budget.get())
+}
+
+/// sets budget on this thread, drops cached errors of the previous budget
+/// cached results that came back Ok are valid under any budget and are kept
+pub fn set_budget(budget: Budget) {
+    if BUDGET.with(|b| b.replace(budget)) != budget {
+        clear_budget_errors();
+    }
+}
+
+/// held while a budgeted call is running, leaves the call on drop
+pub(crate) struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        METER.with(|meter| meter.depth.set(meter.depth.get() - 1));
+    }
+}
+
+/// records the first limit exceeded by the current operation
+fn exceed(error: MathError) {
+    METER.with(|meter| {
+        let mut exceeded = meter.exceeded.borrow_mut();
+        if exceeded.is_none() {
+            *exceeded = Some(error);
+        }
+    });
+}
+
+/// enters a budgeted call, returns None if the depth or step limit has been reached
+/// callers should return an unevaluated node instead of recursing further
+pub(crate) fn enter() -> Option<Guard> {
+    try_enter().ok()
+}
+
+/// enters a budgeted call, returns the limit reached for callers without an unevaluated result
+pub(crate) fn try_enter() -> Result<Guard, MathError> {
+    let budget = budget();
+    METER.with(|meter| {
+        let depth = meter.depth.get();
+        let steps = meter.steps.get();
+        let error = if depth >= budget.max_depth {
+            MathError::RecursionLimit(depth)
+        } else if steps >= budget.max_steps {
+            MathError::BudgetExceeded(format!("{} steps", steps))
+        } else {
+            meter.depth.set(depth + 1);
+            meter.steps.set(steps + 1);
+            return Ok(Guard);
+        };
+        exceed(error.clone());
+        Err(error)
+    })
+}
+
+/// checks that result is within the node limit, records the limit as exceeded if not
+pub(crate) fn within_size(basis: &Basis) -> bool {
+    try_within_size(basis).is_ok()
+}
+
+/// checks that result is within the node limit, returns the limit as MathError if not
+pub(crate) fn try_within_size(basis: &Basis) -> Result<(), MathError> {
+    let max_nodes = budget().max_nodes;
+    let nodes = count_nodes(basis, max_nodes + 1);
+    if nodes > max_nodes {
+        let error = MathError::BudgetExceeded(format!("over {} nodes", max_nodes));
+        exceed(error.clone());
+        return Err(error);
+    }
+    Ok(())
+}
+
+/// counts nodes of Basis, stops early once `limit` is reached
+fn count_nodes(basis: &Basis, limit: usize) -> usize {
+    match basis {
+        Basis::BasisLeaf(_) => 1,
+        Basis::BasisNode(BasisNode { operands, .. }) => operands.iter().fold(1, |count, op| {
+            if count >= limit {
+                return count;
+            }
+            count + count_nodes(op, limit - count)
+        }),
+    }
+}
+
+/// runs `operation` against a fresh meter if it is the outermost budgeted call
+/// returns the first limit exceeded while it ran, if any
+pub(crate) fn metered<T>(operation: impl FnOnce() -> T) -> (T, Option<MathError>) {
+    let outermost = METER.with(|meter| meter.depth.get() == 0);
+    if outermost {
+        METER.with(|meter| {
+            meter.steps.set(0);
+            meter.exceeded.replace(None);
+        });
+    }
+    let result = operation();
+    let exceeded = if outermost {
+        METER.with(|meter| meter.exceeded.replace(None))
+    } else {
+        None
+    };
+    (result, exceeded)
+}
@@ -0,0 +1,214 @@
+// std imports
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+// outer crate imports
+use crate::basis::structs::*;
+use crate::game::cards::LimitCard;
+use crate::game::flags::{ALLOW_LIMITS_BEYOND_BOUNDS, FULL_COMPUTE};
+// local imports
+use super::derivative::try_derivative;
+use super::error::{validate, MathError};
+use super::integral::try_integral;
+use super::inverse::try_inverse;
+use super::limits::try_limit;
+use super::logarithm::logarithm;
+
+/// default number of results held by the CAS cache before evicting
+pub const DEFAULT_CACHE_CAPACITY: usize = 1024;
+
+/// CAS operations that can be cached, includes any flags that change the result
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum CasOperation {
+    Derivative,
+    Integral {
+        full_compute: bool,
+    },
+    Inverse,
+    Logarithm,
+    Limit {
+        limit_card: LimitCard,
+        beyond_bounds: bool,
+    },
+}
+
+/// hit/miss statistics of a CasCache
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+}
+
+/// result of a CAS operation as stored in the cache, errors are cached as well
+pub type CasResult = Result<Basis, MathError>;
+
+/// bounded cache of CAS results keyed by operation and structural Basis, evicts oldest entries first
+/// every card goes through it, including inverses not played before, see `apply_card`
+/// `Field::inverses` and `FieldBasis::history` are looked up first and stay game state rather than entries here:
+/// they depend on what was played (inverting back gives the played form, integrating a derivative its constant),
+/// are saved with the game and can't be evicted or shared between games
+#[derive(Debug)]
+pub struct CasCache {
+    capacity: usize,
+    entries: HashMap<(CasOperation, Basis), CasResult>,
+    order: VecDeque<(CasOperation, Basis)>, // insertion order for eviction
+    stats: CacheStats,
+}
+
+impl CasCache {
+    /// creates empty cache holding at most `capacity` results
+    pub fn new(capacity: usize) -> CasCache {
+        CasCache {
+            capacity,
+            entries: HashMap::default(),
+            order: VecDeque::default(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// looks up result of `operation` on `basis`, records hit or miss
+    pub fn get(&mut self, operation: CasOperation, basis: &Basis) -> Option<CasResult> {
+        match self.entries.get(&(operation, basis.clone())) {
+            Some(result) => {
+                self.stats.hits += 1;
+                Some(result.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// stores result of `operation` on `basis`, evicting the oldest entries if over capacity
+    pub fn insert(&mut self, operation: CasOperation, basis: &Basis, result: CasResult) {
+        if self.capacity == 0 {
+            return;
+        }
+        let key = (operation, basis.clone());
+        if self.entries.insert(key.clone(), result).is_none() {
+            self.order.push_back(key);
+        }
+        self.evict();
+        self.stats.entries = self.entries.len();
+    }
+
+    /// drops oldest entries until cache fits within capacity
+    fn evict(&mut self) {
+        while self.entries.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+                self.stats.evictions += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// changes capacity of cache, evicting if new capacity is smaller
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+        self.stats.entries = self.entries.len();
+    }
+
+    /// removes all entries and resets statistics
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.stats = CacheStats::default();
+    }
+
+    /// removes results that stopped on a limit of the CAS budget, another budget may compute them
+    pub fn clear_budget_errors(&mut self) {
+        self.entries.retain(|_, result| {
+            !matches!(
+                result,
+                Err(MathError::RecursionLimit(_) | MathError::BudgetExceeded(_))
+            )
+        });
+        let entries = &self.entries;
+        self.order.retain(|key| entries.contains_key(key));
+        self.stats.entries = self.entries.len();
+    }
+
+    /// getter for hit/miss statistics
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+}
+
+// thread local rather than static mut since native tests call the CAS from parallel threads
+thread_local! {
+    static CAS_CACHE: RefCell<CasCache> = RefCell::new(CasCache::new(DEFAULT_CACHE_CAPACITY));
+}
+
+/// returns cached result of `operation` on `basis` if available, else computes and stores it
+fn cached(
+    operation: CasOperation,
+    basis: &Basis,
+    compute: impl FnOnce(&Basis) -> CasResult,
+) -> CasResult {
+    if let Some(result) = CAS_CACHE.with(|cache| cache.borrow_mut().get(operation, basis)) {
+        return result;
+    }
+    // borrow is released while computing, the CAS may recurse into the cache
+    let result = compute(basis);
+    CAS_CACHE.with(|cache| cache.borrow_mut().insert(operation, basis, result.clone()));
+    result
+}
+
+/// cached wrapper for `try_derivative`
+pub fn cached_derivative(basis: &Basis) -> CasResult {
+    cached(CasOperation::Derivative, basis, try_derivative)
+}
+
+/// cached wrapper for `try_integral`
+pub fn cached_integral(basis: &Basis) -> CasResult {
+    let full_compute = unsafe { FULL_COMPUTE };
+    cached(CasOperation::Integral { full_compute }, basis, try_integral)
+}
+
+/// cached wrapper for `try_inverse`
+pub fn cached_inverse(basis: &Basis) -> CasResult {
+    cached(CasOperation::Inverse, basis, try_inverse)
+}
+
+/// cached wrapper for `logarithm`
+pub fn cached_logarithm(basis: &Basis) -> CasResult {
+    cached(CasOperation::Logarithm, basis, |b| {
+        validate(b)?;
+        Ok(logarithm(b))
+    })
+}
+
+/// cached wrapper for `try_limit`, invalid limits (Err) are cached as well
+pub fn cached_limit(limit_card: &LimitCard, basis: &Basis) -> CasResult {
+    let beyond_bounds = unsafe { ALLOW_LIMITS_BEYOND_BOUNDS };
+    let operation = CasOperation::Limit {
+        limit_card: *limit_card,
+        beyond_bounds,
+    };
+    cached(operation, basis, |b| try_limit(limit_card, b))
+}
+
+/// hit/miss statistics of the CAS cache on this thread
+pub fn cache_stats() -> CacheStats {
+    CAS_CACHE.with(|cache| cache.borrow().stats())
+}
+
+/// empties the CAS cache on this thread
+pub fn clear_cache() {
+    CAS_CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+/// drops cached results of this thread that stopped on a limit of the CAS budget
+pub fn clear_budget_errors() {
+    CAS_CACHE.with(|cache| cache.borrow_mut().clear_budget_errors());
+}
+
+/// sets maximum number of results held by the CAS cache on this thread
+pub fn set_cache_capacity(capacity: usize) {
+    CAS_CACHE.with(|cache| cache.borrow_mut().set_capacity(capacity));
+}
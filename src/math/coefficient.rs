@@ -0,0 +1,573 @@
+// std imports
+use std::collections::BTreeMap;
+use std::f64::consts::{E, PI};
+use std::fmt::{Display, Formatter, Result};
+use std::ops::Neg;
+// outer crate imports
+use crate::basis::{builders::*, structs::*};
+// local imports
+use super::fraction::Fraction;
+// util imports
+use crate::util::{Language, MathMLFlags, ToLatex, ToMathML, ToSpeech};
+
+/// symbolic constants that can scale a Coefficient, all of them positive
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Constant {
+    Root(i32), // prime base of a radical, its exponent is always kept in (0, 1)
+    Log(i32),  // natural logarithm of an integer greater than 1 that is not a perfect power
+    E,
+    Pi,
+}
+
+/// coefficient made of a rational number and a product of symbolic constants raised to rational powers
+/// ie. 2√2 = 2 * 2^(1/2), 3log(2)e^(1/2)
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub struct Coefficient {
+    pub rational: Fraction,
+    pub constants: BTreeMap<Constant, Fraction>, // constant → exponent
+}
+
+impl Coefficient {
+    /// creates Coefficient of e raised to given power
+    pub fn e(pow: Fraction) -> Coefficient {
+        Coefficient::from(1).with_constant(Constant::E, pow)
+    }
+
+    /// creates Coefficient of π
+    pub fn pi() -> Coefficient {
+        Coefficient::from(1).with_constant(Constant::Pi, Fraction::from(1))
+    }
+
+    /// creates Coefficient of natural logarithm of given integer, returns None if not positive
+    pub fn log(n: i32) -> Option<Coefficient> {
+        if n <= 0 {
+            return None;
+        } else if n == 1 {
+            return Some(Coefficient::from(0));
+        }
+        // log(m^k) = klog(m) with the smallest base m
+        let factors = factorize(n);
+        let k = factors
+            .iter()
+            .fold(0, |acc, (_, multiplicity)| gcd(acc, *multiplicity));
+        let m = factors
+            .iter()
+            .map(|(prime, multiplicity)| prime.pow((multiplicity / k) as u32))
+            .product();
+        Some(Coefficient::from(k).with_constant(Constant::Log(m), Fraction::from(1)))
+    }
+
+    /// checks if Coefficient has no symbolic constants
+    pub fn is_rational(&self) -> bool {
+        self.constants.is_empty()
+    }
+
+    /// sign of Coefficient, symbolic constants are always positive
+    pub fn sign(&self) -> i32 {
+        self.rational.sign()
+    }
+
+    /// reciprocal of Coefficient, every constant raised to the negated power
+    pub fn reciprocal(self) -> Coefficient {
+        Coefficient {
+            rational: !self.rational,
+            constants: self
+                .constants
+                .into_iter()
+                .map(|(constant, pow)| (constant, -pow))
+                .collect(),
+        }
+    }
+
+    /// multiplies Coefficients, returns None if the rational part or a radical overflows i32
+    pub fn checked_mul(self, other: Coefficient) -> Option<Coefficient> {
+        let mut constants = self.constants;
+        for (constant, pow) in other.constants {
+            *constants.entry(constant).or_insert(Fraction::from(0)) += pow;
+        }
+        Coefficient {
+            rational: self.rational.checked_mul(other.rational)?,
+            constants,
+        }
+        .normalize()
+    }
+
+    /// divides Coefficients, rationalising radicals in the denominator, returns None on overflow like `checked_mul`
+    pub fn checked_div(self, other: Coefficient) -> Option<Coefficient> {
+        self.checked_mul(other.reciprocal())
+    }
+
+    /// raises Coefficient to rational power, returns None if the result is not real, undefined or overflows
+    pub fn pow(&self, pow: Fraction) -> Option<Coefficient> {
+        let Fraction { n, d } = self.rational;
+        if n == 0 {
+            return if pow > 0 { Some(self.clone()) } else { None };
+        }
+        // even roots of negatives are not real
+        if n < 0 && pow.d % 2 == 0 {
+            return None;
+        }
+
+        // (a/b)^p = Π(prime^(multiplicity * p)) / Π(prime^(multiplicity * p))
+        let sign = if n < 0 && pow.n % 2 != 0 { -1 } else { 1 };
+        let numerator = factorize(n.abs())
+            .into_iter()
+            .map(|(prime, multiplicity)| (prime, pow * multiplicity));
+        let denominator = factorize(d)
+            .into_iter()
+            .map(|(prime, multiplicity)| (prime, -(pow * multiplicity)));
+        let rational = numerator
+            .chain(denominator)
+            .try_fold(Coefficient::from(sign), |acc, (prime, pow)| {
+                acc.checked_mul(Coefficient::root(prime, pow))
+            })?;
+
+        let constants = self
+            .constants
+            .iter()
+            .map(|(constant, exponent)| (*constant, *exponent * pow))
+            .collect();
+        rational.checked_mul(Coefficient {
+            rational: Fraction::from(1),
+            constants,
+        })
+    }
+
+    /// numeric value of Coefficient
+    pub fn to_f64(&self) -> f64 {
+        self.constants.iter().fold(
+            self.rational.n as f64 / self.rational.d as f64,
+            |acc, (constant, pow)| {
+                let base = match constant {
+                    Constant::Root(prime) => *prime as f64,
+                    Constant::Log(n) => (*n as f64).ln(),
+                    Constant::E => E,
+                    Constant::Pi => PI,
+                };
+                acc * base.powf(pow.n as f64 / pow.d as f64)
+            },
+        )
+    }
+
+    /// finds Coefficient of a constant Basis, returns None if it depends on x or is not a product of known constants
+    pub fn from_basis(basis: &Basis) -> Option<Coefficient> {
+        let coefficient = Coefficient::from(basis.coefficient());
+        let value = match basis {
+            Basis::BasisLeaf(BasisLeaf {
+                element: BasisElement::Num,
+                ..
+            }) => Coefficient::from(1),
+            Basis::BasisLeaf(_) => return None,
+            Basis::BasisNode(BasisNode {
+                operator, operands, ..
+            }) => match operator {
+                BasisOperator::Mult => {
+                    operands.iter().try_fold(Coefficient::from(1), |acc, op| {
+                        acc.checked_mul(Coefficient::from_basis(op)?)
+                    })?
+                }
+                BasisOperator::Div => {
+                    let denominator = Coefficient::from_basis(&operands[1])?;
+                    if denominator.rational == 0 {
+                        return None;
+                    }
+                    Coefficient::from_basis(&operands[0])?.checked_mul(denominator.reciprocal())?
+                }
+                BasisOperator::Pow(pow) => Coefficient::from_basis(&operands[0])?.pow(*pow)?,
+                BasisOperator::E => {
+                    let exponent = Coefficient::from_basis(&operands[0])?;
+                    if !exponent.is_rational() {
+                        return None;
+                    }
+                    Coefficient::e(exponent.rational)
+                }
+                BasisOperator::Log => {
+                    let argument = Coefficient::from_basis(&operands[0])?;
+                    if !argument.is_rational() || argument.rational.d != 1 {
+                        return None;
+                    }
+                    Coefficient::log(argument.rational.n)?
+                }
+                // acos(-1) = π, acos(0) = π/2, acos(1) = 0
+                BasisOperator::Acos if operands[0].is_num(-1) => Coefficient::pi(),
+                BasisOperator::Acos if operands[0].is_num(0) => {
+                    Coefficient::pi().checked_div(Coefficient::from(2))?
+                }
+                BasisOperator::Acos if operands[0].is_num(1) => Coefficient::from(0),
+                // asin(±1) = ±π/2, asin(0) = 0
+                BasisOperator::Asin if operands[0].is_num(1) => {
+                    Coefficient::pi().checked_div(Coefficient::from(2))?
+                }
+                BasisOperator::Asin if operands[0].is_num(-1) => {
+                    Coefficient::pi().checked_div(Coefficient::from(-2))?
+                }
+                BasisOperator::Asin if operands[0].is_num(0) => Coefficient::from(0),
+                _ => return None,
+            },
+        };
+        coefficient.checked_mul(value)
+    }
+
+    /// builds canonical Basis of Coefficient with radicals grouped by degree, π is represented as acos(-1)
+    /// nodes are built as is so that the builders can use this to simplify constants
+    pub fn to_basis(&self) -> Basis {
+        let pow_node = |pow: Fraction, base: Basis| {
+            if pow == 1 {
+                return base;
+            }
+            Basis::BasisNode(BasisNode {
+                coefficient: Fraction::from(1),
+                operator: BasisOperator::Pow(pow),
+                operands: vec![base].into(),
+            })
+        };
+
+        let mut factors = self
+            .radicals()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(degree, radicand)| pow_node(Fraction::from((1, degree)), Basis::from(radicand)))
+            .collect::<Vec<Basis>>();
+        for (constant, pow) in self.constants.iter() {
+            match constant {
+                Constant::Root(_) => {}
+                Constant::Log(n) => factors.push(pow_node(*pow, LogBasisNode(&Basis::from(*n)))),
+                Constant::E => factors.push(EBasisNode(&Basis::from(*pow))),
+                Constant::Pi => factors.push(pow_node(*pow, ACosBasisNode(&Basis::from(-1)))),
+            }
+        }
+        factors.sort_by(canonical_order);
+
+        match factors.len() {
+            0 => Basis::from(self.rational),
+            1 => factors[0].with_frac(self.rational),
+            _ => Basis::BasisNode(BasisNode {
+                coefficient: self.rational,
+                operator: BasisOperator::Mult,
+                operands: factors.into(),
+            }),
+        }
+    }
+
+    /// creates Coefficient of prime raised to given power, not normalized
+    fn root(prime: i32, pow: Fraction) -> Coefficient {
+        Coefficient {
+            rational: Fraction::from(1),
+            constants: BTreeMap::from([(Constant::Root(prime), pow)]),
+        }
+    }
+
+    /// returns copy of Coefficient multiplied by constant raised to given power
+    /// only for e, π and logs, which unlike radicals never change the rational part
+    fn with_constant(mut self, constant: Constant, pow: Fraction) -> Coefficient {
+        *self.constants.entry(constant).or_insert(Fraction::from(0)) += pow;
+        self.constants.retain(|_, pow| *pow != 0);
+        self
+    }
+
+    /// drops constants raised to 0 and moves whole powers of radicals into the rational part,
+    /// ie. 2^(3/2) = 2√2, 2^(-1/2) = √2/2, returns None if a power or radicand overflows i32
+    fn normalize(self) -> Option<Coefficient> {
+        if self.rational.n == 0 {
+            return Some(Coefficient::from(0));
+        }
+        let mut rational = self.rational;
+        let mut constants = BTreeMap::new();
+        for (constant, pow) in self.constants {
+            if let Constant::Root(prime) = constant {
+                let whole = pow.n.div_euclid(pow.d);
+                let power = prime.checked_pow(whole.unsigned_abs())?;
+                rational = rational.checked_mul(if whole < 0 {
+                    Fraction::from((1, power))
+                } else {
+                    Fraction::from(power)
+                })?;
+                let remainder = Fraction::from((pow.n.rem_euclid(pow.d), pow.d));
+                if remainder.n != 0 {
+                    constants.insert(constant, remainder);
+                }
+            } else if pow.n != 0 {
+                constants.insert(constant, pow);
+            }
+        }
+        let coefficient = Coefficient {
+            rational,
+            constants,
+        };
+        // radicands are shown grouped, so they have to fit as well
+        coefficient.radicals()?;
+        Some(coefficient)
+    }
+
+    /// radicals grouped by degree into a single radicand, ie. 2^(1/2) * 3^(1/2) = √6
+    /// returns None if a radicand overflows i32, never the case for normalized Coefficients
+    fn radicals(&self) -> Option<BTreeMap<i32, i32>> {
+        let mut radicals = BTreeMap::new();
+        for (constant, pow) in self.constants.iter() {
+            if let Constant::Root(prime) = constant {
+                let radicand = radicals.entry(pow.d).or_insert(1);
+                *radicand = prime.checked_pow(pow.n as u32)?.checked_mul(*radicand)?;
+            }
+        }
+        Some(radicals)
+    }
+}
+
+/// creates rational Coefficient with given fraction
+impl From<Fraction> for Coefficient {
+    fn from(rational: Fraction) -> Self {
+        Coefficient {
+            rational: rational.simplify(),
+            constants: BTreeMap::new(),
+        }
+    }
+}
+/// creates rational Coefficient with given integer
+impl From<i32> for Coefficient {
+    fn from(i: i32) -> Self {
+        Coefficient::from(Fraction::from(i))
+    }
+}
+
+/// multiplies Coefficient by -1
+impl Neg for Coefficient {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Coefficient {
+            rational: -self.rational,
+            constants: self.constants,
+        }
+    }
+}
+
+/// string representation of Coefficient, symbolic constants are separated by a dot
+impl Display for Coefficient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.is_rational() {
+            return write!(f, "{}", self.rational);
+        }
+        let rational = match self.rational {
+            Fraction { n: 1, d: 1 } => String::new(),
+            Fraction { n: -1, d: 1 } => String::from("-"),
+            Fraction { n, d: 1 } => format!("{}", n),
+            _ => format!("({})", self.rational),
+        };
+        let exponent = |pow: &Fraction| match pow {
+            Fraction { n: 1, d: 1 } => String::new(),
+            Fraction { n, d: 1 } => format!("^{}", n),
+            _ => format!("^({})", pow),
+        };
+
+        let mut symbols = self
+            .radicals()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(degree, radicand)| match degree {
+                2 => format!("√{}", radicand),
+                3 => format!("∛{}", radicand),
+                4 => format!("∜{}", radicand),
+                _ => format!("{}^(1/{})", radicand, degree),
+            })
+            .collect::<Vec<String>>();
+        for (constant, pow) in self.constants.iter() {
+            match constant {
+                Constant::Root(_) => {}
+                Constant::Log(n) => symbols.push(format!("log({}){}", n, exponent(pow))),
+                Constant::E => symbols.push(format!("e{}", exponent(pow))),
+                Constant::Pi => symbols.push(format!("π{}", exponent(pow))),
+            }
+        }
+        write!(f, "{}{}", rational, symbols.join("·"))
+    }
+}
+
+/// LaTeX representation of Coefficient, does not display if coefficient = 1 like Fraction
+impl ToLatex for Coefficient {
+    fn to_latex(&self) -> String {
+        let exponent = |pow: &Fraction| match pow {
+            Fraction { n: 1, d: 1 } => String::new(),
+            _ => format!("^{{{}}}", pow),
+        };
+
+        let mut latex = self.rational.to_latex();
+        for (degree, radicand) in self.radicals().unwrap_or_default() {
+            latex += &match degree {
+                2 => format!("\\sqrt{{{}}}", radicand),
+                _ => format!("\\sqrt[{}]{{{}}}", degree, radicand),
+            };
+        }
+        for (constant, pow) in self.constants.iter() {
+            latex += &match constant {
+                Constant::Root(_) => String::new(),
+                Constant::Log(n) => {
+                    format!("{}{}({})", BasisOperator::Log.to_latex(), exponent(pow), n)
+                }
+                Constant::E => format!("e{}", exponent(pow)),
+                Constant::Pi => format!("\\pi{}", exponent(pow)),
+            };
+        }
+        latex
+    }
+}
+
+/// MathML representation of Coefficient, does not display if coefficient = 1 like Fraction
+impl ToMathML for Coefficient {
+    fn to_mathml_with(&self, flags: MathMLFlags) -> String {
+        let power = |base: String, pow: &Fraction| match pow {
+            Fraction { n: 1, d: 1 } => base,
+            _ => format!(
+                "<msup>{}<mrow>{}</mrow></msup>",
+                base,
+                pow.to_mathml_with(flags)
+            ),
+        };
+
+        let mut mathml = self.rational.to_mathml_with(flags);
+        for (degree, radicand) in self.radicals().unwrap_or_default() {
+            mathml += &match degree {
+                2 => format!("<msqrt><mn>{}</mn></msqrt>", radicand),
+                _ => format!("<mroot><mn>{}</mn><mn>{}</mn></mroot>", radicand, degree),
+            };
+        }
+        for (constant, pow) in self.constants.iter() {
+            mathml += &match constant {
+                Constant::Root(_) => String::new(),
+                Constant::Log(n) => format!(
+                    "{}<mo>&#x2061;</mo><mrow><mo>(</mo><mn>{}</mn><mo>)</mo></mrow>",
+                    power(BasisOperator::Log.to_mathml_with(flags), pow),
+                    n
+                ),
+                Constant::E => power(String::from("<mi>e</mi>"), pow),
+                Constant::Pi => power(String::from("<mi>π</mi>"), pow),
+            };
+        }
+        mathml
+    }
+}
+
+/// spoken Coefficient, factors joined by times, ie. 2 times the square root of 3
+impl ToSpeech for Coefficient {
+    fn to_speech(&self, language: Language) -> String {
+        if self.is_rational() {
+            return self.rational.to_speech(language);
+        }
+        let power = |base: String, pow: &Fraction| match language {
+            _ if *pow == 1 => base,
+            Language::English => format!("{} to the power of {}", base, pow.to_speech(language)),
+            Language::Japanese => format!("{}の{}乗", base, pow.to_speech(language)),
+        };
+
+        let mut factors = vec![];
+        if self.rational != 1 && self.rational != -1 {
+            factors.push(self.rational.to_speech(language));
+        }
+        for (degree, radicand) in self.radicals().unwrap_or_default() {
+            factors.push(match (language, degree) {
+                (Language::English, 2) => format!("the square root of {}", radicand),
+                (Language::English, 3) => format!("the cube root of {}", radicand),
+                (Language::English, _) => format!("the {}th root of {}", degree, radicand),
+                (Language::Japanese, 2) => format!("ルート{}", radicand),
+                (Language::Japanese, _) => format!("{}の{}乗根", radicand, degree),
+            });
+        }
+        for (constant, pow) in self.constants.iter() {
+            let base = match (constant, language) {
+                (Constant::Root(_), _) => continue,
+                (Constant::Log(n), Language::English) => format!("the natural log of {}", n),
+                (Constant::Log(n), Language::Japanese) => format!("{}の自然対数", n),
+                (Constant::E, Language::English) => String::from("e"),
+                (Constant::E, Language::Japanese) => String::from("イー"),
+                (Constant::Pi, Language::English) => String::from("pi"),
+                (Constant::Pi, Language::Japanese) => String::from("パイ"),
+            };
+            factors.push(power(base, pow));
+        }
+        let speech = factors.join(match language {
+            Language::English => " times ",
+            Language::Japanese => "かける",
+        });
+        match (self.rational == -1, language) {
+            (false, _) => speech,
+            (true, Language::English) => format!("minus {}", speech),
+            (true, Language::Japanese) => format!("マイナス{}", speech),
+        }
+    }
+}
+
+/// splits Basis into its constant factor and the remaining function of x, ie. √2xe^x → (√2, xe^x)
+pub fn split_constant(basis: &Basis) -> (Coefficient, Basis) {
+    if let Some(constant) = Coefficient::from_basis(basis) {
+        return (constant, Basis::from(1));
+    }
+    match basis {
+        Basis::BasisNode(BasisNode {
+            coefficient,
+            operator: BasisOperator::Mult,
+            operands,
+        }) => {
+            let mut constant = Coefficient::from(*coefficient);
+            let mut factors = vec![];
+            for op in operands.iter() {
+                // constants whose product overflows stay with the rest, ie. √46349√46351
+                match Coefficient::from_basis(op)
+                    .and_then(|op_constant| constant.clone().checked_mul(op_constant))
+                {
+                    Some(product) => constant = product,
+                    None => factors.push(op.clone()),
+                }
+            }
+            (constant, MultBasisNode(factors))
+        }
+        Basis::BasisNode(BasisNode {
+            coefficient,
+            operator: BasisOperator::Div,
+            operands,
+        }) => {
+            let (numerator_constant, numerator) = split_constant(&operands[0]);
+            let (denominator_constant, denominator) = split_constant(&operands[1]);
+            match numerator_constant
+                .checked_div(denominator_constant)
+                .and_then(|constant| constant.checked_mul(Coefficient::from(*coefficient)))
+            {
+                Some(constant) => (constant, numerator / denominator),
+                // leave the quotient as it is if its constant overflows
+                None => (Coefficient::from(*coefficient), basis.with_coefficient(1)),
+            }
+        }
+        _ => (
+            Coefficient::from(basis.coefficient()),
+            basis.with_coefficient(1),
+        ),
+    }
+}
+
+/// prime factors of positive integer with their multiplicity, ie. 12 → [(2, 2), (3, 1)]
+fn factorize(mut n: i32) -> Vec<(i32, i32)> {
+    let mut factors = vec![];
+    let mut prime = 2;
+    while prime <= n / prime {
+        let mut multiplicity = 0;
+        while n % prime == 0 {
+            n /= prime;
+            multiplicity += 1;
+        }
+        if multiplicity > 0 {
+            factors.push((prime, multiplicity));
+        }
+        prime += 1;
+    }
+    if n > 1 {
+        factors.push((n, 1));
+    }
+    factors
+}
+
+/// greatest common divisor of multiplicities
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
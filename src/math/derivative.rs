@@ -1,15 +1,61 @@
 // outer crate imports
 use crate::basis::{builders::*, structs::*};
 // local imports
+use super::budget::{metered, try_enter, try_within_size};
+use super::error::{validate, MathError};
 use super::util::*;
 
+/// finds the derivative of the given Basis, returns MathError instead of panicking on invalid input
+/// or exceeding the CAS budget
+pub fn try_derivative(basis: &Basis) -> Result<Basis, MathError> {
+    try_partial_derivative(basis, 'x')
+}
+
 /// finds the derivative of the given Basis
 pub fn derivative(basis: &Basis) -> Basis {
-    return match basis {
+    partial_derivative(basis, 'x')
+}
+
+/// finds the partial derivative of the given Basis with respect to variable `var`, other variables are constants
+/// not budgeted since a derivative has no unevaluated form, use `try_partial_derivative` for untrusted input
+pub fn partial_derivative(basis: &Basis, var: char) -> Basis {
+    basis.fold(&mut |basis: &Basis, derivatives: Vec<Basis>| {
+        derivative_rule(basis, derivatives, var)
+    })
+}
+
+/// finds the partial derivative within the CAS budget, returns MathError on invalid input or once it is exceeded
+pub fn try_partial_derivative(basis: &Basis, var: char) -> Result<Basis, MathError> {
+    validate(basis)?;
+    let (result, exceeded) = metered(|| {
+        basis.fold(
+            &mut |basis: &Basis, derivatives: Vec<Result<Basis, MathError>>| {
+                let derivatives = derivatives.into_iter().collect::<Result<Vec<Basis>, _>>()?;
+                let _guard = try_enter()?;
+                let derivative = derivative_rule(basis, derivatives, var);
+                try_within_size(&derivative)?;
+                Ok(derivative)
+            },
+        )
+    });
+    match exceeded {
+        Some(error) => Err(error),
+        None => result,
+    }
+}
+
+/// derivative of `basis` given the derivatives of its operands, one step of `partial_derivative`
+fn derivative_rule(basis: &Basis, derivatives: Vec<Basis>, var: char) -> Basis {
+    if var != 'x' && !basis.depends_on(var) {
+        return Basis::from(0);
+    }
+    match basis {
         // is standard basis
         Basis::BasisLeaf(basis_leaf) => match basis_leaf.element {
-            BasisElement::X => Basis::from(basis_leaf.coefficient),
-            BasisElement::Num => Basis::from(0),
+            BasisElement::X | BasisElement::Var(_) if basis.is_var(var) => {
+                Basis::from(basis_leaf.coefficient)
+            }
+            BasisElement::X | BasisElement::Var(_) | BasisElement::Num => Basis::from(0),
             BasisElement::Inf => basis.clone(),
         },
 
@@ -20,61 +66,90 @@ pub fn derivative(basis: &Basis) -> Basis {
             operands,
         }) => match operator {
             // chain rule, f'(x) = x' * (f')(x)
-            BasisOperator::Add => AddBasisNode(operands.iter().map(|op| derivative(&op)).collect()),
-            BasisOperator::Minus => {
-                MinusBasisNode(operands.iter().map(|op| derivative(&op)).collect())
-            }
-            // product rule, udv + vdu
+            BasisOperator::Add => AddBasisNode(derivatives),
+            BasisOperator::Minus => MinusBasisNode(derivatives),
+            // product rule, d(uvw) = (du)vw + u(dv)w + uv(dw)
             BasisOperator::Mult => {
-                let u = &operands[0];
-                let v = MultBasisNode(operands[1..].to_vec()); // expensive
-
-                (u.clone() * derivative(&v)) + (v.clone() * derivative(u))
+                AddBasisNode(
+                    (0..operands.len())
+                        .map(|i| {
+                            let mut factors = operands.to_vec();
+                            factors[i] = derivatives[i].clone();
+                            MultBasisNode(factors)
+                        })
+                        .collect(),
+                ) * *coefficient
             }
             // quotient rule, (vdu - udv) / uu
             BasisOperator::Div => {
                 let u = &operands[0];
                 let v = &operands[1];
-                (((v.clone() * derivative(&u)) - (u.clone() * derivative(&v))) * coefficient.n)
+                (((v.clone() * derivatives[0].clone()) - (u.clone() * derivatives[1].clone()))
+                    * coefficient.n)
                     / (v.clone() * v.clone() * coefficient.d)
             }
             // power rule, n * x^(n-1)
             BasisOperator::Pow(n) => {
                 let u = operands[0].clone();
-                derivative(&u) * (u ^ (*n - 1)) * (*n * *coefficient)
+                derivatives[0].clone() * (u ^ (*n - 1)) * (*n * *coefficient)
             }
             // chain rule, f'(e^f(y)) = f'(y)e^f(y)
-            BasisOperator::E => derivative(&operands[0]) * EBasisNode(&operands[0]) * *coefficient,
+            BasisOperator::E => derivatives[0].clone() * EBasisNode(&operands[0]) * *coefficient,
             // log rule, f'(log(f(x))) = f'(x)/f(x)
             BasisOperator::Log => {
                 let u = operands[0].clone();
-                (derivative(&u) * coefficient.n) / (u * coefficient.d)
+                (derivatives[0].clone() * coefficient.n) / (u * coefficient.d)
             }
             // chain rule, f'(cos(f(y))) = -f'(y)sin(f(y))
             BasisOperator::Cos => {
-                derivative(&operands[0]) * -SinBasisNode(&operands[0]) * *coefficient
+                derivatives[0].clone() * -SinBasisNode(&operands[0]) * *coefficient
             }
             // chain rule, f'(sin(f(y))) = f'(y)cos(f(y))
             BasisOperator::Sin => {
-                derivative(&operands[0]) * CosBasisNode(&operands[0]) * *coefficient
+                derivatives[0].clone() * CosBasisNode(&operands[0]) * *coefficient
             }
             // d/dx arccos(f(x)) = -f'(x)/sqrt(1-f(x)^2)
             BasisOperator::Acos => {
-                (-operands[0].clone() * coefficient.n)
+                (-derivatives[0].clone() * coefficient.n)
                     / (((Basis::from(1) - (operands[0].clone() ^ 2)) ^ (1, 2)) * coefficient.d)
             }
             // d/dx arcsin(f(x)) = f'(x)/sqrt(1-f(x)^2)
             BasisOperator::Asin => {
-                (operands[0].clone() * coefficient.n)
+                (derivatives[0].clone() * coefficient.n)
                     / (((Basis::from(1) - (operands[0].clone() ^ 2)) ^ (1, 2)) * coefficient.d)
             }
             // inverse rule, d(f-1(x)) = 1/f-1(f')(f-1(x))
-            BasisOperator::Inv => {
-                let inverse_derivative = !derivative(&operands[0]);
+            BasisOperator::Inv if var == 'x' => {
+                let inverse_derivative = !derivatives[0].clone();
                 function_composition(&inverse_derivative, &operands[0]) / coefficient.d
                     * coefficient.n
             }
-            BasisOperator::Int => operands[0].clone() * *coefficient,
+            // implicit differentiation of f(g, y) = x, dg/dy = -(df/dy)(g) / (df/dx)(g)
+            BasisOperator::Inv => {
+                let inverse = basis.with_coefficient(1);
+                -function_composition(&derivatives[0], &inverse)
+                    / function_composition(&derivative(&operands[0]), &inverse)
+                    * *coefficient
+            }
+            BasisOperator::Int if var == 'x' => operands[0].clone() * *coefficient,
+            // differentiation under the integral sign, d/dy I(f) = I(df/dy)
+            BasisOperator::Int => IntBasisNode(&derivatives[0]) * *coefficient,
         },
-    };
+    }
+}
+
+/// gradient of the given Basis, its partial derivatives with respect to each variable in `vars`
+pub fn gradient(basis: &Basis, vars: &[char]) -> Vec<Basis> {
+    vars.iter()
+        .map(|var| partial_derivative(basis, *var))
+        .collect()
+}
+
+/// Laplacian of the given Basis, the sum of its second partial derivatives over `vars`
+pub fn laplacian(basis: &Basis, vars: &[char]) -> Basis {
+    AddBasisNode(
+        vars.iter()
+            .map(|var| partial_derivative(&partial_derivative(basis, *var), *var))
+            .collect(),
+    )
 }
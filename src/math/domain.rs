@@ -0,0 +1,253 @@
+// outer crate imports
+use crate::basis::structs::*;
+// local imports
+use super::budget::{metered, try_enter};
+use super::error::MathError;
+use super::eval::evaluate;
+use super::interval::{Interval, IntervalSet};
+use super::inverse::inverse_branch;
+use super::solve::{solve, Root};
+
+/// periodic roots are only expanded within [-ROOT_WINDOW, ROOT_WINDOW] by `domain`
+pub(crate) const ROOT_WINDOW: f64 = 100.0;
+/// values closer to 0 than this at a critical point are treated as 0
+const ZERO_TOLERANCE: f64 = 1e-9;
+
+/// set of real x where Basis is defined, the Inf leaf is defined everywhere
+/// periodic restrictions (ie. 1/sin(x)) are only exact within ROOT_WINDOW of 0,
+/// returns ℝ if the CAS budget is exceeded, `try_domain` reports it instead
+pub fn domain(basis: &Basis) -> IntervalSet {
+    domain_with(basis, true, Some(ROOT_WINDOW)).unwrap_or_else(|_| IntervalSet::real())
+}
+
+/// set of real x where Basis is defined, returns MathError if the CAS budget is exceeded
+/// or if it is restricted at infinitely many points
+pub fn try_domain(basis: &Basis) -> Result<IntervalSet, MathError> {
+    domain_with(basis, true, None)
+}
+
+/// set of real x where Basis is defined within the CAS budget, optionally ignoring the [-1, 1] bound of acos and asin
+/// periodic roots are expanded within [-window, window], or are Unsupported if there is no window
+pub(crate) fn domain_with(
+    basis: &Basis,
+    inverse_trig_bounds: bool,
+    window: Option<f64>,
+) -> Result<IntervalSet, MathError> {
+    match metered(|| domain_of(basis, inverse_trig_bounds, window)) {
+        (_, Some(error)) => Err(error),
+        (result, None) => result,
+    }
+}
+
+/// domain rules, recurses through the operands so that every node is budgeted
+fn domain_of(
+    basis: &Basis,
+    inverse_trig_bounds: bool,
+    window: Option<f64>,
+) -> Result<IntervalSet, MathError> {
+    let (operator, operands) = match basis {
+        Basis::BasisLeaf(_) => return Ok(IntervalSet::real()),
+        Basis::BasisNode(BasisNode {
+            operator, operands, ..
+        }) => (operator, operands),
+    };
+    let _guard = try_enter()?;
+    let mut set = IntervalSet::real();
+    for op in operands.iter() {
+        set = set.intersect(&domain_of(op, inverse_trig_bounds, window)?);
+    }
+    if set.is_empty() {
+        return Ok(set);
+    }
+
+    Ok(match operator {
+        // log(f(x)) needs f(x) > 0
+        BasisOperator::Log => restrict(&operands[0], &set, &[0], |v| v > 0.0, window)?,
+        // even roots need f(x) >= 0, negative powers need f(x) != 0
+        BasisOperator::Pow(pow) if pow.d % 2 == 0 && pow.n < 0 => {
+            restrict(&operands[0], &set, &[0], |v| v > 0.0, window)?
+        }
+        BasisOperator::Pow(pow) if pow.d % 2 == 0 => {
+            restrict(&operands[0], &set, &[0], |v| v >= 0.0, window)?
+        }
+        BasisOperator::Pow(pow) if pow.n < 0 => {
+            restrict(&operands[0], &set, &[0], |v| v != 0.0, window)?
+        }
+        BasisOperator::Div => restrict(&operands[1], &set, &[0], |v| v != 0.0, window)?,
+        BasisOperator::Acos | BasisOperator::Asin if inverse_trig_bounds => restrict(
+            &operands[0],
+            &set,
+            &[-1, 1],
+            |v| (-1.0..=1.0).contains(&v),
+            window,
+        )?,
+        // f-1(x) is defined on the image of the branch of f it inverts
+        BasisOperator::Inv => match inverse_branch(&operands[0]) {
+            Ok(branch) => set.intersect(&branch.domain),
+            Err(_) => set,
+        },
+        _ => set,
+    })
+}
+
+/// sign of Basis if it is the same on all of `interval`, 0 if Basis is 0 there
+/// returns None if the sign changes, Basis is undefined somewhere on `interval`, or `interval` is empty
+pub fn sign_on(basis: &Basis, interval: &Interval) -> Option<i32> {
+    if basis.is_inf(1) || basis.is_inf(-1) {
+        return Some(basis.coefficient().sign());
+    }
+    let set = IntervalSet::from(*interval);
+    // roots beyond a bounded interval don't matter, periodic ones are Unsupported otherwise
+    let window = Some(interval.lower.abs().max(interval.upper.abs())).filter(|w| w.is_finite());
+    if set.is_empty() || domain_with(basis, true, window).ok()?.intersect(&set) != set {
+        return None;
+    }
+    [
+        (1, preimage(basis, &set, &[0], |v| v > 0.0, window).ok()?),
+        (-1, preimage(basis, &set, &[0], |v| v < 0.0, window).ok()?),
+        (0, preimage(basis, &set, &[0], |v| v == 0.0, window).ok()?),
+    ]
+    .iter()
+    .find(|(_, subset)| *subset == set)
+    .map(|(sign, _)| *sign)
+}
+
+/// restricts `set` to where the value of `operand` is accepted, unless it cannot be evaluated (ie. Int, Inv, other variables)
+fn restrict(
+    operand: &Basis,
+    set: &IntervalSet,
+    levels: &[i32],
+    accept: impl Fn(f64) -> bool,
+    window: Option<f64>,
+) -> Result<IntervalSet, MathError> {
+    let evaluable = !operand
+        .contains(|node| node.is_node(BasisOperator::Int) || node.is_node(BasisOperator::Inv))
+        && operand.variables().iter().all(|var| *var == 'x');
+    if !evaluable {
+        return Ok(set.clone());
+    }
+    preimage(operand, set, levels, accept, window)
+}
+
+/// points of `set` where `accept` holds for the value of Basis, the value can only change
+/// acceptance at solutions of basis = level for each level, or where Basis is undefined
+fn preimage(
+    basis: &Basis,
+    set: &IntervalSet,
+    levels: &[i32],
+    accept: impl Fn(f64) -> bool,
+    window: Option<f64>,
+) -> Result<IntervalSet, MathError> {
+    let range = match (window, set.intervals()) {
+        (Some(window), _) => (-window, window),
+        (None, [first, .., last]) | (None, [first @ last]) => (first.lower, last.upper),
+        (None, []) => return Ok(IntervalSet::empty()),
+    };
+    let critical = critical_points(basis, levels, range)?;
+    let mut pieces = vec![];
+    for interval in set.intervals() {
+        let points = split(interval, &critical);
+        // value is the same between consecutive critical points
+        for piece in points.windows(2) {
+            if evaluate(basis, middle(piece[0], piece[1])).is_some_and(&accept) {
+                pieces.push(Interval::open(piece[0], piece[1]));
+            }
+        }
+        for point in points.iter().filter(|x| interval.contains(**x)) {
+            let value =
+                evaluate(basis, *point).map(|v| if v.abs() < ZERO_TOLERANCE { 0.0 } else { v });
+            if value.is_some_and(&accept) {
+                pieces.push(Interval::closed(*point, *point));
+            }
+        }
+    }
+    Ok(IntervalSet::from(pieces))
+}
+
+/// open pieces of `set` between consecutive roots of Basis, with the sign of Basis on each piece
+/// periodic roots are only split at within ROOT_WINDOW of 0
+pub(crate) fn sign_pieces(basis: &Basis, set: &IntervalSet) -> Vec<(Interval, i32)> {
+    // a bounded range always expands periodic roots
+    let critical = critical_points(basis, &[0], (-ROOT_WINDOW, ROOT_WINDOW)).unwrap_or_default();
+    let mut pieces = vec![];
+    for interval in set.intervals() {
+        for piece in split(interval, &critical).windows(2) {
+            if let Some(value) = evaluate(basis, middle(piece[0], piece[1])) {
+                let sign = if value.abs() < ZERO_TOLERANCE {
+                    0
+                } else {
+                    value.signum() as i32
+                };
+                pieces.push((Interval::open(piece[0], piece[1]), sign));
+            }
+        }
+    }
+    pieces
+}
+
+/// solutions of basis = level for each level, periodic ones are listed within `range`
+fn critical_points(
+    basis: &Basis,
+    levels: &[i32],
+    range: (f64, f64),
+) -> Result<Vec<f64>, MathError> {
+    let mut points = vec![];
+    for level in levels {
+        points.extend(root_values(
+            &solve(&(basis.clone() - Basis::from(*level))).roots,
+            range,
+        )?);
+    }
+    Ok(points)
+}
+
+/// endpoints of `interval` and the critical points inside it, sorted
+fn split(interval: &Interval, critical: &[f64]) -> Vec<f64> {
+    let mut points = critical
+        .iter()
+        .copied()
+        .filter(|x| interval.lower < *x && *x < interval.upper)
+        .chain([interval.lower, interval.upper])
+        .collect::<Vec<f64>>();
+    points.sort_by(|a, b| a.total_cmp(b));
+    points.dedup_by(|a, b| (*a - *b).abs() < ZERO_TOLERANCE);
+    points
+}
+
+/// point inside the open interval (a, b), which may be unbounded
+fn middle(a: f64, b: f64) -> f64 {
+    match (a.is_finite(), b.is_finite()) {
+        (true, true) => (a + b) / 2.0,
+        (true, false) => a + 1.0,
+        (false, true) => b - 1.0,
+        (false, false) => 0.0,
+    }
+}
+
+/// numeric values of roots, families are expanded within `range`
+/// returns Unsupported for a family if `range` is unbounded, since it has infinitely many roots there
+fn root_values(roots: &[Root], (lower, upper): (f64, f64)) -> Result<Vec<f64>, MathError> {
+    let mut values = vec![];
+    for root in roots {
+        match root {
+            Root::Family(base, period) => match (evaluate(base, 0.0), evaluate(period, 0.0)) {
+                (Some(_), Some(period))
+                    if period > 0.0 && !(lower.is_finite() && upper.is_finite()) =>
+                {
+                    return Err(MathError::Unsupported(format!(
+                        "infinitely many roots at {}",
+                        root
+                    )));
+                }
+                (Some(base), Some(period)) if period > 0.0 => {
+                    let first = ((lower - base) / period).ceil() as i64;
+                    let last = ((upper - base) / period).floor() as i64;
+                    values.extend((first..=last).map(|k| base + k as f64 * period));
+                }
+                _ => {}
+            },
+            _ => values.extend(root.value()),
+        }
+    }
+    Ok(values)
+}
@@ -0,0 +1,125 @@
+// outer crate imports
+use crate::basis::structs::*;
+// local imports
+use super::derivative::derivative;
+use super::eval::{evaluate, evaluate_exact, sample_points};
+use super::fraction::Fraction;
+use super::metrics::polynomial_degree;
+
+/// number of random points each comparison samples
+const SAMPLE_COUNT: usize = 12;
+/// number of points both bases must be defined at before they are considered likely equivalent
+const MIN_SAMPLES: usize = 6;
+/// relative error allowed between numeric evaluations
+const TOLERANCE: f64 = 1e-9;
+/// highest polynomial degree proven with exact evaluation, larger degrees overflow anyway
+const MAX_EXACT_DEGREE: i32 = 32;
+
+/// result of comparing two Bases as functions of x
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum Equivalence {
+    Proven,    // equal after simplification, or polynomials agreeing at enough exact points
+    Likely,    // agree at every numeric sample point
+    Different, // disagree at some sample point, or too few points to compare
+}
+
+/// checks if `a` and `b` are the same function of x
+pub fn equivalent(a: &Basis, b: &Basis) -> Equivalence {
+    if a == b || (a.clone() - b.clone()).is_num(0) {
+        return Equivalence::Proven;
+    }
+
+    // polynomials of degree n agreeing at n + 1 points are equal
+    if let (Some(a_degree), Some(b_degree)) = (polynomial_degree_of(a), polynomial_degree_of(b)) {
+        let points = sample_points(&[a, b], a_degree.max(b_degree) as usize + 1);
+        let values = points
+            .iter()
+            .map(|x| Some((evaluate_exact(a, *x)?, evaluate_exact(b, *x)?)))
+            .collect::<Option<Vec<(Fraction, Fraction)>>>();
+        if let Some(values) = values {
+            if values.iter().all(|(a_value, b_value)| a_value == b_value) {
+                return Equivalence::Proven;
+            }
+            return Equivalence::Different;
+        }
+    }
+
+    numeric_equivalence(a, b, close)
+}
+
+/// checks if `a` and `b` differ only by a constant, ie. are antiderivatives of the same function
+pub fn equivalent_up_to_constant(a: &Basis, b: &Basis) -> Equivalence {
+    equivalent(&derivative(a), &derivative(b))
+}
+
+/// checks if `a` is a constant multiple of `b`, ie. the two are linearly dependent
+pub fn scalar_multiple(a: &Basis, b: &Basis) -> Equivalence {
+    if a.with_coefficient(1) == b.with_coefficient(1) {
+        return Equivalence::Proven;
+    }
+
+    // ratio taken from the first point where both are defined and non zero
+    let mut ratio = None;
+    numeric_equivalence(a, b, |a_value, b_value| {
+        if b_value == 0.0 {
+            return a_value == 0.0;
+        }
+        match ratio {
+            None => {
+                ratio = Some(a_value / b_value);
+                a_value != 0.0
+            }
+            Some(ratio) => close(a_value, ratio * b_value),
+        }
+    })
+}
+
+/// degree of Basis if it is a polynomial with non-negative integer powers only, up to MAX_EXACT_DEGREE
+fn polynomial_degree_of(basis: &Basis) -> Option<i32> {
+    let has_non_polynomial_node = basis.contains(|node| match node {
+        Basis::BasisNode(BasisNode {
+            operator: BasisOperator::Div,
+            ..
+        }) => true,
+        Basis::BasisNode(BasisNode {
+            operator: BasisOperator::Pow(pow),
+            ..
+        }) => pow.n < 0 || pow.d != 1,
+        _ => false,
+    });
+    if has_non_polynomial_node {
+        return None;
+    }
+    let degree = polynomial_degree(basis)?;
+    if degree.d == 1 && degree.n >= 0 && degree.n <= MAX_EXACT_DEGREE {
+        return Some(degree.n);
+    }
+    None
+}
+
+/// compares `a` and `b` at random sample points with given comparison
+fn numeric_equivalence(
+    a: &Basis,
+    b: &Basis,
+    mut agree: impl FnMut(f64, f64) -> bool,
+) -> Equivalence {
+    let mut compared = 0;
+    for x in sample_points(&[a, b], SAMPLE_COUNT) {
+        let x = x.n as f64 / x.d as f64;
+        match (evaluate(a, x), evaluate(b, x)) {
+            (None, None) => continue,
+            (Some(a_value), Some(b_value)) if agree(a_value, b_value) => compared += 1,
+            _ => return Equivalence::Different,
+        }
+    }
+    if compared >= MIN_SAMPLES {
+        Equivalence::Likely
+    } else {
+        Equivalence::Different
+    }
+}
+
+/// checks if two floats are equal within relative TOLERANCE
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() <= TOLERANCE * a.abs().max(b.abs()).max(1.0)
+}
@@ -0,0 +1,124 @@
+// std imports
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+// outer crate imports
+use crate::basis::structs::*;
+// local imports
+use super::fraction::Fraction;
+
+/// largest coefficient or exponent magnitude accepted, keeps products of two coefficients within i32
+pub const MAX_COEFFICIENT: i32 = 1 << 15;
+/// deepest nesting of BasisNodes accepted by the fallible CAS functions
+pub const MAX_DEPTH: usize = 64;
+
+/// errors returned by the fallible CAS functions instead of panicking
+#[derive(Clone, Debug, Hash, Eq, PartialEq)]
+pub enum MathError {
+    Unsupported(String),    // operation is not implemented for this Basis
+    DomainError(String),    // Basis is outside the domain of the operation (ie. 1/0, log(-INF))
+    Overflow(String),       // coefficient or exponent too large to compute with
+    RecursionLimit(usize),  // depth at which computation was stopped
+    BudgetExceeded(String), // step or node limit of the CAS budget reached
+    Malformed(String),      // BasisNode has wrong number of operands or invalid leaf
+}
+
+/// string representation of MathError, used for logging
+impl Display for MathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            MathError::Unsupported(message) => write!(f, "Unsupported: {}", message),
+            MathError::DomainError(message) => write!(f, "Domain error: {}", message),
+            MathError::Overflow(message) => write!(f, "Overflow: {}", message),
+            MathError::RecursionLimit(depth) => write!(f, "Recursion limit reached at {}", depth),
+            MathError::BudgetExceeded(message) => write!(f, "Budget exceeded: {}", message),
+            MathError::Malformed(message) => write!(f, "Malformed Basis: {}", message),
+        }
+    }
+}
+
+impl Error for MathError {}
+
+/// checks that fraction has non-zero denominator and fits within MAX_COEFFICIENT
+fn validate_fraction(frac: &Fraction, basis: &Basis) -> std::result::Result<(), MathError> {
+    if frac.d == 0 {
+        return Err(MathError::DomainError(format!(
+            "zero denominator in {}",
+            basis
+        )));
+    }
+    if frac.n.abs() > MAX_COEFFICIENT || frac.d.abs() > MAX_COEFFICIENT {
+        return Err(MathError::Overflow(format!("{} in {:?}", frac, basis)));
+    }
+    Ok(())
+}
+
+/// checks that Basis is well formed before handing it to the CAS
+/// rejects wrong operand counts, invalid INF, zero denominators, oversized coefficients, and deep nesting
+pub fn validate(basis: &Basis) -> std::result::Result<(), MathError> {
+    validate_depth(basis, 0)
+}
+
+fn validate_depth(basis: &Basis, depth: usize) -> std::result::Result<(), MathError> {
+    if depth > MAX_DEPTH {
+        return Err(MathError::RecursionLimit(depth));
+    }
+
+    match basis {
+        Basis::BasisLeaf(basis_leaf) => {
+            validate_fraction(&basis_leaf.coefficient, basis)?;
+            if basis_leaf.element == BasisElement::Inf
+                && !(basis_leaf.coefficient == 1 || basis_leaf.coefficient == -1)
+            {
+                return Err(MathError::Malformed(format!(
+                    "INF with coefficient {}",
+                    basis_leaf.coefficient
+                )));
+            }
+            Ok(())
+        }
+        Basis::BasisNode(BasisNode {
+            coefficient,
+            operator,
+            operands,
+        }) => {
+            validate_fraction(coefficient, basis)?;
+            let arity_ok = match operator {
+                BasisOperator::Add | BasisOperator::Minus | BasisOperator::Mult => {
+                    !operands.is_empty()
+                }
+                BasisOperator::Div => operands.len() == 2,
+                _ => operands.len() == 1,
+            };
+            if !arity_ok {
+                return Err(MathError::Malformed(format!(
+                    "{} with {} operands",
+                    operator,
+                    operands.len()
+                )));
+            }
+
+            match operator {
+                BasisOperator::Div if operands[1].is_num(0) => {
+                    return Err(MathError::DomainError(format!(
+                        "division by zero in {}",
+                        basis
+                    )));
+                }
+                BasisOperator::Pow(pow) => {
+                    validate_fraction(pow, basis)?;
+                    if pow.n < 0 && operands[0].is_num(0) {
+                        return Err(MathError::DomainError(format!(
+                            "division by zero in {}",
+                            basis
+                        )));
+                    }
+                }
+                _ => {}
+            }
+
+            operands
+                .iter()
+                .try_for_each(|op| validate_depth(op, depth + 1))
+        }
+    }
+}
@@ -0,0 +1,177 @@
+// std imports
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+// outer crate imports
+use crate::basis::structs::*;
+// local imports
+use super::fraction::Fraction;
+
+/// rational number with wide integers so that intermediate products don't overflow
+pub(crate) type Rational = (i128, i128);
+
+/// evaluates Basis numerically at x, returns None if undefined there or not computable (ie. INF, Int, Inv)
+pub fn evaluate(basis: &Basis, x: f64) -> Option<f64> {
+    basis.fold(&mut |basis: &Basis, operands: Vec<Option<f64>>| {
+        let coefficient = basis.coefficient().n as f64 / basis.coefficient().d as f64;
+        let value = match basis {
+            Basis::BasisLeaf(basis_leaf) => match basis_leaf.element {
+                BasisElement::Num => 1.0,
+                BasisElement::X => x,
+                BasisElement::Var(_) | BasisElement::Inf => return None,
+            },
+            Basis::BasisNode(BasisNode { operator, .. }) => {
+                let operands = operands.into_iter().collect::<Option<Vec<f64>>>()?;
+                match operator {
+                    BasisOperator::Add => operands.iter().sum(),
+                    BasisOperator::Minus => operands[0] - operands[1..].iter().sum::<f64>(),
+                    BasisOperator::Mult => operands.iter().product(),
+                    BasisOperator::Div if operands[1] == 0.0 => return None,
+                    BasisOperator::Div => operands[0] / operands[1],
+                    BasisOperator::Pow(Fraction { n, d }) => {
+                        let pow = *n as f64 / *d as f64;
+                        if operands[0] >= 0.0 {
+                            operands[0].powf(pow)
+                        } else if d % 2 == 1 {
+                            // odd roots of negatives are real
+                            let magnitude = (-operands[0]).powf(pow);
+                            if n % 2 == 0 {
+                                magnitude
+                            } else {
+                                -magnitude
+                            }
+                        } else {
+                            return None;
+                        }
+                    }
+                    BasisOperator::E => operands[0].exp(),
+                    BasisOperator::Log if operands[0] <= 0.0 => return None,
+                    BasisOperator::Log => operands[0].ln(),
+                    BasisOperator::Cos => operands[0].cos(),
+                    BasisOperator::Sin => operands[0].sin(),
+                    BasisOperator::Acos | BasisOperator::Asin if operands[0].abs() > 1.0 => {
+                        return None
+                    }
+                    BasisOperator::Acos => operands[0].acos(),
+                    BasisOperator::Asin => operands[0].asin(),
+                    BasisOperator::Inv | BasisOperator::Int => return None,
+                }
+            }
+        };
+        Some(coefficient * value).filter(|value| value.is_finite())
+    })
+}
+
+/// evaluates Basis exactly at rational x, returns None if the result is irrational, undefined, or too large
+pub fn evaluate_exact(basis: &Basis, x: Fraction) -> Option<Fraction> {
+    let (n, d) = exact(basis, (x.n as i128, x.d as i128))?;
+    if n.abs() > i32::MAX as i128 || d.abs() > i32::MAX as i128 {
+        return None;
+    }
+    Some(Fraction::from((n as i32, d as i32)).simplify())
+}
+
+/// recursive exact evaluation with wide rationals
+fn exact(basis: &Basis, x: Rational) -> Option<Rational> {
+    let coefficient = (basis.coefficient().n as i128, basis.coefficient().d as i128);
+    let value = match basis {
+        Basis::BasisLeaf(basis_leaf) => match basis_leaf.element {
+            BasisElement::Num => (1, 1),
+            BasisElement::X => x,
+            BasisElement::Var(_) | BasisElement::Inf => return None,
+        },
+        Basis::BasisNode(BasisNode {
+            operator, operands, ..
+        }) => {
+            let operands = operands
+                .iter()
+                .map(|op| exact(op, x))
+                .collect::<Option<Vec<Rational>>>()?;
+            match operator {
+                BasisOperator::Add => operands.into_iter().try_fold((0, 1), add)?,
+                BasisOperator::Minus => operands[1..]
+                    .iter()
+                    .try_fold(operands[0], |acc, op| add(acc, (-op.0, op.1)))?,
+                BasisOperator::Mult => operands.into_iter().try_fold((1, 1), mul)?,
+                BasisOperator::Div if operands[1].0 == 0 => return None,
+                BasisOperator::Div => mul(operands[0], (operands[1].1, operands[1].0))?,
+                // only integer powers stay rational in general
+                BasisOperator::Pow(Fraction { n, d: 1 }) => {
+                    let base = if *n < 0 {
+                        if operands[0].0 == 0 {
+                            return None;
+                        }
+                        (operands[0].1, operands[0].0)
+                    } else {
+                        operands[0]
+                    };
+                    (0..n.abs()).try_fold((1, 1), |acc, _| mul(acc, base))?
+                }
+                _ => return None,
+            }
+        }
+    };
+    mul(coefficient, value)
+}
+
+/// greatest common divisor for reducing wide rationals
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b > 0 {
+        let c = a % b;
+        a = b;
+        b = c;
+    }
+    a.max(1)
+}
+
+/// reduces rational, keeping denominator positive
+fn reduce((n, d): Rational) -> Rational {
+    let gcd = gcd(n, d) * d.signum();
+    (n / gcd, d / gcd)
+}
+
+/// adds wide rationals, None on overflow
+pub(crate) fn add(a: Rational, b: Rational) -> Option<Rational> {
+    let n = a.0.checked_mul(b.1)?.checked_add(b.0.checked_mul(a.1)?)?;
+    let d = a.1.checked_mul(b.1)?;
+    Some(reduce((n, d)))
+}
+
+/// multiplies wide rationals, None on overflow
+pub(crate) fn mul(a: Rational, b: Rational) -> Option<Rational> {
+    Some(reduce((a.0.checked_mul(b.0)?, a.1.checked_mul(b.1)?)))
+}
+
+/// pseudo-random distinct rational points, half in (0, 1) and half in (1, 4)
+/// seeded from the given bases so that comparisons are reproducible
+pub(crate) fn sample_points(bases: &[&Basis], count: usize) -> Vec<Fraction> {
+    let mut state = bases.iter().fold(0, |seed, basis| {
+        let mut hasher = DefaultHasher::new();
+        basis.hash(&mut hasher);
+        seed ^ hasher.finish()
+    });
+    let mut next = |bound: u64| {
+        // linear congruential generator
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) % bound
+    };
+
+    let mut points: Vec<Fraction> = vec![];
+    let mut below_one = true;
+    while points.len() < count {
+        let d = 2 + next(30) as i32;
+        let n = if below_one {
+            1 + next(d as u64 - 1) as i32 // (0, 1)
+        } else {
+            d + 1 + next(3 * d as u64 - 1) as i32 // (1, 4)
+        };
+        let point = Fraction::from((n, d)).simplify();
+        if !points.contains(&point) {
+            points.push(point);
+            below_one = !below_one;
+        }
+    }
+    points
+}
@@ -3,7 +3,7 @@ use std::cmp::{max, min, Ordering};
 use std::fmt::{Display, Formatter, Result};
 use std::ops::{Add, AddAssign, BitXor, Div, DivAssign, Mul, MulAssign, Neg, Not, Sub, SubAssign};
 // util imports
-use crate::util::ToLatex;
+use crate::util::{Language, MathMLFlags, ToLatex, ToMathML, ToSpeech};
 
 /// struct to represent a rational number coefficient in terms of numerator and denominator
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord)]
@@ -39,9 +39,13 @@ impl Fraction {
 
     /// reduces fraction to lowest terms
     pub fn simplify(self) -> Self {
-        if self.n == 0 || self.d == 0 {
+        if self.d == 0 {
             return self;
         }
+        // every zero is 0/1, so zero leaves compare equal to Basis::from(0)
+        if self.n == 0 {
+            return Fraction { n: 0, d: 1 };
+        }
 
         let gcd = Fraction::gcd(self.n, self.d);
         let (mut n, mut d) = (self.n, self.d);
@@ -56,13 +60,25 @@ impl Fraction {
         }
     }
 
+    /// multiplies fractions, returns None if the product overflows i32
+    pub fn checked_mul(self, frac: Fraction) -> Option<Self> {
+        let out_frac = Fraction {
+            n: self.n.checked_mul(frac.n)?,
+            d: self.d.checked_mul(frac.d)?,
+        };
+        Some(out_frac.simplify())
+    }
+
     /// tries to take fractional root, returns None if not possible with integers
     pub fn try_fractional_root(&self, (n, d): (i32, i32)) -> Option<Self> {
         // strips negatives
         let out_n = (self.n.abs() as f32).powf(n.abs() as f32 / d.abs() as f32);
         let out_d = (self.d.abs() as f32).powf(n.abs() as f32 / d.abs() as f32);
 
-        if out_n.fract() == 0.0 && out_d.fract() == 0.0 {
+        // roots that don't fit in i32 are left unevaluated
+        if out_n > i32::MAX as f32 || out_d > i32::MAX as f32 {
+            None
+        } else if out_n.fract() == 0.0 && out_d.fract() == 0.0 {
             if n < 0 || d < 0 {
                 Some(Fraction {
                     n: out_d as i32,
@@ -130,6 +146,35 @@ impl ToLatex for Fraction {
     }
 }
 
+/// MathML representation of Fraction, does not display if fraction = 1 like ToLatex
+impl ToMathML for Fraction {
+    fn to_mathml_with(&self, _flags: MathMLFlags) -> String {
+        let sign = if self.n < 0 { "<mo>-</mo>" } else { "" };
+        match (self.n.abs(), self.d) {
+            (1, 1) => sign.to_string(),
+            (n, 1) => format!("{}<mn>{}</mn>", sign, n),
+            (n, d) => format!("{}<mfrac><mn>{}</mn><mn>{}</mn></mfrac>", sign, n, d),
+        }
+    }
+}
+
+/// spoken Fraction, ie. minus 1 over 2, マイナス2分の1
+impl ToSpeech for Fraction {
+    fn to_speech(&self, language: Language) -> String {
+        let sign = match (self.n < 0, language) {
+            (false, _) => "",
+            (true, Language::English) => "minus ",
+            (true, Language::Japanese) => "マイナス",
+        };
+        let n = self.n.abs();
+        match language {
+            _ if self.d == 1 => format!("{}{}", sign, n),
+            Language::English => format!("{}{} over {}", sign, n, self.d),
+            Language::Japanese => format!("{}{}分の{}", sign, self.d, n),
+        }
+    }
+}
+
 /// checks if self Fraction is an integer and is equal to other Fraction integer
 impl PartialEq<i32> for Fraction {
     fn eq(&self, other: &i32) -> bool {
@@ -4,18 +4,66 @@ use std::cmp::{max, min};
 use crate::basis::{builders::*, structs::*};
 use crate::game::flags::FULL_COMPUTE;
 // local imports
+use super::budget::{enter, metered, within_size};
+use super::coefficient::split_constant;
 use super::derivative::derivative;
+use super::error::{validate, MathError};
 use super::fraction::Fraction;
 use super::liate;
+use super::metrics::worth_showing;
 
 use crate::util::js_log;
 
-/// finds integral of given Basis if possible, returns IntBasisNode if not
+/// finds integral of given Basis if possible, returns MathError instead of panicking on invalid input
+/// or exceeding the CAS budget
+pub fn try_integral(basis: &Basis) -> Result<Basis, MathError> {
+    validate(basis)?;
+    match budgeted_integral(basis) {
+        (_, Some(error)) => Err(error),
+        (result, None) => Ok(result),
+    }
+}
+
+/// finds integral of given Basis if possible, returns IntBasisNode if not or if the CAS budget is exceeded
 pub fn integral(basis: &Basis) -> Basis {
+    budgeted_integral(basis).0
+}
+
+/// runs integral within the CAS budget, returns the first limit exceeded if any
+fn budgeted_integral(basis: &Basis) -> (Basis, Option<MathError>) {
+    metered(|| {
+        let _guard = match enter() {
+            Some(guard) => guard,
+            None => return IntBasisNode(basis),
+        };
+        let result = integral_of(basis);
+        if within_size(&result) {
+            result
+        } else {
+            IntBasisNode(basis)
+        }
+    })
+}
+
+/// integral rules, recurses through `integral` so that every step is budgeted
+fn integral_of(basis: &Basis) -> Basis {
+    // I(c) = cx, I(cf(x)) = cI(f(x)) for symbolic constants c, ie. √2, log(2), e^2
+    let (constant, rest) = split_constant(basis);
+    if !constant.is_rational() {
+        return integral(&rest) * constant.to_basis();
+    }
+
+    // I(f(y)) = xf(y) for functions of other variables only
+    if matches!(basis, Basis::BasisNode(_)) && !basis.depends_on('x') {
+        return Basis::x() * basis.clone();
+    }
+
     match basis {
         Basis::BasisLeaf(basis_leaf) => match basis_leaf.element {
             BasisElement::Num => Basis::x() * basis_leaf.coefficient,
             BasisElement::X => (Basis::x() ^ 2) * (basis_leaf.coefficient / 2),
+            // other variables are constants, I(y) = xy
+            BasisElement::Var(_) => Basis::x() * basis.clone(),
             BasisElement::Inf => basis.clone(),
         },
         Basis::BasisNode(BasisNode {
@@ -27,6 +75,15 @@ pub fn integral(basis: &Basis) -> Basis {
             BasisOperator::Minus => {
                 MinusBasisNode(operands.iter().map(|op| integral(&op)).collect())
             }
+            BasisOperator::Mult
+                if operands.iter().any(|op| !op.depends_on('x'))
+                    && operands.iter().any(|op| op.depends_on('x')) =>
+            {
+                // I(f(y)g(x)) = f(y)I(g(x))
+                let (constants, factors): (Vec<Basis>, Vec<Basis>) =
+                    operands.iter().cloned().partition(|op| !op.depends_on('x'));
+                integral(&MultBasisNode(factors)) * MultBasisNode(constants) * *coefficient
+            }
             BasisOperator::Mult | BasisOperator::Div => {
                 // TODO: support multi op
                 if let Basis::BasisNode(basis_node) = basis {
@@ -86,7 +143,10 @@ pub fn integral(basis: &Basis) -> Basis {
                 let flag = unsafe { FULL_COMPUTE };
                 if flag {
                     // I(acos(x)) = xacos(x) - I(x * d/dx(acos(x)))
-                    return integration_by_parts(basis, &Basis::from(1));
+                    let result = integration_by_parts(basis, &Basis::from(1));
+                    if worth_showing(&result) {
+                        return result;
+                    }
                 }
                 IntBasisNode(basis) * *coefficient
             }
@@ -234,8 +294,12 @@ pub fn integration_by_parts(u: &Basis, dv: &Basis) -> Basis {
 }
 
 /// handles integration by parts for polyad multiplication and division
-fn polynomial_integration_by_parts(operands: Vec<Basis>) -> Basis {
-    unimplemented!("Not yet implemented: {:?}", operands);
+#[allow(dead_code)]
+fn polynomial_integration_by_parts(operands: Vec<Basis>) -> Result<Basis, MathError> {
+    Err(MathError::Unsupported(format!(
+        "integration by parts of {} operands",
+        operands.len()
+    )))
     // TODO:B make this general
     // let elements: Vec<Basis> = vec![];
     // let pointer = left_operand;
@@ -0,0 +1,243 @@
+// std imports
+use std::f64::consts::PI;
+use std::fmt::{Display, Formatter, Result};
+
+/// largest denominator tried when printing an endpoint as a fraction or multiple of π
+const MAX_DISPLAY_DENOMINATOR: i32 = 12;
+/// distance from a fraction below which an endpoint is printed as that fraction
+const DISPLAY_TOLERANCE: f64 = 1e-12;
+
+/// interval of the real line, infinite endpoints are always open
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Interval {
+    pub lower: f64,
+    pub upper: f64,
+    pub lower_closed: bool,
+    pub upper_closed: bool,
+}
+
+impl Interval {
+    /// creates Interval, opening infinite endpoints
+    pub fn new(lower: f64, upper: f64, lower_closed: bool, upper_closed: bool) -> Interval {
+        Interval {
+            lower,
+            upper,
+            lower_closed: lower_closed && lower.is_finite(),
+            upper_closed: upper_closed && upper.is_finite(),
+        }
+    }
+    /// creates closed Interval [lower, upper]
+    pub fn closed(lower: f64, upper: f64) -> Interval {
+        Interval::new(lower, upper, true, true)
+    }
+    /// creates open Interval (lower, upper)
+    pub fn open(lower: f64, upper: f64) -> Interval {
+        Interval::new(lower, upper, false, false)
+    }
+    /// creates Interval of the whole real line
+    pub fn real() -> Interval {
+        Interval::open(f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    /// checks if Interval contains no points
+    pub fn is_empty(&self) -> bool {
+        self.lower > self.upper
+            || (self.lower == self.upper && !(self.lower_closed && self.upper_closed))
+    }
+    /// checks if Interval contains x
+    pub fn contains(&self, x: f64) -> bool {
+        (self.lower < x || (self.lower_closed && self.lower == x))
+            && (x < self.upper || (self.upper_closed && self.upper == x))
+    }
+    /// largest Interval contained in both, may be empty
+    pub fn intersect(&self, other: &Interval) -> Interval {
+        let (lower, lower_closed) = if self.lower == other.lower {
+            (self.lower, self.lower_closed && other.lower_closed)
+        } else if self.lower > other.lower {
+            (self.lower, self.lower_closed)
+        } else {
+            (other.lower, other.lower_closed)
+        };
+        let (upper, upper_closed) = if self.upper == other.upper {
+            (self.upper, self.upper_closed && other.upper_closed)
+        } else if self.upper < other.upper {
+            (self.upper, self.upper_closed)
+        } else {
+            (other.upper, other.upper_closed)
+        };
+        Interval::new(lower, upper, lower_closed, upper_closed)
+    }
+    /// image of Interval under a function monotone on it, `f` takes the side the endpoint is approached from
+    pub fn map(&self, f: impl Fn(f64, f64) -> f64) -> Interval {
+        let lower = f(self.lower, 1.0);
+        let upper = f(self.upper, -1.0);
+        if lower <= upper {
+            Interval::new(lower, upper, self.lower_closed, self.upper_closed)
+        } else {
+            Interval::new(upper, lower, self.upper_closed, self.lower_closed)
+        }
+    }
+}
+
+/// string representation of Interval, ie. [0, π], (-∞, 1)
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(
+            f,
+            "{}{}, {}{}",
+            if self.lower_closed { "[" } else { "(" },
+            endpoint_string(self.lower),
+            endpoint_string(self.upper),
+            if self.upper_closed { "]" } else { ")" }
+        )
+    }
+}
+
+/// union of disjoint Intervals, kept sorted and merged
+#[derive(Clone, Debug, PartialEq)]
+pub struct IntervalSet {
+    intervals: Vec<Interval>,
+}
+
+impl IntervalSet {
+    /// creates IntervalSet with no points
+    pub fn empty() -> IntervalSet {
+        IntervalSet { intervals: vec![] }
+    }
+    /// creates IntervalSet of the whole real line
+    pub fn real() -> IntervalSet {
+        IntervalSet::from(Interval::real())
+    }
+
+    /// disjoint Intervals in increasing order
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+    /// checks if IntervalSet contains no points
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+    /// checks if IntervalSet contains x
+    pub fn contains(&self, x: f64) -> bool {
+        self.intervals.iter().any(|interval| interval.contains(x))
+    }
+    /// points in either IntervalSet
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::from(
+            self.intervals
+                .iter()
+                .chain(other.intervals.iter())
+                .copied()
+                .collect::<Vec<Interval>>(),
+        )
+    }
+    /// points in both IntervalSets
+    pub fn intersect(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::from(
+            self.intervals
+                .iter()
+                .flat_map(|a| other.intervals.iter().map(move |b| a.intersect(b)))
+                .collect::<Vec<Interval>>(),
+        )
+    }
+}
+
+/// creates IntervalSet of a single Interval
+impl From<Interval> for IntervalSet {
+    fn from(interval: Interval) -> IntervalSet {
+        IntervalSet::from(vec![interval])
+    }
+}
+
+/// creates IntervalSet from any Intervals, dropping empty ones and merging overlaps
+impl From<Vec<Interval>> for IntervalSet {
+    fn from(mut intervals: Vec<Interval>) -> IntervalSet {
+        intervals.retain(|interval| !interval.is_empty());
+        intervals.sort_by(|a, b| {
+            a.lower
+                .total_cmp(&b.lower)
+                .then(b.lower_closed.cmp(&a.lower_closed))
+        });
+        let mut merged: Vec<Interval> = vec![];
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last)
+                    if last.upper > interval.lower
+                        || (last.upper == interval.lower
+                            && (last.upper_closed || interval.lower_closed)) =>
+                {
+                    if interval.upper > last.upper {
+                        last.upper = interval.upper;
+                        last.upper_closed = interval.upper_closed;
+                    } else if interval.upper == last.upper {
+                        last.upper_closed |= interval.upper_closed;
+                    }
+                }
+                _ => merged.push(interval),
+            }
+        }
+        IntervalSet { intervals: merged }
+    }
+}
+
+/// string representation of IntervalSet, ie. (-∞, 0) ∪ (0, ∞)
+impl Display for IntervalSet {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        if self.intervals.is_empty() {
+            return write!(f, "∅");
+        }
+        let intervals = self
+            .intervals
+            .iter()
+            .map(|interval| interval.to_string())
+            .collect::<Vec<String>>();
+        write!(f, "{}", intervals.join(" ∪ "))
+    }
+}
+
+/// endpoint as ∞, a small fraction, a fraction of π, or a decimal
+pub(crate) fn endpoint_string(x: f64) -> String {
+    if x.is_infinite() {
+        return if x > 0.0 { "∞" } else { "-∞" }.to_string();
+    }
+    for d in 1..=MAX_DISPLAY_DENOMINATOR {
+        let n = (x * d as f64).round();
+        if (x * d as f64 - n).abs() < DISPLAY_TOLERANCE {
+            return fraction_string(n as i64, d as i64, "");
+        }
+    }
+    for d in 1..=MAX_DISPLAY_DENOMINATOR {
+        let n = (x / PI * d as f64).round();
+        if (x / PI * d as f64 - n).abs() < DISPLAY_TOLERANCE {
+            return fraction_string(n as i64, d as i64, "π");
+        }
+    }
+    x.to_string()
+}
+
+/// n/d with an optional symbol in the numerator, ie. -π/2, 3π/4, 1/2
+fn fraction_string(n: i64, d: i64, symbol: &str) -> String {
+    let gcd = gcd(n, d);
+    let (n, d) = (n / gcd, d / gcd);
+    let numerator = match (n, symbol) {
+        (1, "π") => symbol.to_string(),
+        (-1, "π") => format!("-{}", symbol),
+        _ => format!("{}{}", n, symbol),
+    };
+    if d == 1 {
+        numerator
+    } else {
+        format!("{}/{}", numerator, d)
+    }
+}
+
+/// greatest common divisor for printing reduced fractions
+fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b > 0 {
+        let c = a % b;
+        a = b;
+        b = c;
+    }
+    a.max(1)
+}
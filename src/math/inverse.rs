@@ -1,7 +1,39 @@
+// std imports
+use std::f64::consts::PI;
 // outer crate imports
 use crate::basis::{builders::*, structs::*};
 // local imports
+use super::budget::{enter, metered, within_size};
+use super::equivalence::Equivalence;
+use super::error::{validate, MathError};
+use super::eval::{evaluate, sample_points};
 use super::fraction::Fraction;
+use super::interval::{Interval, IntervalSet};
+use super::util::function_composition;
+
+/// number of points each composition is checked at by `verify_inverse`
+const SAMPLE_COUNT: usize = 12;
+/// number of points a composition must be defined at before it is considered likely the identity
+const MIN_SAMPLES: usize = 4;
+/// relative error allowed between f(f-1(x)) and x
+const TOLERANCE: f64 = 1e-9;
+
+/// inverse of a function restricted to a branch it is injective on
+#[derive(Clone, Debug, PartialEq)]
+pub struct InverseBranch {
+    pub inverse: Basis,      // f-1(x)
+    pub domain: IntervalSet, // values of x f-1 is valid on, the image of the branch under f
+    pub branch: IntervalSet, // values of x f is inverted on, the image of the domain under f-1
+}
+
+/// single invertible step of a function, steps are applied innermost first starting from x
+#[derive(Clone, Debug)]
+enum Step {
+    Linear(Basis, Basis),               // av + b
+    Mobius(Basis, Basis, Basis, Basis), // (av + b) / (cv + d), with c != 0 and ad != bc
+    Unary(BasisOperator),               // e^v, log(v), cos(v), sin(v), acos(v), asin(v), v^n
+    Inverse(Basis, Option<Vec<Step>>),  // g-1(v), with the steps of g if g can be decomposed
+}
 
 /// find inverse of given operator if possible
 fn operator_inverse(operator: BasisOperator) -> Option<BasisOperator> {
@@ -17,133 +49,459 @@ fn operator_inverse(operator: BasisOperator) -> Option<BasisOperator> {
     }
 }
 
-/// finds inverse of Basis if possible, returns InvBasisNode if not
-/// uses stack-based recursion to invert operator order
+/// finds inverse of Basis if possible, returns MathError instead of panicking on invalid input,
+/// inverting a function with no valid branch, or exceeding the CAS budget
+pub fn try_inverse(basis: &Basis) -> Result<Basis, MathError> {
+    validate(basis)?;
+    if let Some((domain, _)) = decompose(basis).and_then(|steps| chain_branch(&steps)) {
+        if domain.is_empty() {
+            return Err(MathError::DomainError(format!(
+                "{} has no invertible branch",
+                basis
+            )));
+        }
+    }
+    match budgeted_inverse(basis) {
+        (_, Some(error)) => Err(error),
+        (result, None) => Ok(result),
+    }
+}
+
+/// finds inverse of Basis if possible, returns InvBasisNode if not or if the CAS budget is exceeded
+/// non-injective functions are inverted on their principal branch, ie. f-1(x^2) = √x
 pub fn inverse(basis: &Basis) -> Basis {
-    if let Basis::BasisLeaf(basis_leaf) = basis {
-        if basis_leaf.element == BasisElement::X {
-            return basis.clone() / basis.coefficient();
+    budgeted_inverse(basis).0
+}
+
+/// finds inverse of Basis with the domain it is valid on and the branch it inverts,
+/// returns MathError if Basis is not invertible or no branch is valid
+pub fn inverse_branch(basis: &Basis) -> Result<InverseBranch, MathError> {
+    let inverse = try_inverse(basis)?;
+    let steps = decompose(basis)
+        .ok_or_else(|| MathError::Unsupported(format!("{} is not invertible", basis)))?;
+    let (domain, branch) = chain_branch(&steps).ok_or_else(|| {
+        MathError::Unsupported(format!(
+            "branch of {} with symbolic constants or unknown inverses",
+            basis
+        ))
+    })?;
+    Ok(InverseBranch {
+        inverse,
+        domain,
+        branch,
+    })
+}
+
+/// checks that f(f-1(x)) = x on the domain and f-1(f(x)) = x on the branch of `inverse_branch`
+pub fn verify_inverse(basis: &Basis, inverse_branch: &InverseBranch) -> Equivalence {
+    let forward = function_composition(basis, &inverse_branch.inverse);
+    let backward = function_composition(&inverse_branch.inverse, basis);
+    if forward.is_x()
+        && forward.coefficient() == 1
+        && backward.is_x()
+        && backward.coefficient() == 1
+    {
+        return Equivalence::Proven;
+    }
+
+    let mut compared = 0;
+    for (composition, set) in [
+        (&forward, &inverse_branch.domain),
+        (&backward, &inverse_branch.branch),
+    ] {
+        for x in points_in(set, &[basis, &inverse_branch.inverse]) {
+            match evaluate(composition, x) {
+                Some(value) if (value - x).abs() <= TOLERANCE * x.abs().max(1.0) => compared += 1,
+                Some(_) => return Equivalence::Different,
+                None => continue,
+            }
+        }
+    }
+    if compared >= 2 * MIN_SAMPLES {
+        Equivalence::Likely
+    } else {
+        Equivalence::Different
+    }
+}
+
+/// runs inverse within the CAS budget, returns the first limit exceeded if any
+fn budgeted_inverse(basis: &Basis) -> (Basis, Option<MathError>) {
+    metered(|| {
+        let _guard = match enter() {
+            Some(guard) => guard,
+            None => return InvBasisNode(basis),
+        };
+        let result = inverse_of(basis);
+        if within_size(&result) {
+            result
+        } else {
+            InvBasisNode(basis)
         }
+    })
+}
+
+/// inverse rules, inverts each step of the function outermost first
+fn inverse_of(basis: &Basis) -> Basis {
+    // constants are left as is
+    if !basis.depends_on('x') {
         return basis.clone();
     }
+    match decompose(basis) {
+        Some(steps) => steps
+            .iter()
+            .rev()
+            .fold(Basis::x(), |out, step| step.invert(out)),
+        None => InvBasisNode(basis),
+    }
+}
 
-    // stack of coefficient, basis (for polyary ops), operator
-    let mut operator_stack: Vec<(Fraction, Option<Basis>, BasisOperator)> = vec![];
-    let mut ptr = basis;
-    // drill down to base leaf
-    while let Basis::BasisNode(BasisNode {
-        operator,
-        operands,
-        coefficient,
-    }) = ptr
-    {
-        match operator {
-            // only matches f(x) + integers
-            BasisOperator::Add | BasisOperator::Minus
-                if operands.len() == 2 && operands.iter().any(|op| op.is_numeric()) =>
-            {
-                let add_op;
-                // n + f(x)
-                if operands[0].is_numeric() {
-                    ptr = &operands[1];
-                    add_op = operands[0].clone();
-                }
-                // f(x) + n
-                else {
-                    ptr = &operands[0];
-                    add_op = operands[1].clone();
-                }
-                operator_stack.push((Fraction::from(1), Some(add_op), *operator));
+/// splits Basis into invertible steps applied innermost first, returns None if any step is not invertible
+fn decompose(basis: &Basis) -> Option<Vec<Step>> {
+    let coefficient = basis.coefficient();
+    let mut steps = match basis {
+        Basis::BasisLeaf(BasisLeaf {
+            element: BasisElement::X,
+            ..
+        }) => vec![],
+        Basis::BasisLeaf(_) => return None,
+        _ if !basis.depends_on('x') => return None,
+        Basis::BasisNode(BasisNode {
+            operator: BasisOperator::Inv,
+            operands,
+            ..
+        }) => vec![Step::Inverse(operands[0].clone(), decompose(&operands[0]))],
+        Basis::BasisNode(BasisNode {
+            operator, operands, ..
+        }) => {
+            // af(x) + b, (af(x) + b) / (cf(x) + d) with a single distinct f(x)
+            let kernel = basis
+                .pre_order()
+                .skip(1)
+                .filter(|u| u.depends_on('x'))
+                .map(|u| u.with_coefficient(1))
+                .find_map(|u| Some((ratio_step(basis, &u)?, u)));
+            if let Some((step, u)) = kernel {
+                let mut steps = decompose(&u)?;
+                steps.push(step);
+                return Some(steps);
             }
-            BasisOperator::Inv => {
-                operator_stack.push((*coefficient, None, *operator));
-                ptr = &operands[0];
+            match operator {
+                BasisOperator::E
+                | BasisOperator::Log
+                | BasisOperator::Cos
+                | BasisOperator::Sin
+                | BasisOperator::Acos
+                | BasisOperator::Asin
+                | BasisOperator::Pow(_) => {
+                    let mut steps = decompose(&operands[0])?;
+                    steps.push(Step::Unary(*operator));
+                    steps
+                }
+                // all other operators are non-invertible
+                _ => return None,
             }
-            BasisOperator::Mult
-                if operands.iter().any(|op| op.is_numeric())
-                    && operands.iter().filter(|op| !op.is_numeric()).count() == 1 =>
-            {
-                operator_stack.push((
-                    Fraction::from(1),
-                    Some(MultBasisNode(
+        }
+    };
+    if coefficient != 1 {
+        steps.push(Step::Linear(Basis::from(coefficient), Basis::from(0)));
+    }
+    Some(steps)
+}
+
+/// finds (a, b) such that Basis = au + b, with a and b independent of x
+fn linear_in(basis: &Basis, u: &Basis) -> Option<(Basis, Basis)> {
+    if !basis.depends_on('x') {
+        return Some((Basis::from(0), basis.clone()));
+    }
+    if basis.with_coefficient(1) == *u {
+        return Some((Basis::from(basis.coefficient()), Basis::from(0)));
+    }
+    match basis {
+        Basis::BasisNode(BasisNode {
+            coefficient,
+            operator,
+            operands,
+        }) => {
+            let (a, b) = match operator {
+                BasisOperator::Add | BasisOperator::Minus => {
+                    let (a, b): (Vec<Basis>, Vec<Basis>) = operands
+                        .iter()
+                        .map(|op| linear_in(op, u))
+                        .collect::<Option<Vec<(Basis, Basis)>>>()?
+                        .into_iter()
+                        .unzip();
+                    if *operator == BasisOperator::Add {
+                        (AddBasisNode(a), AddBasisNode(b))
+                    } else {
+                        (MinusBasisNode(a), MinusBasisNode(b))
+                    }
+                }
+                BasisOperator::Mult
+                    if operands.iter().filter(|op| op.depends_on('x')).count() == 1 =>
+                {
+                    let (a, b) = linear_in(operands.iter().find(|op| op.depends_on('x'))?, u)?;
+                    let rest = MultBasisNode(
                         operands
                             .iter()
-                            .filter(|op| op.is_numeric())
-                            .map(|op| op.clone())
+                            .filter(|op| !op.depends_on('x'))
+                            .cloned()
                             .collect(),
-                    )),
-                    BasisOperator::Div,
-                ));
-                ptr = operands.iter().find(|op| !op.is_numeric()).unwrap();
-            }
-            BasisOperator::Div if operands.iter().any(|op| op.is_numeric()) => {
-                if operands[1].is_numeric() {
-                    operator_stack.push((
-                        Fraction::from(1),
-                        Some(operands[1].clone()),
-                        BasisOperator::Mult,
-                    ));
-                    ptr = &operands[0];
-                } else {
-                    operator_stack.push((
-                        Fraction::from(1),
-                        None,
-                        BasisOperator::Pow(Fraction::from(-1)),
-                    ));
-                    operator_stack.push((
-                        Fraction::from(1),
-                        Some(operands[0].clone()),
-                        BasisOperator::Mult,
-                    ));
-                    ptr = &operands[1];
+                    );
+                    (a * rest.clone(), b * rest)
                 }
-            }
-            _ => {
-                let try_operator_inverse = operator_inverse(*operator);
-                // all other operators are non-invertible
-                if try_operator_inverse.is_none() {
-                    return InvBasisNode(basis);
+                BasisOperator::Div if !operands[1].depends_on('x') => {
+                    let (a, b) = linear_in(&operands[0], u)?;
+                    (a / operands[1].clone(), b / operands[1].clone())
                 }
-                operator_stack.push((*coefficient, None, *operator));
-                ptr = &operands[0];
-            }
+                _ => return None,
+            };
+            Some((a * *coefficient, b * *coefficient))
         }
+        Basis::BasisLeaf(_) => None,
     }
+}
 
-    // apply operands in reverse order, inverted
-    let mut out = Basis::x();
-    for i in 0..operator_stack.len() {
-        let (coefficient, basis, op) = &operator_stack[i];
-        out = out / *coefficient;
-        match *op {
-            BasisOperator::Inv => out = inverse(&out),
-            BasisOperator::Add | BasisOperator::Minus => {
-                out = AddBasisNode(vec![out, -basis.as_ref().unwrap().clone()])
-            }
-            BasisOperator::Div => {
-                out = out / basis.as_ref().unwrap().clone();
+/// finds the Linear or Mobius step taking u to Basis if there is one
+fn ratio_step(basis: &Basis, u: &Basis) -> Option<Step> {
+    let (a, b, c, d) = match basis {
+        Basis::BasisNode(BasisNode {
+            coefficient,
+            operator: BasisOperator::Div,
+            operands,
+        }) if operands[1].depends_on('x') => {
+            let (a, b) = linear_in(&operands[0], u)?;
+            let (c, d) = linear_in(&operands[1], u)?;
+            (a * *coefficient, b * *coefficient, c, d)
+        }
+        _ => {
+            let (a, b) = linear_in(basis, u)?;
+            (a, b, Basis::from(0), Basis::from(1))
+        }
+    };
+    if c.is_num(0) {
+        if a.is_num(0) {
+            return None;
+        }
+        return Some(Step::Linear(a / d.clone(), b / d));
+    }
+    // ad = bc is a constant function
+    if (a.clone() * d.clone() - b.clone() * c.clone()).is_num(0) {
+        return None;
+    }
+    Some(Step::Mobius(a, b, c, d))
+}
+
+/// domain of the inverse of a chain of steps and the branch it inverts, None if a step has symbolic constants
+fn chain_branch(steps: &[Step]) -> Option<(IntervalSet, IntervalSet)> {
+    let mut domain = IntervalSet::real();
+    for step in steps {
+        domain = step.forward_set(&domain)?;
+    }
+    let mut branch = domain.clone();
+    for step in steps.iter().rev() {
+        branch = step.backward_set(&branch)?;
+    }
+    Some((domain, branch))
+}
+
+/// applies chain of steps to x numerically
+fn chain_forward(steps: &[Step], x: f64, side: f64) -> Option<f64> {
+    steps
+        .iter()
+        .try_fold(x, |value, step| step.forward(value, side))
+}
+
+/// applies inverse of chain of steps to x numerically
+fn chain_backward(steps: &[Step], x: f64, side: f64) -> Option<f64> {
+    steps
+        .iter()
+        .rev()
+        .try_fold(x, |value, step| step.backward(value, side))
+}
+
+impl Step {
+    /// applies inverse of step to Basis
+    fn invert(&self, out: Basis) -> Basis {
+        match self {
+            Step::Linear(a, b) => {
+                let out = if b.is_num(0) {
+                    out
+                } else {
+                    AddBasisNode(vec![out, -b.clone()])
+                };
+                if a.is_frac(a.coefficient()) {
+                    out / a.coefficient()
+                } else {
+                    out / a.clone()
+                }
             }
-            BasisOperator::Mult => {
-                out = out * basis.as_ref().unwrap().clone();
+            // v = (b - dx) / (cx - a)
+            Step::Mobius(a, b, c, d) => {
+                (b.clone() - d.clone() * out.clone()) / (c.clone() * out - a.clone())
             }
-            BasisOperator::Pow(Fraction { n, d }) => {
-                out = PowBasisNode(d, n, &out);
+            Step::Unary(BasisOperator::Pow(Fraction { n, d })) => PowBasisNode(*d, *n, &out),
+            Step::Unary(BasisOperator::Log) => EBasisNode(&out),
+            Step::Unary(BasisOperator::E) => LogBasisNode(&out),
+            Step::Unary(operator) => Basis::BasisNode(BasisNode {
+                coefficient: Fraction::from(1),
+                operator: operator_inverse(*operator).unwrap(),
+                operands: vec![out].into(),
+            }),
+            Step::Inverse(g, _) => function_composition(g, &out),
+        }
+    }
+
+    /// Intervals the step is monotone and defined on, their union is the principal branch
+    fn pieces(&self) -> Option<Vec<Interval>> {
+        let positive = |closed| Interval::new(0.0, f64::INFINITY, closed, false);
+        let negative = |closed| Interval::new(f64::NEG_INFINITY, 0.0, false, closed);
+        Some(match self {
+            Step::Linear(_, _) => vec![Interval::real()],
+            Step::Mobius(_, _, c, d) => {
+                let pole = -value(d)? / value(c)?;
+                vec![
+                    Interval::open(f64::NEG_INFINITY, pole),
+                    Interval::open(pole, f64::INFINITY),
+                ]
             }
-            BasisOperator::Log => {
-                out = EBasisNode(&out);
+            Step::Unary(operator) => match operator {
+                BasisOperator::E => vec![Interval::real()],
+                BasisOperator::Log => vec![positive(false)],
+                BasisOperator::Cos => vec![Interval::closed(0.0, PI)],
+                BasisOperator::Sin => vec![Interval::closed(-PI / 2.0, PI / 2.0)],
+                BasisOperator::Acos | BasisOperator::Asin => vec![Interval::closed(-1.0, 1.0)],
+                // odd powers are injective on the whole line, even powers and roots on x >= 0
+                BasisOperator::Pow(Fraction { n, d }) => match (*n > 0, n % 2 != 0 && d % 2 != 0) {
+                    (true, true) => vec![Interval::real()],
+                    (true, false) => vec![positive(true)],
+                    (false, true) => vec![negative(false), positive(false)],
+                    (false, false) => vec![positive(false)],
+                },
+                _ => return None,
+            },
+            Step::Inverse(_, steps) => chain_branch(steps.as_ref()?)?.0.intervals().to_vec(),
+        })
+    }
+
+    /// applies step to x numerically, `side` is the direction a singular x is approached from
+    fn forward(&self, x: f64, side: f64) -> Option<f64> {
+        Some(match self {
+            Step::Linear(a, b) => value(a)? * x + value(b)?,
+            Step::Mobius(a, b, c, d) => mobius(value(a)?, value(b)?, value(c)?, value(d)?, x, side),
+            Step::Unary(operator) => match operator {
+                BasisOperator::E => x.exp(),
+                BasisOperator::Log => x.ln(),
+                BasisOperator::Cos => x.cos(),
+                BasisOperator::Sin => x.sin(),
+                BasisOperator::Acos => x.acos(),
+                BasisOperator::Asin => x.asin(),
+                BasisOperator::Pow(pow) => signed_pow(x, *pow, side),
+                _ => return None,
+            },
+            Step::Inverse(_, steps) => chain_backward(steps.as_ref()?, x, side)?,
+        })
+    }
+
+    /// applies inverse of step to x numerically, `side` is the direction a singular x is approached from
+    fn backward(&self, x: f64, side: f64) -> Option<f64> {
+        Some(match self {
+            Step::Linear(a, b) => (x - value(b)?) / value(a)?,
+            // v = (b - dx) / (cx - a)
+            Step::Mobius(a, b, c, d) => {
+                mobius(-value(d)?, value(b)?, value(c)?, -value(a)?, x, side)
             }
-            BasisOperator::E => {
-                out = LogBasisNode(&out);
+            Step::Unary(operator) => match operator {
+                BasisOperator::E => x.ln(),
+                BasisOperator::Log => x.exp(),
+                BasisOperator::Cos => x.acos(),
+                BasisOperator::Sin => x.asin(),
+                BasisOperator::Acos => x.cos(),
+                BasisOperator::Asin => x.sin(),
+                BasisOperator::Pow(Fraction { n, d }) => {
+                    signed_pow(x, Fraction::from((*d, *n)), side)
+                }
+                _ => return None,
+            },
+            Step::Inverse(_, steps) => chain_forward(steps.as_ref()?, x, side)?,
+        })
+    }
+
+    /// image of IntervalSet under the step, restricted to its principal branch
+    fn forward_set(&self, set: &IntervalSet) -> Option<IntervalSet> {
+        let mut image = vec![];
+        for piece in self.pieces()? {
+            for interval in set.intervals() {
+                let interval = interval.intersect(&piece);
+                if !interval.is_empty() {
+                    self.forward(interval.lower, 1.0)?;
+                    self.forward(interval.upper, -1.0)?;
+                    image.push(interval.map(|x, side| self.forward(x, side).unwrap()));
+                }
             }
-            _ => {
-                out = Basis::BasisNode(BasisNode {
-                    coefficient: Fraction::from(1),
-                    operator: operator_inverse(*op).unwrap(),
-                    operands: vec![out],
-                })
+        }
+        Some(IntervalSet::from(image))
+    }
+
+    /// preimage of IntervalSet under the step, restricted to its principal branch
+    fn backward_set(&self, set: &IntervalSet) -> Option<IntervalSet> {
+        let mut preimage = vec![];
+        for piece in self.pieces()? {
+            let piece_image = self.forward_set(&IntervalSet::from(piece))?;
+            for interval in set.intersect(&piece_image).intervals() {
+                self.backward(interval.lower, 1.0)?;
+                self.backward(interval.upper, -1.0)?;
+                preimage.push(interval.map(|x, side| self.backward(x, side).unwrap()));
             }
         }
+        Some(IntervalSet::from(preimage))
+    }
+}
+
+/// numeric value of a constant Basis, None for symbolic constants like other variables
+fn value(basis: &Basis) -> Option<f64> {
+    evaluate(basis, 0.0)
+}
+
+/// (ax + b) / (cx + d) including its limits at INF and at the pole
+fn mobius(a: f64, b: f64, c: f64, d: f64, x: f64, side: f64) -> f64 {
+    if x.is_infinite() {
+        return a / c;
     }
+    let denominator = c * x + d;
+    if denominator == 0.0 {
+        return f64::INFINITY * (a * x + b).signum() * c.signum() * side;
+    }
+    (a * x + b) / denominator
+}
+
+/// x^pow with real odd roots of negatives, 0 to a negative power is INF signed by `side`
+fn signed_pow(x: f64, pow: Fraction, side: f64) -> f64 {
+    let magnitude = x.abs().powf(pow.n as f64 / pow.d as f64);
+    let negative = x < 0.0 || (x == 0.0 && side < 0.0);
+    if negative && pow.n % 2 != 0 && pow.d % 2 != 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
 
-    out / ptr.coefficient() // divide by coefficient from base ptr
+/// sample points spread over the Intervals of `set`, seeded from the given bases
+fn points_in(set: &IntervalSet, bases: &[&Basis]) -> Vec<f64> {
+    let points = sample_points(bases, SAMPLE_COUNT);
+    set.intervals()
+        .iter()
+        .flat_map(|interval| {
+            points.iter().filter_map(move |point| {
+                // t is in (0, 4)
+                let t = point.n as f64 / point.d as f64;
+                let x = match (interval.lower.is_finite(), interval.upper.is_finite()) {
+                    (true, true) => interval.lower + (interval.upper - interval.lower) * t / 4.0,
+                    (true, false) => interval.lower + t,
+                    (false, true) => interval.upper - t,
+                    (false, false) => t - 2.0,
+                };
+                Some(x).filter(|x| interval.contains(*x))
+            })
+        })
+        .collect()
 }
@@ -4,6 +4,7 @@ use crate::game::flags::FULL_COMPUTE;
 // local imports
 use super::fraction::Fraction;
 use super::integral::*;
+use super::metrics::worth_showing;
 use super::util::function_composition;
 
 /// tries integration with a logarithmic component, skips if doesn't match pattern
@@ -90,9 +91,14 @@ pub fn algebraic(operator: BasisOperator, u: &Basis, dv: &Basis) -> Option<Basis
                 ..
             }) => {
                 let flag = unsafe { FULL_COMPUTE };
-                // skip if too complex
-                if flag || *n < 4 {
+                if *n < 4 {
                     return Some(tabular_integration(u, dv));
+                } else if flag {
+                    // skip if too complex
+                    let result = tabular_integration(u, dv);
+                    if worth_showing(&result) {
+                        return Some(result);
+                    }
                 }
             }
             Basis::BasisLeaf(BasisLeaf {
@@ -3,11 +3,26 @@ use crate::basis::{builders::*, structs::*};
 use crate::game::cards::LimitCard;
 use crate::game::flags::ALLOW_LIMITS_BEYOND_BOUNDS;
 use crate::math::logarithm::logarithm;
+// local imports
+use super::analysis::{monotonicity, Monotonicity};
+use super::budget::{metered, try_enter};
+use super::coefficient::Coefficient;
+use super::domain::{domain_with, try_domain, ROOT_WINDOW};
+use super::error::{validate, MathError};
+use super::fraction::Fraction;
+use super::interval::IntervalSet;
+use super::inverse::inverse_branch;
+use super::solve::{solve, Root};
 
-/// find limits of arccos and arcsin
+/// largest denominator tried when turning a finite branch endpoint into a Basis
+const MAX_ENDPOINT_DENOMINATOR: i32 = 12;
+/// distance from a fraction below which a branch endpoint is that fraction
+const ENDPOINT_TOLERANCE: f64 = 1e-12;
+
+/// find limits of arccos and arcsin, `basis` is the Acos or Asin node
 fn limit_arccos_arcsin(
     limit_card: &LimitCard,
-    operator: &Basis,
+    basis: &Basis,
     operand_limit: Basis,
 ) -> Option<Basis> {
     // max(arccos) = PI, max(arcsin) = PI/2
@@ -15,27 +30,27 @@ fn limit_arccos_arcsin(
         return Some(Basis::from(1));
     } else if matches!(limit_card, LimitCard::Liminf) {
         // min(arccos) = 0
-        if operator.is_node(BasisOperator::Cos) {
+        if basis.is_node(BasisOperator::Acos) {
             return Some(Basis::from(0));
         }
         // min(arcsin) = -PI/2
-        if operator.is_node(BasisOperator::Sin) {
+        if basis.is_node(BasisOperator::Asin) {
             return Some(Basis::from(1));
         }
     }
 
     if operand_limit.is_num(0) {
         // arccos(0) = PI/2
-        if operator.is_node(BasisOperator::Cos) {
+        if basis.is_node(BasisOperator::Acos) {
             return Some(Basis::from(1));
         }
         // arcsin(0) = 0
-        if operator.is_node(BasisOperator::Sin) {
+        if basis.is_node(BasisOperator::Asin) {
             return Some(Basis::from(0));
         }
     }
     // arccos(-INF)
-    if operand_limit.is_inf(-1) && operator.is_node(BasisOperator::Cos) {
+    if operand_limit.is_inf(-1) && basis.is_node(BasisOperator::Acos) {
         return Some(Basis::from(0));
     }
 
@@ -46,184 +61,337 @@ fn limit_arccos_arcsin(
 /// finds the limit given by `limit_card` of `basis` if possible, returns None if not
 pub fn limit(limit_card: &LimitCard) -> impl Fn(&Basis) -> Option<Basis> {
     let limit_card = limit_card.clone();
-    return move |basis| {
-        match basis {
-            Basis::BasisLeaf(basis_leaf) => match basis_leaf.element {
-                BasisElement::X => Some(match limit_card {
-                    LimitCard::Lim0 => Basis::from(0),
-                    LimitCard::Liminf | LimitCard::Limsup | LimitCard::LimPosInf => Basis::inf(1),
-                    LimitCard::LimNegInf => Basis::inf(-1),
-                }),
-                _ => Some(basis.clone()),
+    return move |basis| try_limit(&limit_card, basis).ok();
+}
+
+/// finds the limit given by `limit_card` of `basis`, returns MathError if the limit is invalid or unsupported
+pub fn try_limit(limit_card: &LimitCard, basis: &Basis) -> Result<Basis, MathError> {
+    validate(basis)?;
+    match metered(|| budgeted_limit(limit_card, basis)) {
+        (_, Some(error)) => Err(error),
+        (result, None) => result,
+    }
+}
+
+/// limit within the CAS budget, checks x can approach the limit point first
+fn budgeted_limit(limit_card: &LimitCard, basis: &Basis) -> Result<Basis, MathError> {
+    // x has to approach the limit within the domain, ie. not log(-x) as x → INF
+    let beyond_bounds = unsafe { ALLOW_LIMITS_BEYOND_BOUNDS };
+    // only points near 0 matter for Lim0, the domain has to be exact as x → ±INF
+    let window = match limit_card {
+        LimitCard::Lim0 => Some(ROOT_WINDOW),
+        _ => None,
+    };
+    if !approachable(limit_card, &domain_with(basis, !beyond_bounds, window)?) {
+        return Err(invalid_limit(limit_card, basis));
+    }
+    limit_of(limit_card, basis)
+}
+
+/// checks if the point of `limit_card` is in the closure of `domain`
+fn approachable(limit_card: &LimitCard, domain: &IntervalSet) -> bool {
+    let intervals = domain.intervals();
+    match limit_card {
+        LimitCard::Lim0 => intervals
+            .iter()
+            .any(|interval| interval.lower <= 0.0 && 0.0 <= interval.upper),
+        LimitCard::LimNegInf => intervals
+            .first()
+            .is_some_and(|interval| interval.lower == f64::NEG_INFINITY),
+        LimitCard::Liminf | LimitCard::Limsup | LimitCard::LimPosInf => intervals
+            .last()
+            .is_some_and(|interval| interval.upper == f64::INFINITY),
+    }
+}
+
+/// limit implementation folding the tree bottom-up, assumes `basis` has already been validated
+/// every step is budgeted, a step over budget fails like an unsupported limit
+fn limit_of(limit_card: &LimitCard, basis: &Basis) -> Result<Basis, MathError> {
+    basis.fold(
+        &mut |basis: &Basis, limits: Vec<Result<Basis, MathError>>| {
+            let _guard = try_enter()?;
+            limit_rule(limit_card, basis, limits)
+        },
+    )
+}
+
+/// limit of `basis` given the limits of its operands, one step of `limit_of`
+/// operand limits that failed only bubble up if the rule needs them, ie. limsup of cos(1/x) at 0 exists
+fn limit_rule(
+    limit_card: &LimitCard,
+    basis: &Basis,
+    limits: Vec<Result<Basis, MathError>>,
+) -> Result<Basis, MathError> {
+    let limit_card = *limit_card;
+    match basis {
+        Basis::BasisLeaf(basis_leaf) => match basis_leaf.element {
+            BasisElement::X => match limit_card {
+                LimitCard::Lim0 => Ok(Basis::from(0)),
+                LimitCard::Liminf | LimitCard::Limsup | LimitCard::LimPosInf => {
+                    Basis::try_inf(basis_leaf.coefficient.sign())
+                }
+                LimitCard::LimNegInf => Basis::try_inf(-basis_leaf.coefficient.sign()),
             },
-            Basis::BasisNode(BasisNode {
-                coefficient,
-                operator,
-                operands,
-            }) => {
-                let base_limit = limit(&limit_card)(&operands[0]);
-                match operator {
-                    BasisOperator::Add | BasisOperator::Minus | BasisOperator::Mult => {
-                        let operand_limits = operands
-                            .iter()
-                            .map(|op| limit(&limit_card)(op))
-                            .collect::<Vec<Option<Basis>>>();
-                        if operand_limits.iter().any(|op| op.is_none()) {
-                            return None; // bubble up invalid limit
-                        }
+            _ => Ok(basis.clone()),
+        },
+        Basis::BasisNode(BasisNode {
+            coefficient,
+            operator,
+            operands,
+        }) => {
+            // constants and functions of other variables are their own limit, ie. √2, log(2), π, sin(y)
+            if !basis.depends_on('x') {
+                return Ok(basis.clone());
+            }
+            let base_limit = || limits[0].clone();
+            match operator {
+                BasisOperator::Add | BasisOperator::Minus | BasisOperator::Mult => {
+                    // bubble up invalid limit
+                    let operand_limits = limits
+                        .iter()
+                        .cloned()
+                        .collect::<Result<Vec<Basis>, MathError>>()?;
 
-                        // short circuit 0 or INF or -INF
-                        let try_inf = operand_limits.iter().find(|op| {
-                            let op = op.as_ref().unwrap();
-                            (op.is_inf(1) || op.is_inf(-1))
-                                || (*operator == BasisOperator::Mult && op.is_num(0))
-                        });
-                        if try_inf.is_some() {
-                            return Some(try_inf.unwrap().as_ref().unwrap().clone());
+                    // short circuit 0 or INF or -INF
+                    let try_inf = operand_limits.iter().find(|op| {
+                        (op.is_inf(1) || op.is_inf(-1))
+                            || (*operator == BasisOperator::Mult && op.is_num(0))
+                    });
+                    if let Some(inf) = try_inf {
+                        if *operator == BasisOperator::Mult && !inf.is_num(0) {
+                            // sign of INF flips with negative constant factors, ie. -√2 * INF = -INF
+                            let sign = operand_limits.iter().fold(coefficient.sign(), |acc, op| {
+                                match Coefficient::from_basis(op) {
+                                    Some(constant) if !op.is_inf(1) && !op.is_inf(-1) => {
+                                        acc * constant.sign()
+                                    }
+                                    _ => acc * op.coefficient().sign(),
+                                }
+                            });
+                            if sign != 0 {
+                                return Ok(Basis::inf(sign));
+                            }
                         }
+                        return Ok(inf.clone());
+                    }
 
-                        let unwrapped_operands = operand_limits
-                            .iter()
-                            .map(|op| op.as_ref().unwrap().clone())
-                            .collect();
-                        Some(
-                            match operator {
-                                BasisOperator::Mult => MultBasisNode(unwrapped_operands),
-                                BasisOperator::Add => AddBasisNode(unwrapped_operands),
-                                _ => unreachable!("Tried: limit {} of {}", limit_card, operator),
-                            } * *coefficient,
-                        )
+                    Ok(match operator {
+                        BasisOperator::Mult => MultBasisNode(operand_limits),
+                        BasisOperator::Minus => MinusBasisNode(operand_limits),
+                        _ => AddBasisNode(operand_limits),
+                    } * *coefficient)
+                }
+                BasisOperator::Div => {
+                    let denominator_limit = limits[1].clone()?;
+                    if denominator_limit.is_num(0) {
+                        return Err(invalid_limit(&limit_card, basis)); // invalid limit, (1/0)
+                    } else if denominator_limit.is_inf(1) || denominator_limit.is_inf(-1) {
+                        return Ok(Basis::from(0));
                     }
-                    BasisOperator::Div => {
-                        let denominator_limit = limit(&limit_card)(&operands[1]);
-                        if denominator_limit.as_ref().unwrap().is_num(0)
-                            || denominator_limit.is_none()
-                        {
-                            return None; // invalid limit, (1/0)
-                        } else if denominator_limit.as_ref().unwrap().is_inf(1)
-                            || denominator_limit.as_ref().unwrap().is_inf(-1)
-                        {
-                            return Some(Basis::from(0));
-                        } else if base_limit.is_none() {
-                            return None;
-                        } else if base_limit.as_ref().unwrap().is_inf(-1)
-                            || base_limit.as_ref().unwrap().is_inf(-1)
-                            || base_limit.as_ref().unwrap().is_num(0)
-                        {
-                            return base_limit;
-                        }
-                        Some(Basis::from(
-                            *coefficient * base_limit.unwrap().coefficient()
-                                / denominator_limit.unwrap().coefficient(),
-                        ))
+                    let numerator_limit = base_limit()?;
+                    if numerator_limit.is_inf(1)
+                        || numerator_limit.is_inf(-1)
+                        || numerator_limit.is_num(0)
+                    {
+                        return Ok(numerator_limit);
+                    }
+                    Ok(numerator_limit / denominator_limit * *coefficient)
+                }
+                BasisOperator::Pow(frac) => {
+                    let base_limit = base_limit()?;
+                    if base_limit.is_num(0) && frac.n < 0 {
+                        return Err(invalid_limit(&limit_card, basis)); // invalid limit (1/0)
+                    }
+                    if base_limit.is_inf(1) || base_limit.is_inf(-1) {
+                        return Ok(base_limit ^ (frac.n, frac.d));
                     }
-                    BasisOperator::Pow(frac) => {
-                        if base_limit.is_none()
-                            || base_limit.as_ref().unwrap().is_num(0) && frac.n < 0
-                        {
-                            return None; // invalid limit (1/0)
-                        }
-                        if base_limit.as_ref().unwrap().is_inf(1)
-                            || base_limit.as_ref().unwrap().is_inf(-1)
-                        {
-                            return Some(base_limit.unwrap() ^ (frac.n, frac.d));
-                        }
 
-                        match operands[0] {
-                            Basis::BasisLeaf(BasisLeaf {
-                                element: BasisElement::Num,
-                                ..
-                            }) => Some(basis.clone()),
-                            _ => Some((base_limit.unwrap() ^ *frac) * *coefficient),
-                        }
+                    match operands[0] {
+                        Basis::BasisLeaf(BasisLeaf {
+                            element: BasisElement::Num,
+                            ..
+                        }) => Ok(basis.clone()),
+                        _ => Ok((base_limit ^ *frac) * *coefficient),
                     }
-                    BasisOperator::E => {
-                        if base_limit.is_none() {
-                            return None;
-                        }
-                        if base_limit.as_ref().unwrap().is_inf(1) {
-                            return Some(Basis::inf(1));
-                        } else if base_limit.as_ref().unwrap().is_inf(-1) {
-                            return Some(Basis::from(0));
-                        }
-                        Some(EBasisNode(&base_limit.unwrap()) * *coefficient)
+                }
+                BasisOperator::E => {
+                    let base_limit = base_limit()?;
+                    if base_limit.is_inf(1) {
+                        return Ok(Basis::inf(1));
+                    } else if base_limit.is_inf(-1) {
+                        return Ok(Basis::from(0));
                     }
-                    BasisOperator::Log => {
-                        if base_limit.is_none() || base_limit.as_ref().unwrap().is_inf(-1) {
-                            return None; // invalid limit (log(-INF))
-                        } else if base_limit.as_ref().unwrap().is_inf(1) {
-                            return Some(Basis::inf(1));
-                        } else if base_limit.as_ref().unwrap().is_num(0) {
-                            return Some(Basis::inf(-1));
-                        }
-                        match operands[0] {
-                            Basis::BasisLeaf(BasisLeaf {
-                                element: BasisElement::Num,
-                                ..
-                            }) => Some(basis.clone()),
-                            _ => Some(logarithm(&base_limit.unwrap()) * *coefficient),
-                        }
+                    Ok(EBasisNode(&base_limit) * *coefficient)
+                }
+                BasisOperator::Log => {
+                    let base_limit = base_limit()?;
+                    if base_limit.is_inf(1) {
+                        return Ok(Basis::inf(1));
+                    } else if base_limit.is_num(0) {
+                        return Ok(Basis::inf(-1));
                     }
-                    BasisOperator::Cos | BasisOperator::Sin => {
-                        if matches!(limit_card, LimitCard::LimPosInf | LimitCard::LimNegInf) {
-                            return None; // invalid limit (oscillating function)
-                        } else if matches!(limit_card, LimitCard::Limsup) {
-                            return Some(Basis::from(*coefficient));
-                        } else if matches!(limit_card, LimitCard::Liminf) {
-                            return Some(Basis::from(-*coefficient));
-                        } else if base_limit.as_ref().unwrap().is_num(0) {
-                            if *operator == BasisOperator::Cos {
-                                return Some(Basis::from(*coefficient));
-                            } else {
-                                return Some(Basis::from(0));
-                            }
-                        }
-                        if *operator == BasisOperator::Cos {
-                            Some(CosBasisNode(&base_limit.unwrap()) * *coefficient)
-                        } else {
-                            Some(SinBasisNode(&base_limit.unwrap()) * *coefficient)
-                        }
+                    match operands[0] {
+                        Basis::BasisLeaf(BasisLeaf {
+                            element: BasisElement::Num,
+                            ..
+                        }) => Ok(basis.clone()),
+                        _ => Ok(logarithm(&base_limit) * *coefficient),
                     }
-                    BasisOperator::Acos | BasisOperator::Asin => {
-                        let flag = unsafe { ALLOW_LIMITS_BEYOND_BOUNDS };
-                        if flag {
-                            // find nested limit
-                            let operand_limit = limit(&limit_card)(&Basis::x()).unwrap();
-                            return limit_arccos_arcsin(&limit_card, &operands[0], operand_limit);
+                }
+                BasisOperator::Cos | BasisOperator::Sin => {
+                    if matches!(limit_card, LimitCard::LimPosInf | LimitCard::LimNegInf) {
+                        // invalid limit (oscillating function)
+                        return Err(MathError::DomainError(format!(
+                            "{} of oscillating {}",
+                            limit_card, basis
+                        )));
+                    } else if matches!(limit_card, LimitCard::Limsup) {
+                        return Ok(Basis::from(*coefficient));
+                    } else if matches!(limit_card, LimitCard::Liminf) {
+                        return Ok(Basis::from(-*coefficient));
+                    }
+                    let base_limit = base_limit()?;
+                    if base_limit.is_num(0) {
+                        if *operator == BasisOperator::Cos {
+                            return Ok(Basis::from(*coefficient));
                         } else {
-                            match *operator {
-                                // acos(0) = PI/2
-                                BasisOperator::Acos if limit_card == LimitCard::Lim0 => {
-                                    Some(Basis::from(1))
-                                }
-                                // asin(0) = 0
-                                BasisOperator::Asin if limit_card == LimitCard::Lim0 => {
-                                    Some(Basis::from(0))
-                                }
-                                _ => None,
-                            }
+                            return Ok(Basis::from(0));
                         }
                     }
-                    BasisOperator::Inv => {
-                        unimplemented!(
-                            "Not yet implemented: {} of {} ({:?})",
-                            limit_card,
-                            basis,
-                            basis
-                        );
-                    }
-                    BasisOperator::Int => {
-                        // assume that the limits of integration are from 0 to x for INF, x to 0 for -INF, what for 0?
-                        let _res = integral_limit(basis);
-                        Some(Basis::from(0))
+                    if *operator == BasisOperator::Cos {
+                        Ok(CosBasisNode(&base_limit) * *coefficient)
+                    } else {
+                        Ok(SinBasisNode(&base_limit) * *coefficient)
                     }
                 }
+                BasisOperator::Acos | BasisOperator::Asin => {
+                    // limits beyond [-1, 1] are only reached with ALLOW_LIMITS_BEYOND_BOUNDS
+                    limit_arccos_arcsin(&limit_card, basis, base_limit()?)
+                        .ok_or_else(|| invalid_limit(&limit_card, basis))
+                }
+                BasisOperator::Inv => scale(
+                    inverse_limit(&limit_card, basis, &operands[0])?,
+                    coefficient,
+                ),
+                BasisOperator::Int => scale(
+                    integral_limit(&limit_card, basis, &operands[0])?,
+                    coefficient,
+                ),
             }
         }
+    }
+}
+
+/// error for limits that do not exist
+fn invalid_limit(limit_card: &LimitCard, basis: &Basis) -> MathError {
+    MathError::DomainError(format!("{} of {} does not exist", limit_card, basis))
+}
+
+/// multiplies a limit by the coefficient of its node, keeping INF as INF
+fn scale(limit: Basis, coefficient: &Fraction) -> Result<Basis, MathError> {
+    if limit.is_inf(1) || limit.is_inf(-1) {
+        Basis::try_inf(limit.coefficient().sign() * coefficient.sign())
+    } else {
+        Ok(limit * *coefficient)
+    }
+}
+
+/// limit of f-1 using the monotonicity of f on the branch it is inverted on,
+/// f-1 approaches the end of the branch where f approaches the limit point
+fn inverse_limit(
+    limit_card: &LimitCard,
+    basis: &Basis,
+    inverted: &Basis,
+) -> Result<Basis, MathError> {
+    let unsupported = || MathError::Unsupported(format!("{} of {}", limit_card, basis));
+    let branch = match inverse_branch(inverted) {
+        Ok(inverse_branch) => inverse_branch.branch,
+        Err(_) => try_domain(inverted)?,
+    };
+    let branch = match branch.intervals() {
+        [interval] => *interval,
+        _ => return Err(unsupported()),
+    };
+    let direction = monotonicity(inverted)
+        .into_iter()
+        .find(|(piece, _)| piece.lower <= branch.lower && branch.upper <= piece.upper)
+        .and_then(|(_, monotonicity)| match monotonicity {
+            Monotonicity::Increasing => Some(1),
+            Monotonicity::Decreasing => Some(-1),
+            Monotonicity::Constant => None,
+        })
+        .ok_or_else(unsupported)?;
+
+    let endpoint = match limit_card {
+        // f-1(0) is the root of f on the branch
+        LimitCard::Lim0 => {
+            return solve(inverted)
+                .roots
+                .into_iter()
+                .find_map(|root| match root {
+                    Root::Exact(x) if evaluate_root(&x).is_some_and(|x| branch.contains(x)) => {
+                        Some(x)
+                    }
+                    _ => None,
+                })
+                .ok_or_else(unsupported);
+        }
+        LimitCard::LimNegInf if direction == 1 => branch.lower,
+        LimitCard::LimNegInf => branch.upper,
+        _ if direction == 1 => branch.upper,
+        _ => branch.lower,
     };
+    endpoint_basis(endpoint).ok_or_else(unsupported)
 }
 
-/// calculates integral limits using squeeze theorem and comparing to a smaller/larger function with known limit
-fn integral_limit(_basis: &Basis) -> Option<Basis> {
-    None
+/// numeric value of an exact root
+fn evaluate_root(root: &Basis) -> Option<f64> {
+    Root::Exact(root.clone()).value()
+}
+
+/// branch endpoint as INF or a small fraction
+fn endpoint_basis(x: f64) -> Option<Basis> {
+    if x.is_infinite() {
+        return Some(Basis::inf(x.signum() as i32));
+    }
+    (1..=MAX_ENDPOINT_DENOMINATOR).find_map(|d| {
+        let n = (x * d as f64).round();
+        ((x * d as f64 - n).abs() < ENDPOINT_TOLERANCE).then(|| Basis::from((n as i32, d)))
+    })
+}
+
+/// limit of the antiderivative F of f, integrals are taken from 0 to x so F(0) = 0 when f is
+/// bounded near 0, and F grows without bound when f tends to INF or a non-zero constant
+fn integral_limit(
+    limit_card: &LimitCard,
+    basis: &Basis,
+    integrand: &Basis,
+) -> Result<Basis, MathError> {
+    let integrand_limit = limit_of(limit_card, integrand);
+    if matches!(limit_card, LimitCard::Lim0) {
+        return match integrand_limit {
+            Ok(limit) if !limit.is_inf(1) && !limit.is_inf(-1) => Ok(Basis::from(0)),
+            _ => Err(MathError::Unsupported(format!(
+                "{} of {} may diverge",
+                limit_card, basis
+            ))),
+        };
+    }
+    let integrand_limit = integrand_limit?;
+    let sign = if integrand_limit.is_inf(1) || integrand_limit.is_inf(-1) {
+        integrand_limit.coefficient().sign()
+    } else {
+        Coefficient::from_basis(&integrand_limit).map_or(0, |constant| constant.sign())
+    };
+    if sign == 0 {
+        return Err(MathError::Unsupported(format!(
+            "{} of {} may converge",
+            limit_card, basis
+        )));
+    }
+    // F increases with the sign of f as x → INF, and against it as x → -INF
+    Ok(match limit_card {
+        LimitCard::LimNegInf => Basis::inf(-sign),
+        _ => Basis::inf(sign),
+    })
 }
@@ -0,0 +1,151 @@
+// outer crate imports
+use crate::basis::structs::*;
+// local imports
+use super::derivative::derivative;
+use super::eval::{evaluate, sample_points};
+
+/// number of random points the Wronskian is evaluated at
+const SAMPLE_COUNT: usize = 8;
+/// number of points the Wronskian must be defined at before the bases are considered dependent
+const MIN_SAMPLES: usize = 4;
+/// determinant relative to its Hadamard bound below which it is treated as zero
+const TOLERANCE: f64 = 1e-9;
+/// largest set whose Wronskian determinant is expanded symbolically
+const MAX_SYMBOLIC_SIZE: usize = 3;
+
+/// result of checking a set of bases for linear dependence
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum Dependence {
+    Proven,      // Wronskian simplifies to 0
+    Likely,      // Wronskian is 0 at every numeric sample point
+    Independent, // Wronskian is non zero at some sample point, or too few points to compare
+}
+
+/// Wronskian matrix of bases, row i holds the ith derivatives
+pub fn wronskian_matrix(bases: &[Basis]) -> Vec<Vec<Basis>> {
+    let mut rows = vec![bases.to_vec()];
+    for i in 1..bases.len() {
+        let row = rows[i - 1].iter().map(derivative).collect();
+        rows.push(row);
+    }
+    rows
+}
+
+/// symbolic Wronskian determinant of bases, expanded along the first row
+pub fn wronskian(bases: &[Basis]) -> Basis {
+    determinant(&wronskian_matrix(bases))
+}
+
+/// symbolic determinant by cofactor expansion, only meant for small matrices
+fn determinant(matrix: &[Vec<Basis>]) -> Basis {
+    match matrix.len() {
+        0 => Basis::from(1),
+        1 => matrix[0][0].clone(),
+        n => (0..n)
+            .zip([false, true].iter().cycle())
+            .map(|(j, negate)| {
+                let minor = matrix[1..]
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .enumerate()
+                            .filter(|(k, _)| *k != j)
+                            .map(|(_, basis)| basis.clone())
+                            .collect()
+                    })
+                    .collect::<Vec<Vec<Basis>>>();
+                let term = matrix[0][j].clone() * determinant(&minor);
+                if *negate {
+                    -term
+                } else {
+                    term
+                }
+            })
+            .fold(Basis::from(0), |sum, term| sum + term),
+    }
+}
+
+/// numeric determinant by gaussian elimination with partial pivoting, also returns its Hadamard bound
+fn numeric_determinant(mut matrix: Vec<Vec<f64>>) -> (f64, f64) {
+    let bound = matrix
+        .iter()
+        .map(|row| row.iter().map(|v| v * v).sum::<f64>().sqrt())
+        .product::<f64>();
+    let n = matrix.len();
+    let mut det = 1.0;
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|a, b| matrix[*a][col].abs().total_cmp(&matrix[*b][col].abs()))
+            .unwrap();
+        if matrix[pivot][col] == 0.0 {
+            return (0.0, bound);
+        }
+        if pivot != col {
+            matrix.swap(pivot, col);
+            det = -det;
+        }
+        det *= matrix[col][col];
+        let (upper, lower) = matrix.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for row in lower.iter_mut() {
+            let factor = row[col] / pivot_row[col];
+            for (value, pivot_value) in row.iter_mut().zip(pivot_row).skip(col) {
+                *value -= factor * pivot_value;
+            }
+        }
+    }
+    (det, bound)
+}
+
+/// checks if bases are linearly dependent, ie. some basis is a linear combination of the others
+/// identically zero Wronskian implies dependence for the analytic functions a Basis can represent
+pub fn linear_dependence(bases: &[Basis]) -> Dependence {
+    if bases.len() <= 1 {
+        return match bases.first() {
+            Some(basis) if basis.is_num(0) => Dependence::Proven,
+            _ => Dependence::Independent,
+        };
+    }
+
+    let matrix = wronskian_matrix(bases);
+    if bases.len() <= MAX_SYMBOLIC_SIZE && determinant(&matrix).is_num(0) {
+        return Dependence::Proven;
+    }
+
+    let mut compared = 0;
+    for x in sample_points(&bases.iter().collect::<Vec<&Basis>>(), SAMPLE_COUNT) {
+        let x = x.n as f64 / x.d as f64;
+        let values = matrix
+            .iter()
+            .map(|row| row.iter().map(|basis| evaluate(basis, x)).collect())
+            .collect::<Option<Vec<Vec<f64>>>>();
+        if let Some(values) = values {
+            let (det, bound) = numeric_determinant(values);
+            if det.abs() > TOLERANCE * bound.max(1.0) {
+                return Dependence::Independent;
+            }
+            compared += 1;
+        }
+    }
+    if compared >= MIN_SAMPLES {
+        Dependence::Likely
+    } else {
+        Dependence::Independent
+    }
+}
+
+/// indices of bases kept when each basis is dropped if it is dependent on the bases kept before it
+pub fn independent_subset(bases: &[Basis]) -> Vec<usize> {
+    let mut kept: Vec<usize> = vec![];
+    for i in 0..bases.len() {
+        let mut candidate = kept
+            .iter()
+            .map(|k| bases[*k].clone())
+            .collect::<Vec<Basis>>();
+        candidate.push(bases[i].clone());
+        if linear_dependence(&candidate) == Dependence::Independent {
+            kept.push(i);
+        }
+    }
+    kept
+}
@@ -17,6 +17,11 @@ pub fn logarithm(basis: &Basis) -> Basis {
                 // log(nx) = log(n) + log(x)
                 LogBasisNode(&Basis::x()) + LogBasisNode(&Basis::from(basis_leaf.coefficient))
             }
+            BasisElement::Var(symbol) => {
+                // log(ny) = log(n) + log(y)
+                LogBasisNode(&Basis::var(symbol))
+                    + LogBasisNode(&Basis::from(basis_leaf.coefficient))
+            }
             BasisElement::Inf => basis.clone(),
         },
         Basis::BasisNode(basis_node) => match basis_node.operator {
@@ -0,0 +1,169 @@
+// std imports
+use std::collections::HashMap;
+// outer crate imports
+use crate::basis::structs::*;
+// local imports
+use super::fraction::Fraction;
+
+/// complexity above which FULL_COMPUTE results are left unevaluated instead of shown
+pub const FULL_COMPUTE_MAX_COMPLEXITY: u32 = 256;
+
+/// rate at which a Basis grows as x approaches INF, ordered from slowest to fastest
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub enum GrowthClass {
+    Constant,
+    Oscillating, // bounded or unbounded, but never settles
+    Logarithmic,
+    Polynomial,
+    Exponential,
+}
+
+/// number of BasisLeafs and BasisNodes in the Basis
+pub fn node_count(basis: &Basis) -> usize {
+    basis.pre_order().count()
+}
+
+/// longest path from root to a BasisLeaf, a lone BasisLeaf has depth 1
+pub fn depth(basis: &Basis) -> usize {
+    basis.fold(&mut |_: &Basis, operands: Vec<usize>| 1 + operands.into_iter().max().unwrap_or(0))
+}
+
+/// number of times each operator appears in the Basis
+pub fn operator_histogram(basis: &Basis) -> HashMap<BasisOperator, usize> {
+    let mut histogram = HashMap::new();
+    for node in basis.pre_order() {
+        if let Basis::BasisNode(BasisNode { operator, .. }) = node {
+            *histogram.entry(*operator).or_insert(0) += 1;
+        }
+    }
+    histogram
+}
+
+/// highest power of x if Basis is a (generalised) polynomial, None if it contains any other function
+/// negative and fractional degrees are returned for rational functions and roots
+pub fn polynomial_degree(basis: &Basis) -> Option<Fraction> {
+    match basis {
+        Basis::BasisLeaf(basis_leaf) => match basis_leaf.element {
+            // other variables are constants in x
+            BasisElement::Num | BasisElement::Var(_) => Some(Fraction::from(0)),
+            BasisElement::X => Some(Fraction::from(1)),
+            BasisElement::Inf => None,
+        },
+        Basis::BasisNode(BasisNode {
+            operator, operands, ..
+        }) => {
+            let degrees = operands
+                .iter()
+                .map(polynomial_degree)
+                .collect::<Option<Vec<Fraction>>>()?;
+            match operator {
+                BasisOperator::Add | BasisOperator::Minus => {
+                    degrees
+                        .into_iter()
+                        .fold(None, |max: Option<Fraction>, degree| match max {
+                            Some(max) if max >= degree => Some(max),
+                            _ => Some(degree),
+                        })
+                }
+                BasisOperator::Mult => Some(
+                    degrees
+                        .into_iter()
+                        .fold(Fraction::from(0), |sum, degree| sum + degree),
+                ),
+                BasisOperator::Div => Some(degrees[0] - degrees[1]),
+                BasisOperator::Pow(pow) => Some(degrees[0] * *pow),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// rough growth class of Basis as x approaches INF, used to compare how "large" bases are
+pub fn growth_class(basis: &Basis) -> GrowthClass {
+    match basis {
+        Basis::BasisLeaf(basis_leaf) => match basis_leaf.element {
+            BasisElement::X => GrowthClass::Polynomial,
+            _ => GrowthClass::Constant,
+        },
+        Basis::BasisNode(BasisNode {
+            operator, operands, ..
+        }) => {
+            let classes = operands.iter().map(growth_class).collect::<Vec<_>>();
+            let max_class = classes
+                .iter()
+                .max()
+                .copied()
+                .unwrap_or(GrowthClass::Constant);
+            match operator {
+                BasisOperator::Add | BasisOperator::Minus => max_class,
+                BasisOperator::Mult if classes.contains(&GrowthClass::Oscillating) => {
+                    GrowthClass::Oscillating
+                }
+                BasisOperator::Mult => max_class,
+                // decays if denominator grows faster
+                BasisOperator::Div if classes[0] >= classes[1] => classes[0],
+                BasisOperator::Div => GrowthClass::Constant,
+                BasisOperator::Pow(pow) if pow.n < 0 => match classes[0] {
+                    GrowthClass::Oscillating => GrowthClass::Oscillating,
+                    _ => GrowthClass::Constant,
+                },
+                BasisOperator::Pow(_) => classes[0],
+                BasisOperator::E | BasisOperator::Log => match classes[0] {
+                    GrowthClass::Constant | GrowthClass::Oscillating => classes[0],
+                    _ if *operator == BasisOperator::E => GrowthClass::Exponential,
+                    _ => GrowthClass::Logarithmic,
+                },
+                BasisOperator::Cos | BasisOperator::Sin => match classes[0] {
+                    GrowthClass::Constant => GrowthClass::Constant,
+                    _ => GrowthClass::Oscillating,
+                },
+                BasisOperator::Acos | BasisOperator::Asin => GrowthClass::Constant,
+                BasisOperator::Inv => match classes[0] {
+                    GrowthClass::Exponential => GrowthClass::Logarithmic,
+                    GrowthClass::Logarithmic => GrowthClass::Exponential,
+                    class => class,
+                },
+                BasisOperator::Int => max_class.max(GrowthClass::Polynomial),
+            }
+        }
+    }
+}
+
+/// weight of a single operator in the complexity score, roughly how hard it is to read and compute
+fn operator_weight(operator: &BasisOperator) -> u32 {
+    match operator {
+        BasisOperator::Add | BasisOperator::Minus => 1,
+        BasisOperator::Mult => 2,
+        BasisOperator::Div => 3,
+        BasisOperator::Pow(pow) if pow.d != 1 => 3,
+        BasisOperator::Pow(_) => 2,
+        BasisOperator::E | BasisOperator::Log | BasisOperator::Cos | BasisOperator::Sin => 3,
+        BasisOperator::Acos | BasisOperator::Asin => 4,
+        BasisOperator::Inv => 5,
+        BasisOperator::Int => 6,
+    }
+}
+
+/// weighted complexity score of Basis, sums operator weights and penalises non-trivial coefficients
+pub fn complexity(basis: &Basis) -> u32 {
+    basis.fold(&mut |basis: &Basis, operands: Vec<u32>| {
+        let coefficient = basis.coefficient();
+        let coefficient_weight = if coefficient.d != 1 {
+            2
+        } else if coefficient != 1 && coefficient != -1 {
+            1
+        } else {
+            0
+        };
+        let operator_weight = match basis {
+            Basis::BasisLeaf(_) => 1,
+            Basis::BasisNode(BasisNode { operator, .. }) => operator_weight(operator),
+        };
+        operator_weight + coefficient_weight + operands.into_iter().sum::<u32>()
+    })
+}
+
+/// checks if result computed under FULL_COMPUTE is simple enough to show to the player
+pub fn worth_showing(basis: &Basis) -> bool {
+    complexity(basis) <= FULL_COMPUTE_MAX_COMPLEXITY
+}
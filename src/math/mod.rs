@@ -1,8 +1,20 @@
+pub mod analysis;
+pub mod budget;
+pub mod cache;
+pub mod coefficient;
 pub mod derivative;
+pub mod domain;
+pub mod equivalence;
+pub mod error;
+pub mod eval;
 pub mod fraction;
 pub mod integral;
+pub mod interval;
 pub mod inverse;
 pub mod liate;
 pub mod limits;
+pub mod linear_algebra;
 pub mod logarithm;
+pub mod metrics;
+pub mod solve;
 pub mod util;
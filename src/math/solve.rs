@@ -0,0 +1,569 @@
+// std imports
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+// outer crate imports
+use crate::basis::{builders::*, structs::*};
+// local imports
+use super::budget::{enter, metered};
+use super::coefficient::Coefficient;
+use super::error::{validate, MathError};
+use super::eval::{add, evaluate, mul, Rational};
+use super::fraction::Fraction;
+use super::logarithm::logarithm;
+
+/// highest polynomial degree expanded exactly
+const MAX_DEGREE: usize = 12;
+/// largest constant and leading term searched for rational roots
+const MAX_ROOT_TERM: i128 = 100_000;
+/// numeric fallback scans [-SCAN_BOUND, SCAN_BOUND] for sign changes
+const SCAN_BOUND: f64 = 10.0;
+/// number of steps the scanned interval is split into
+const SCAN_STEPS: usize = 2000;
+/// number of bisections for each bracketed root
+const BISECTION_STEPS: usize = 100;
+/// largest |f(x)| a bisected point can have to be a root, rather than a pole
+const ROOT_TOLERANCE: f64 = 1e-6;
+/// distance below which two roots are the same
+const DUPLICATE_TOLERANCE: f64 = 1e-9;
+
+/// root of a Basis in x
+#[derive(Clone, Debug, PartialEq)]
+pub enum Root {
+    Exact(Basis),         // x = constant
+    Family(Basis, Basis), // x = base + k * period for every integer k
+    Approximate(f64),     // found numerically by bisection
+}
+
+impl Root {
+    /// numeric value of root, the base of a family
+    pub fn value(&self) -> Option<f64> {
+        match self {
+            Root::Exact(basis) | Root::Family(basis, _) => evaluate(basis, 0.0),
+            Root::Approximate(x) => Some(*x),
+        }
+    }
+}
+
+/// string representation of Root, ie. x = √2, x = π/2 + kπ, x ≈ 1.2345
+impl Display for Root {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Root::Exact(basis) => write!(f, "x = {}", basis),
+            Root::Family(base, period) if base.is_num(0) => write!(f, "x = k{}", period),
+            Root::Family(base, period) => write!(f, "x = {} + k{}", base, period),
+            Root::Approximate(x) => write!(f, "x ≈ {}", x),
+        }
+    }
+}
+
+/// roots of a Basis in x
+#[derive(Clone, Debug, PartialEq)]
+pub struct SolutionSet {
+    pub roots: Vec<Root>,
+    pub everywhere: bool, // Basis is identically 0
+    pub complete: bool,   // every real root is listed, false if the numeric fallback was used
+}
+
+impl SolutionSet {
+    /// creates SolutionSet of given roots, all of them found exactly
+    fn exact(roots: Vec<Root>) -> SolutionSet {
+        SolutionSet {
+            roots,
+            everywhere: false,
+            complete: true,
+        }
+    }
+    /// creates SolutionSet of a Basis that is 0 for every x
+    fn everywhere() -> SolutionSet {
+        SolutionSet {
+            roots: vec![],
+            everywhere: true,
+            complete: true,
+        }
+    }
+    /// roots of both SolutionSets
+    fn union(mut self, other: SolutionSet) -> SolutionSet {
+        self.roots.extend(other.roots);
+        self.everywhere |= other.everywhere;
+        self.complete &= other.complete;
+        self
+    }
+    /// drops roots `basis` is undefined at and duplicates, sorts the rest by value
+    fn defined_on(mut self, basis: &Basis) -> SolutionSet {
+        self.roots.retain(|root| match root {
+            Root::Exact(_) => root.value().is_none_or(|x| evaluate(basis, x).is_some()),
+            _ => true,
+        });
+        self.roots.sort_by(|a, b| {
+            let (a, b) = (a.value(), b.value());
+            a.unwrap_or(f64::INFINITY)
+                .total_cmp(&b.unwrap_or(f64::INFINITY))
+        });
+        let mut roots: Vec<Root> = vec![];
+        for root in self.roots {
+            let duplicate = roots.iter().any(|other| match (other, &root) {
+                (Root::Family(_, _), _) | (_, Root::Family(_, _)) => *other == root,
+                _ => match (other.value(), root.value()) {
+                    (Some(a), Some(b)) => (a - b).abs() < DUPLICATE_TOLERANCE,
+                    _ => *other == root,
+                },
+            });
+            if !duplicate {
+                roots.push(root);
+            }
+        }
+        self.roots = roots;
+        self
+    }
+}
+
+/// string representation of SolutionSet, ie. {x = -1, x = 1}
+impl Display for SolutionSet {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        if self.everywhere {
+            return write!(f, "{{x ∈ ℝ}}");
+        }
+        let roots = self
+            .roots
+            .iter()
+            .map(|root| root.to_string())
+            .collect::<Vec<String>>();
+        write!(
+            f,
+            "{{{}{}}}",
+            roots.join(", "),
+            if self.complete { "" } else { ", ..." }
+        )
+    }
+}
+
+/// finds the roots of Basis, returns MathError instead of panicking on invalid input or exceeding the CAS budget
+pub fn try_solve(basis: &Basis) -> Result<SolutionSet, MathError> {
+    validate(basis)?;
+    match budgeted_solve(basis) {
+        (_, Some(error)) => Err(error),
+        (result, None) => Ok(result),
+    }
+}
+
+/// finds the roots of Basis, exactly where possible and numerically by bisection otherwise,
+/// or once the CAS budget is exceeded
+pub fn solve(basis: &Basis) -> SolutionSet {
+    budgeted_solve(basis).0
+}
+
+/// runs solve within the CAS budget, returns the first limit exceeded if any
+fn budgeted_solve(basis: &Basis) -> (SolutionSet, Option<MathError>) {
+    metered(|| {
+        solve_equal(basis, &Basis::from(0))
+            .unwrap_or_else(|| {
+                let roots = numeric_roots(|x| evaluate(basis, x));
+                SolutionSet {
+                    roots: roots.into_iter().map(Root::Approximate).collect(),
+                    everywhere: false,
+                    complete: false,
+                }
+            })
+            .defined_on(basis)
+    })
+}
+
+/// exact solutions of basis = value for a constant value, None if there is no closed form or the budget is exceeded
+fn solve_equal(basis: &Basis, value: &Basis) -> Option<SolutionSet> {
+    let _guard = enter()?;
+    if !basis.depends_on('x') {
+        let difference = basis.clone() - value.clone();
+        if difference.is_num(0) {
+            return Some(SolutionSet::everywhere());
+        }
+        return evaluate(&difference, 0.0).map(|_| SolutionSet::exact(vec![]));
+    }
+
+    // polynomials are solved exactly up to degree 2 and by rational roots beyond that
+    if value.is_frac(value.coefficient()) {
+        if let Some(mut polynomial) = polynomial(basis) {
+            let constant = (value.coefficient().n as i128, value.coefficient().d as i128);
+            polynomial[0] = add(polynomial[0], (-constant.0, constant.1))?;
+            return solve_polynomial(polynomial);
+        }
+    }
+
+    let (coefficient, operator, operands) = match basis {
+        // x = value
+        Basis::BasisLeaf(basis_leaf) => {
+            return Some(SolutionSet::exact(vec![Root::Exact(
+                value.clone() / basis_leaf.coefficient,
+            )]))
+        }
+        Basis::BasisNode(BasisNode {
+            coefficient,
+            operator,
+            operands,
+        }) => (*coefficient, *operator, operands),
+    };
+    let value = value.clone() / coefficient;
+    let numeric_value = evaluate(&value, 0.0);
+    let no_roots = Some(SolutionSet::exact(vec![]));
+
+    match operator {
+        // f(x) + c = value
+        BasisOperator::Add => {
+            let (constants, terms): (Vec<Basis>, Vec<Basis>) =
+                operands.iter().cloned().partition(|op| !op.depends_on('x'));
+            if terms.len() != 1 {
+                return None;
+            }
+            solve_equal(&terms[0], &(value - AddBasisNode(constants)))
+        }
+        // f(x)g(x) = 0 if f(x) = 0 or g(x) = 0
+        BasisOperator::Mult if value.is_num(0) => operands
+            .iter()
+            .filter(|op| op.depends_on('x'))
+            .map(|op| solve_equal(op, &value))
+            .reduce(|a, b| Some(a?.union(b?)))?,
+        // cf(x) = value
+        BasisOperator::Mult => {
+            let (constants, terms): (Vec<Basis>, Vec<Basis>) =
+                operands.iter().cloned().partition(|op| !op.depends_on('x'));
+            if terms.len() != 1 {
+                return None;
+            }
+            solve_equal(&terms[0], &(value / MultBasisNode(constants)))
+        }
+        BasisOperator::Div if !operands[1].depends_on('x') => {
+            solve_equal(&operands[0], &(value * operands[1].clone()))
+        }
+        // f(x) / g(x) = value if f(x) - value * g(x) = 0, roots of g(x) are dropped later
+        BasisOperator::Div if value.is_num(0) => solve_equal(&operands[0], &value),
+        BasisOperator::Div => solve_equal(
+            &(operands[0].clone() - value * operands[1].clone()),
+            &Basis::from(0),
+        ),
+        BasisOperator::Pow(Fraction { n, d }) => {
+            let numeric_value = numeric_value?;
+            if numeric_value == 0.0 {
+                return if n < 0 {
+                    no_roots
+                } else {
+                    solve_equal(&operands[0], &value)
+                };
+            }
+            // odd powers keep sign, even powers and roots are non negative
+            let odd = n % 2 != 0 && d % 2 != 0;
+            if numeric_value < 0.0 && !odd {
+                return no_roots;
+            }
+            let root = value ^ Fraction::from((d, n));
+            let positive = solve_equal(&operands[0], &root)?;
+            if odd || d % 2 == 0 {
+                return Some(positive);
+            }
+            Some(positive.union(solve_equal(&operands[0], &-root)?))
+        }
+        // e^f(x) = value if f(x) = log(value)
+        BasisOperator::E if numeric_value? <= 0.0 => no_roots,
+        BasisOperator::E => solve_equal(&operands[0], &logarithm(&value)),
+        // log(f(x)) = value if f(x) = e^value
+        BasisOperator::Log => solve_equal(&operands[0], &simplify_constant(EBasisNode(&value))),
+        BasisOperator::Acos if !(0.0..=std::f64::consts::PI).contains(&numeric_value?) => no_roots,
+        BasisOperator::Acos => solve_equal(&operands[0], &simplify_constant(CosBasisNode(&value))),
+        BasisOperator::Asin
+            if !(-std::f64::consts::FRAC_PI_2..=std::f64::consts::FRAC_PI_2)
+                .contains(&numeric_value?) =>
+        {
+            no_roots
+        }
+        BasisOperator::Asin => solve_equal(&operands[0], &simplify_constant(SinBasisNode(&value))),
+        BasisOperator::Sin | BasisOperator::Cos => {
+            let numeric_value = numeric_value?;
+            if !(-1.0..=1.0).contains(&numeric_value) {
+                return no_roots;
+            }
+            let pi = Coefficient::pi().to_basis();
+            let families = match (operator, numeric_value) {
+                (BasisOperator::Sin, 0.0) => vec![(Basis::from(0), pi)],
+                (BasisOperator::Sin, v) if v.abs() == 1.0 => {
+                    vec![(pi.clone() * Fraction::from((v as i32, 2)), pi * 2)]
+                }
+                (BasisOperator::Sin, _) => {
+                    let asin = ASinBasisNode(&value);
+                    vec![(asin.clone(), pi.clone() * 2), (pi.clone() - asin, pi * 2)]
+                }
+                (_, 0.0) => vec![(pi.clone() / 2, pi)],
+                (_, 1.0) => vec![(Basis::from(0), pi * 2)],
+                (_, -1.0) => vec![(pi.clone(), pi * 2)],
+                _ => {
+                    let acos = ACosBasisNode(&value);
+                    vec![(acos.clone(), pi.clone() * 2), (-acos, pi * 2)]
+                }
+            };
+            // families only have a closed form through linear functions, x = (base - b) / a
+            let linear = polynomial(&operands[0]).filter(|p| p.len() == 2)?;
+            let (a, b) = (to_fraction(linear[1])?, to_fraction(linear[0])?);
+            Some(SolutionSet::exact(
+                families
+                    .into_iter()
+                    .map(|(base, period)| {
+                        Root::Family(
+                            (base - Basis::from(b)) / a,
+                            period / if a < 0 { -a } else { a },
+                        )
+                    })
+                    .collect(),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// roots of polynomial with given coefficients, lowest degree first
+fn solve_polynomial(mut polynomial: Vec<Rational>) -> Option<SolutionSet> {
+    while polynomial.last().is_some_and(|c| c.0 == 0) {
+        polynomial.pop();
+    }
+    if polynomial.is_empty() {
+        return Some(SolutionSet::everywhere());
+    }
+
+    let mut roots = vec![];
+    // x^n factor
+    if polynomial[0].0 == 0 {
+        roots.push(Root::Exact(Basis::from(0)));
+        while polynomial[0].0 == 0 {
+            polynomial.remove(0);
+        }
+    }
+    // rational roots p/q, p divides the constant and q the leading term
+    while polynomial.len() > 3 {
+        match rational_root(&polynomial) {
+            Some(root) => {
+                roots.push(Root::Exact(Basis::from(to_fraction(root)?)));
+                polynomial = deflate(&polynomial, root)?;
+            }
+            None => break,
+        }
+    }
+
+    let mut solutions = SolutionSet::exact(roots);
+    match polynomial.len() {
+        1 => {}
+        // ax + b = 0
+        2 => {
+            let root = to_fraction(polynomial[0])? / to_fraction(polynomial[1])?;
+            solutions
+                .roots
+                .push(Root::Exact(Basis::from((-root).simplify())));
+        }
+        // ax^2 + bx + c = 0, x = (-b ± √(b^2 - 4ac)) / 2a
+        3 => {
+            let (c, b, a) = (polynomial[0], polynomial[1], polynomial[2]);
+            let discriminant = to_fraction(add(mul(b, b)?, mul((-4, 1), mul(a, c)?)?)?)?;
+            let (a, b) = (to_fraction(a)?, to_fraction(b)?);
+            let vertex = -b / a / 2;
+            let vertex = Basis::from(if vertex.n == 0 {
+                Fraction::from(0)
+            } else {
+                vertex.simplify()
+            });
+            if discriminant == 0 {
+                solutions.roots.push(Root::Exact(vertex));
+            } else if discriminant > 0 {
+                let offset = Coefficient::from(discriminant)
+                    .pow(Fraction::from((1, 2)))?
+                    .to_basis()
+                    / (a * 2);
+                solutions
+                    .roots
+                    .push(Root::Exact(vertex.clone() - offset.clone()));
+                solutions.roots.push(Root::Exact(vertex + offset));
+            }
+        }
+        _ => {
+            let numeric = polynomial
+                .iter()
+                .map(|c| c.0 as f64 / c.1 as f64)
+                .collect::<Vec<f64>>();
+            let roots =
+                numeric_roots(|x| Some(numeric.iter().rev().fold(0.0, |acc, c| acc * x + c)));
+            solutions
+                .roots
+                .extend(roots.into_iter().map(Root::Approximate));
+            solutions.complete = false;
+        }
+    }
+    Some(solutions)
+}
+
+/// coefficients of Basis as a polynomial in x, lowest degree first, None if it is not a polynomial
+fn polynomial(basis: &Basis) -> Option<Vec<Rational>> {
+    let coefficient = (basis.coefficient().n as i128, basis.coefficient().d as i128);
+    let terms = match basis {
+        Basis::BasisLeaf(basis_leaf) => match basis_leaf.element {
+            BasisElement::Num => vec![(1, 1)],
+            BasisElement::X => vec![(0, 1), (1, 1)],
+            _ => return None,
+        },
+        Basis::BasisNode(BasisNode {
+            operator, operands, ..
+        }) => {
+            let operands = operands
+                .iter()
+                .map(polynomial)
+                .collect::<Option<Vec<Vec<Rational>>>>()?;
+            match operator {
+                BasisOperator::Add => operands
+                    .iter()
+                    .try_fold(vec![], |acc, op| polynomial_add(&acc, op))?,
+                BasisOperator::Minus => operands[1..]
+                    .iter()
+                    .try_fold(operands[0].clone(), |acc, op| {
+                        polynomial_add(&acc, &polynomial_mul(op, &[(-1, 1)])?)
+                    })?,
+                BasisOperator::Mult => operands
+                    .iter()
+                    .try_fold(vec![(1, 1)], |acc, op| polynomial_mul(&acc, op))?,
+                BasisOperator::Pow(Fraction { n, d: 1 }) if *n >= 0 => {
+                    (0..*n).try_fold(vec![(1, 1)], |acc, _| polynomial_mul(&acc, &operands[0]))?
+                }
+                _ => return None,
+            }
+        }
+    };
+    polynomial_mul(&terms, &[coefficient])
+}
+
+/// sum of polynomials
+fn polynomial_add(a: &[Rational], b: &[Rational]) -> Option<Vec<Rational>> {
+    (0..a.len().max(b.len()))
+        .map(|i| add(*a.get(i).unwrap_or(&(0, 1)), *b.get(i).unwrap_or(&(0, 1))))
+        .collect()
+}
+
+/// product of polynomials, None if the degree is above MAX_DEGREE
+fn polynomial_mul(a: &[Rational], b: &[Rational]) -> Option<Vec<Rational>> {
+    if a.is_empty() || b.is_empty() {
+        return Some(vec![]);
+    }
+    if a.len() + b.len() - 1 > MAX_DEGREE + 1 {
+        return None;
+    }
+    let mut product = vec![(0, 1); a.len() + b.len() - 1];
+    for (i, a_coefficient) in a.iter().enumerate() {
+        for (product_coefficient, b_coefficient) in product[i..].iter_mut().zip(b) {
+            *product_coefficient = add(*product_coefficient, mul(*a_coefficient, *b_coefficient)?)?;
+        }
+    }
+    Some(product)
+}
+
+/// finds a rational root of polynomial if it has one
+fn rational_root(polynomial: &[Rational]) -> Option<Rational> {
+    // scale to integer coefficients
+    let scale = polynomial
+        .iter()
+        .try_fold(1i128, |lcm, c| lcm.checked_mul(c.1 / gcd(lcm, c.1)))?;
+    let constant = polynomial[0].0.checked_mul(scale / polynomial[0].1)?;
+    let leading = polynomial.last()?;
+    let leading = leading.0.checked_mul(scale / leading.1)?;
+    if constant.abs() > MAX_ROOT_TERM || leading.abs() > MAX_ROOT_TERM {
+        return None;
+    }
+
+    for q in divisors(leading) {
+        for p in divisors(constant) {
+            for root in [(p, q), (-p, q)] {
+                let value = polynomial
+                    .iter()
+                    .rev()
+                    .try_fold((0, 1), |acc, c| add(mul(acc, root)?, *c));
+                if value.is_some_and(|value| value.0 == 0) {
+                    return mul(root, (1, 1));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// polynomial divided by (x - root), ie. synthetic division
+fn deflate(polynomial: &[Rational], root: Rational) -> Option<Vec<Rational>> {
+    let mut carry = (0, 1);
+    let mut quotient = polynomial[1..]
+        .iter()
+        .rev()
+        .map(|c| {
+            carry = add(*c, mul(carry, root)?)?;
+            Some(carry)
+        })
+        .collect::<Option<Vec<Rational>>>()?;
+    quotient.reverse();
+    Some(quotient)
+}
+
+/// positive divisors of n
+fn divisors(n: i128) -> impl Iterator<Item = i128> {
+    let n = n.abs();
+    (1..=n).filter(move |k| n % k == 0)
+}
+
+/// greatest common divisor for scaling polynomials
+fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b > 0 {
+        let c = a % b;
+        a = b;
+        b = c;
+    }
+    a.max(1)
+}
+
+/// converts wide rational to Fraction if it fits
+fn to_fraction((n, d): Rational) -> Option<Fraction> {
+    Some(Fraction::from((i32::try_from(n).ok()?, i32::try_from(d).ok()?)).simplify())
+}
+
+/// replaces constants with integer values by the integer, ie. cos(0) = 1
+fn simplify_constant(basis: Basis) -> Basis {
+    match evaluate(&basis, 0.0) {
+        Some(value) if value.fract() == 0.0 && value.abs() <= i32::MAX as f64 => {
+            Basis::from(value as i32)
+        }
+        _ => basis,
+    }
+}
+
+/// roots found by scanning for sign changes and bisecting, poles are rejected
+fn numeric_roots(f: impl Fn(f64) -> Option<f64>) -> Vec<f64> {
+    let half = (SCAN_STEPS / 2) as f64;
+    let mut roots: Vec<f64> = vec![];
+    let mut previous: Option<(f64, f64)> = None;
+    for i in 0..=SCAN_STEPS {
+        let x = (i as f64 - half) * SCAN_BOUND / half;
+        let value = f(x);
+        match (previous, value) {
+            (_, Some(0.0)) => roots.push(x),
+            (Some((a, a_value)), Some(value))
+                if a_value != 0.0 && a_value.signum() != value.signum() =>
+            {
+                let (mut a, mut b) = (a, x);
+                for _ in 0..BISECTION_STEPS {
+                    let middle = (a + b) / 2.0;
+                    match f(middle) {
+                        Some(middle_value) if middle_value.signum() == a_value.signum() => {
+                            a = middle
+                        }
+                        Some(_) => b = middle,
+                        None => break,
+                    }
+                }
+                let root = (a + b) / 2.0;
+                if f(root).is_some_and(|value| value.abs() < ROOT_TOLERANCE) {
+                    roots.push(root);
+                }
+            }
+            _ => {}
+        }
+        previous = value.map(|value| (x, value));
+    }
+    roots
+}
@@ -3,12 +3,21 @@ use std::collections::HashMap;
 // wasm-bindgen imports
 use gloo::events::EventListener;
 use wasm_bindgen::JsCast;
-use web_sys::{Document, Element, HtmlInputElement};
+use web_sys::{Document, Element, HtmlInputElement, HtmlSelectElement};
 // outer crate imports
+use crate::events::mousedown_handler::{schedule_ai_turn, update_targets};
+use crate::game::ai::Difficulty;
+use crate::game::engine::Rules;
 use crate::game::flags::*;
 use crate::game::structs::{Game, GameState};
 use crate::render::katex::clear_katex_element;
+use crate::render::render;
 use crate::render::util::{PLAYER_1_COLOUR, PLAYER_2_COLOUR};
+#[cfg(feature = "serde")]
+use crate::storage::{self, StorageError};
+// util imports
+#[cfg(feature = "serde")]
+use crate::util::js_log;
 // root imports
 use super::{GAME, MENU};
 
@@ -24,6 +33,8 @@ pub struct Menu {
 
     pub main_menu: MainMenu,
     pub settings_menu: SettingsMenu,
+    #[cfg(feature = "serde")]
+    pub saves_menu: SavesMenu,
 }
 
 impl Menu {
@@ -48,8 +59,8 @@ impl Menu {
 
         let main_menu_button = document.get_element_by_id("button-MENU").unwrap();
         let main_menu_listener = EventListener::new(&main_menu_button, "click", |_e| {
-            let (game, menu_ref) = unsafe { (GAME.as_mut().unwrap(), MENU.as_ref()) };
-            game.set_state(GameState::from("MENU"));
+            let menu_ref = unsafe { MENU.as_ref() };
+            set_game_state(GameState::from("MENU"));
 
             if menu_ref.is_some() {
                 menu_ref.unwrap().activate("MENU".to_string());
@@ -66,17 +77,16 @@ impl Menu {
                     menu_ref.unwrap().activate("MENU".to_string());
                     unsafe {
                         GAME = Some(Game::new());
-                        // clear graveyard katex items
-                        ["g=1", "g=2", "g=3"].iter().for_each(|id| {
-                            clear_katex_element(format!("katex-item_{}", id));
-                        })
                     }
+                    clear_graveyard();
                 }
             },
         );
 
         let main_menu = MainMenu::new(document);
         let settings_menu = SettingsMenu::new(document);
+        #[cfg(feature = "serde")]
+        let saves_menu = SavesMenu::new(document);
 
         Menu {
             menu_children,
@@ -87,6 +97,8 @@ impl Menu {
             game_over_listener,
             main_menu,
             settings_menu,
+            #[cfg(feature = "serde")]
+            saves_menu,
         }
     }
 
@@ -117,51 +129,260 @@ impl Menu {
         self.menu_element
             .remove_attribute("hidden")
             .expect("Failed to show main menu");
+        #[cfg(feature = "serde")]
+        self.main_menu.update_continue();
     }
+
+    /// show game over screen with winning player
+    pub fn game_over(&self, winner: u32) {
+        // finished games can't be continued
+        #[cfg(feature = "serde")]
+        storage::delete_game(None);
+
+        self.game_over_menu
+            .get_elements_by_tag_name("h2")
+            .item(0)
+            .unwrap()
+            .set_text_content(Some(format!("Player {} wins!", winner).as_str()));
+        self.open();
+        self.activate("GAMEOVER".to_string());
+    }
+}
+
+/// set new game state, showing the game or the menu to match
+pub fn set_game_state(state: GameState) {
+    let (game, menu) = unsafe { (GAME.as_mut().unwrap(), MENU.as_ref().unwrap()) };
+
+    game.state = state;
+    match state {
+        GameState::PLAYAI | GameState::PLAYVS => {
+            game.mode = state;
+            game.rules = rules_from_flags();
+            menu.close();
+            update_targets();
+            render::draw();
+            schedule_ai_turn();
+        }
+        GameState::MENU => {
+            menu.open();
+        }
+        _ => {}
+    }
+}
+
+/// end of turn rules chosen in the settings menu
+fn rules_from_flags() -> Rules {
+    unsafe {
+        Rules {
+            allow_linear_dependence: ALLOW_LINEAR_DEPENDENCE,
+            remove_dependent_bases: REMOVE_DEPENDENT_BASES,
+        }
+    }
+}
+
+/// clears graveyard katex items left over from the previous game
+fn clear_graveyard() {
+    ["g=1", "g=2", "g=3"].iter().for_each(|id| {
+        clear_katex_element(format!("katex-item_{}", id));
+    })
+}
+
+/// replaces the current game with a saved one, from a named slot or the autosave if `slot` is None
+#[cfg(feature = "serde")]
+fn resume_game(slot: Option<&str>) {
+    let menu = unsafe { MENU.as_ref().unwrap() };
+    let game = match storage::load_game(slot) {
+        Ok(game) => game,
+        Err(error) => {
+            js_log!("{}", error);
+            // incompatible saves are dropped so they aren't offered again
+            if let StorageError::Incompatible(_) = error {
+                storage::delete_game(slot);
+            }
+            menu.main_menu.update_continue();
+            menu.saves_menu.refresh();
+            return;
+        }
+    };
+    // state is SAVES for games saved from the saves menu, mode is the opponent they were played against
+    let state = match game.mode {
+        GameState::PLAYAI => GameState::PLAYAI,
+        _ => GameState::PLAYVS,
+    };
+    unsafe {
+        GAME = Some(game);
+    }
+    clear_graveyard();
+    set_game_state(state);
 }
 
 /// controller for the main menu
 pub struct MainMenu {
     pub button_elements: Vec<Element>,
     pub button_listeners: HashMap<String, EventListener>,
+
+    #[cfg(feature = "serde")]
+    pub continue_button: Element,
+    #[cfg(feature = "serde")]
+    pub continue_listener: EventListener,
 }
 
 impl MainMenu {
     /// extracts child elements from DOM and adds event listeners for each button
     pub fn new(document: &Document) -> Self {
-        let button_elements: Vec<Element> =
-            vec!["PLAYVS", "PLAYAI", "TUTORIAL", "SETTINGS", "CREDITS"]
-                .iter()
-                .map(|state| {
-                    document
-                        .get_element_by_id(&format!("button-{}", state).to_owned()[..])
-                        .unwrap()
-                })
-                .collect();
+        let button_elements: Vec<Element> = vec![
+            "PLAYVS", "PLAYAI", "TUTORIAL", "SETTINGS", "SAVES", "CREDITS",
+        ]
+        .iter()
+        .map(|state| {
+            document
+                .get_element_by_id(&format!("button-{}", state).to_owned()[..])
+                .unwrap()
+        })
+        .collect();
 
         let mut button_listeners: HashMap<String, EventListener> = HashMap::new();
         for element in button_elements.iter() {
             let element_target = element.dyn_ref::<Element>().unwrap();
             let target_id = element_target.id();
             let listener = EventListener::new(element, "click", move |_e| {
-                let (game, menu_ref) = unsafe { (GAME.as_mut().unwrap(), MENU.as_ref()) };
+                let menu_ref = unsafe { MENU.as_ref() };
                 // split id from 'button-ID'
                 let target_state = target_id.split("-").nth(1).unwrap();
-                game.set_state(GameState::from(target_state));
+                set_game_state(GameState::from(target_state));
 
                 if menu_ref.is_some() {
                     let menu = menu_ref.unwrap();
                     if menu.menu_children.contains_key(target_state) {
                         menu.activate(target_state.to_string());
                     }
+                    #[cfg(feature = "serde")]
+                    if target_state == "SAVES" {
+                        menu.saves_menu.refresh();
+                    }
                 }
             });
             button_listeners.insert(element_target.id(), listener);
         }
 
-        Self {
+        #[cfg(feature = "serde")]
+        let continue_button = document.get_element_by_id("button-CONTINUE").unwrap();
+        #[cfg(feature = "serde")]
+        let continue_listener =
+            EventListener::new(&continue_button, "click", |_e| resume_game(None));
+
+        let main_menu = Self {
             button_elements,
             button_listeners,
+            #[cfg(feature = "serde")]
+            continue_button,
+            #[cfg(feature = "serde")]
+            continue_listener,
+        };
+        #[cfg(feature = "serde")]
+        main_menu.update_continue();
+        main_menu
+    }
+
+    /// shows Continue only if a game was saved after the last turn played
+    #[cfg(feature = "serde")]
+    pub fn update_continue(&self) {
+        if storage::has_autosave() {
+            self.continue_button.remove_attribute("hidden").ok();
+        } else {
+            self.continue_button.set_attribute("hidden", "true").ok();
+        }
+    }
+}
+
+/// controller for the saved games menu
+#[cfg(feature = "serde")]
+#[allow(dead_code)]
+pub struct SavesMenu {
+    name_input: Element,
+    save_listener: EventListener,
+
+    slots_element: Element,
+    slots_listener: EventListener,
+}
+
+#[cfg(feature = "serde")]
+impl SavesMenu {
+    /// extracts slot elements from DOM and adds event listeners for saving, loading and deleting
+    pub fn new(document: &Document) -> Self {
+        let name_input = document.get_element_by_id("save-NAME").unwrap();
+        let slots_element = document.get_element_by_id("save-SLOTS").unwrap();
+
+        let (input_target, slots_target) = (name_input.clone(), slots_element.clone());
+        let save_listener = EventListener::new(
+            &document.get_element_by_id("save-SAVE").unwrap(),
+            "click",
+            move |_e| {
+                let game = unsafe { GAME.as_ref().unwrap() };
+                let input = input_target.dyn_ref::<HtmlInputElement>().unwrap();
+                let name = input.value().trim().to_string();
+                if name.is_empty() {
+                    return;
+                }
+                if let Err(error) = storage::save_game(game, Some(&name)) {
+                    js_log!("{}", error);
+                }
+                input.set_value("");
+                SavesMenu::render_slots(&slots_target);
+            },
+        );
+
+        let slots_target = slots_element.clone();
+        // one listener for every slot, buttons carry their slot and action as data attributes
+        let slots_listener = EventListener::new(&slots_element, "click", move |e| {
+            let event_target = e.target().unwrap();
+            let target = event_target.dyn_ref::<Element>().unwrap();
+            let (slot, action) = match (
+                target.get_attribute("data-slot"),
+                target.get_attribute("data-action"),
+            ) {
+                (Some(slot), Some(action)) => (slot, action),
+                _ => return,
+            };
+            match action.as_str() {
+                "load" => resume_game(Some(&slot)),
+                "delete" => {
+                    storage::delete_game(Some(&slot));
+                    SavesMenu::render_slots(&slots_target);
+                }
+                _ => unreachable!("{} is not a valid save action", action),
+            }
+        });
+
+        Self {
+            name_input,
+            save_listener,
+            slots_element,
+            slots_listener,
+        }
+    }
+
+    /// rebuilds the list of save slots
+    pub fn refresh(&self) {
+        SavesMenu::render_slots(&self.slots_element);
+    }
+
+    /// fills slots element with a row per save slot, each with a load and delete button
+    fn render_slots(slots_element: &Element) {
+        let document = slots_element.owner_document().unwrap();
+        slots_element.set_inner_html("");
+        for slot in storage::save_slots() {
+            let row = document.create_element("div").unwrap();
+            row.set_class_name("save-slot");
+            for (action, label) in [("load", slot.as_str()), ("delete", "Delete")] {
+                let button = document.create_element("button").unwrap();
+                button.set_class_name("menu-button");
+                button.set_attribute("data-slot", &slot).ok();
+                button.set_attribute("data-action", action).ok();
+                button.set_text_content(Some(label));
+                row.append_child(&button).ok();
+            }
+            slots_element.append_child(&row).ok();
         }
     }
 }
@@ -174,14 +395,18 @@ pub struct SettingsMenu {
 
     colours: Vec<Element>,
     colour_listeners: HashMap<String, EventListener>,
+
+    difficulty: Element,
+    difficulty_listener: EventListener,
 }
 
 impl SettingsMenu {
-    /// extracts child elements from DOM and adds event listeners for each checkbox
+    /// extracts child elements from DOM and adds event listeners for each checkbox, colour and select
     pub fn new(document: &Document) -> Self {
         let checkboxes: Vec<Element> = vec![
             "DISPLAY_LN_FOR_LOG",
             "ALLOW_LINEAR_DEPENDENCE",
+            "REMOVE_DEPENDENT_BASES",
             "ALLOW_LIMITS_BEYOND_BOUNDS",
             "FULL_COMPUTE",
             "USE_FRACTIONAL_EXPONENTS",
@@ -210,6 +435,7 @@ impl SettingsMenu {
                     match flag_name {
                         "DISPLAY_LN_FOR_LOG" => DISPLAY_LN_FOR_LOG = flag_value,
                         "ALLOW_LINEAR_DEPENDENCE" => ALLOW_LINEAR_DEPENDENCE = flag_value,
+                        "REMOVE_DEPENDENT_BASES" => REMOVE_DEPENDENT_BASES = flag_value,
                         "ALLOW_LIMITS_BEYOND_BOUNDS" => ALLOW_LIMITS_BEYOND_BOUNDS = flag_value,
                         "FULL_COMPUTE" => FULL_COMPUTE = flag_value,
                         "USE_FRACTIONAL_EXPONENTS" => USE_FRACTIONAL_EXPONENTS = flag_value,
@@ -217,6 +443,9 @@ impl SettingsMenu {
                         _ => panic!("Unknown flag name: {}", flag_name),
                     }
                 }
+                if let Some(game) = unsafe { GAME.as_mut() } {
+                    game.rules = rules_from_flags();
+                }
             });
             checkbox_listeners.insert(element.id(), listener);
         }
@@ -247,11 +476,22 @@ impl SettingsMenu {
             colour_listeners.insert(player_target.id(), listener);
         }
 
+        let difficulty = document.get_element_by_id("select-AI_DIFFICULTY").unwrap();
+        let difficulty_listener = EventListener::new(&difficulty, "change", |e| {
+            let event_target = e.target().unwrap();
+            let value = event_target.dyn_ref::<HtmlSelectElement>().unwrap().value();
+            unsafe {
+                AI_DIFFICULTY = Difficulty::from(value.as_str());
+            }
+        });
+
         Self {
             checkboxes,
             checkbox_listeners,
             colours,
             colour_listeners,
+            difficulty,
+            difficulty_listener,
         }
     }
 }
@@ -8,7 +8,7 @@ use super::pos;
 use super::render;
 use super::util::{RenderId, RenderItem};
 // root imports
-use crate::{CANVAS, GAME};
+use crate::CANVAS;
 // util imports
 use crate::util::min;
 
@@ -96,9 +96,11 @@ pub fn animate_hover(id: Option<RenderId>) {
     canvas.anim_controller.start_anim();
 }
 
+/// animates cards already dealt into hand slots `ids`, hiding each until it arrives
 pub fn animate_deal_cards(ids: Vec<RenderId>) {
     let canvas = unsafe { CANVAS.as_mut().unwrap() };
     let anim_controller = &mut canvas.anim_controller;
+    anim_controller.pending_deal.extend(ids.iter());
 
     for (i, id) in ids.iter().enumerate() {
         let (deal_id, anim_item) = animate_deal(*id);
@@ -140,14 +142,11 @@ pub fn animate_deal(id: RenderId) -> (RenderId, AnimItem) {
                 (AnimAttribute::R, (deck_pos.r, target_pos.r)),
             ]),
             callback: vec![|| {
-                let game = unsafe { GAME.as_mut().unwrap() };
-                // inverted since the turn is already advanced
-                let player = if game.get_current_player_num() == 1 {
-                    &mut game.player_2
-                } else {
-                    &mut game.player_1
-                };
-                player.push(game.deck.pop().unwrap());
+                let canvas = unsafe { CANVAS.as_mut().unwrap() };
+                // card has arrived, show it in hand
+                if !canvas.anim_controller.pending_deal.is_empty() {
+                    canvas.anim_controller.pending_deal.remove(0);
+                }
                 render::draw();
             }],
         },
@@ -158,6 +157,7 @@ pub fn animate_deal(id: RenderId) -> (RenderId, AnimItem) {
 pub struct AnimController {
     pub anim_items: HashMap<RenderId, AnimItem>, // map of currently animated items
     pub anim_chain: HashMap<RenderId, Vec<AnimItem>>, // map of chain animation callbacks
+    pub pending_deal: Vec<RenderId>, // hand slots dealt by the game but still animating in
     pub render_animation_frame_handle: AnimationFrame, // current raf handle
 }
 
@@ -1,6 +1,9 @@
 // wasm-bindgen imports
 use wasm_bindgen::prelude::*;
 use web_sys::Element;
+// outer crate imports
+use crate::basis::structs::Basis;
+use crate::math::metrics::complexity;
 // root imports
 use crate::CANVAS;
 // util imports
@@ -33,6 +36,17 @@ where
     )
 }
 
+/// picks KaTeX font size for Basis so that complex expressions still fit on a card
+pub fn katex_size(basis: &Basis) -> &'static str {
+    match complexity(basis) {
+        0..=6 => "Huge",
+        7..=12 => "huge",
+        13..=20 => "LARGE",
+        21..=32 => "Large",
+        _ => "large",
+    }
+}
+
 /// removes KaTeX rendering from given DOM element
 pub fn clear_katex_element(id: String) -> Element {
     js_render_katex_element(String::default(), id)
@@ -142,7 +142,12 @@ fn draw_deck(id: RenderId) {
     let (canvas, game) = unsafe { (CANVAS.as_mut().unwrap(), GAME.as_ref().unwrap()) };
     let deck = &canvas.render_items[&id];
 
+    // highlighted when passing is the only legal action
+    if game.active.targets.contains(&id) {
+        canvas.context.set_line_width(3.5);
+    }
     draw_rect(deck.x, deck.y, deck.w, deck.h, deck.r, id.to_string());
+    canvas.context.set_line_width(2.0);
 
     let context = &mut canvas.context;
     context.set_font("40px KaTeX_Main");
@@ -150,7 +155,10 @@ fn draw_deck(id: RenderId) {
     context.set_text_align("center");
     context
         .fill_text(
-            game.deck.len().to_string().as_str(),
+            // cards still animating in count as part of the deck
+            (game.deck.len() + canvas.anim_controller.pending_deal.len())
+                .to_string()
+                .as_str(),
             deck.x + deck.w / 2.0,
             deck.y + deck.h / 2.0,
         )
@@ -273,6 +281,8 @@ fn draw_field(id: RenderId) {
     }
     if game.active.selected.contains(&id) {
         context.set_line_width(5.0);
+    } else if game.active.targets.contains(&id) {
+        context.set_line_width(3.5);
     }
     draw_rect(card.x, card.y, card.w, card.h, card.r, id.to_string());
     set_line_dash(context, 0, 0.0);
@@ -283,7 +293,7 @@ fn draw_field(id: RenderId) {
         draw_katex(
             basis,
             katex_element_id,
-            "Huge",
+            katex_size(basis),
             Vector2 {
                 y: card.y + card.h / 2.0,
                 x: card.x + card.w / 2.0,
@@ -310,8 +320,10 @@ fn draw_hand(id: RenderId) {
 
     if game.active.selected.contains(&id) {
         canvas.context.set_line_width(5.0);
+    } else if game.active.targets.contains(&id) {
+        canvas.context.set_line_width(3.5);
     }
-    if val >= hand.len() {
+    if val >= hand.len() || canvas.anim_controller.pending_deal.contains(&id) {
         set_line_dash(context, 2, 10.0) // set line dash for empty field basis
     } else {
         let player_card = hand[val]; // get Card from hand
@@ -360,8 +372,6 @@ fn draw_hand(id: RenderId) {
         );
     }
     draw_rect(card.x, card.y, card.w, card.h, card.r, id.to_string());
-    if game.active.selected.contains(&id) {
-        canvas.context.set_line_width(2.0);
-    }
+    canvas.context.set_line_width(2.0);
     set_line_dash(context, 0, 0.0);
 }
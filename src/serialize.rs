@@ -0,0 +1,241 @@
+// std imports
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::hash::Hash;
+// outer crate imports
+use serde::de::{self, DeserializeOwned, SeqAccess, Visitor};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+// local imports
+use crate::basis::builders::rebuild_node;
+use crate::basis::structs::*;
+use crate::math::error::validate;
+use crate::math::fraction::Fraction;
+
+/// version of the JSON format, bumped whenever a serialized type changes shape
+pub const FORMAT_VERSION: u32 = 1;
+
+/// errors returned when saving or loading JSON
+#[derive(Clone, Debug, PartialEq)]
+pub enum SerializeError {
+    Json(String), // invalid JSON or a value of the wrong shape
+    Version(u32), // JSON written by an unsupported format version
+}
+
+/// string representation of SerializeError, used for logging
+impl Display for SerializeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            SerializeError::Json(message) => write!(f, "Invalid JSON: {}", message),
+            SerializeError::Version(version) => write!(
+                f,
+                "Unsupported format version {}, expected {}",
+                version, FORMAT_VERSION
+            ),
+        }
+    }
+}
+
+impl Error for SerializeError {}
+
+impl From<serde_json::Error> for SerializeError {
+    fn from(error: serde_json::Error) -> Self {
+        SerializeError::Json(error.to_string())
+    }
+}
+
+/// JSON envelope of any serialized value, ie. {"version":1,"data":...}
+#[derive(Serialize, Deserialize)]
+struct Versioned<T> {
+    version: u32,
+    data: T,
+}
+
+/// writes value as versioned JSON
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, SerializeError> {
+    Ok(serde_json::to_string(&Versioned {
+        version: FORMAT_VERSION,
+        data: value,
+    })?)
+}
+
+/// reads value from versioned JSON, rejecting other format versions
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, SerializeError> {
+    let versioned: Versioned<Value> = serde_json::from_str(json)?;
+    if versioned.version != FORMAT_VERSION {
+        return Err(SerializeError::Version(versioned.version));
+    }
+    Ok(serde_json::from_value(versioned.data)?)
+}
+
+/// Fraction as [n, d]
+impl Serialize for Fraction {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.n, self.d).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Fraction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (n, d) = <(i32, i32)>::deserialize(deserializer)?;
+        if d == 0 {
+            return Err(de::Error::custom(format!(
+                "zero denominator in [{}, {}]",
+                n, d
+            )));
+        }
+        Ok(Fraction::from((n, d)))
+    }
+}
+
+/// tag of a BasisLeaf element or BasisNode operator in the compact format
+fn tag(basis: &Basis) -> &'static str {
+    match basis {
+        Basis::BasisLeaf(basis_leaf) => match basis_leaf.element {
+            BasisElement::Num => "num",
+            BasisElement::X => "x",
+            BasisElement::Var(_) => "var",
+            BasisElement::Inf => "inf",
+        },
+        Basis::BasisNode(basis_node) => match basis_node.operator {
+            BasisOperator::Add => "add",
+            BasisOperator::Minus => "minus",
+            BasisOperator::Mult => "mult",
+            BasisOperator::Div => "div",
+            BasisOperator::Pow(_) => "pow",
+            BasisOperator::E => "e",
+            BasisOperator::Log => "log",
+            BasisOperator::Cos => "cos",
+            BasisOperator::Sin => "sin",
+            BasisOperator::Acos => "acos",
+            BasisOperator::Asin => "asin",
+            BasisOperator::Inv => "inv",
+            BasisOperator::Int => "int",
+        },
+    }
+}
+
+/// Basis as a tagged array, [tag, coefficient, ...], ie. 2x^3 as ["pow",[2,1],[3,1],["x",[1,1]]]
+/// leaves are ["num", c], ["x", c], ["var", c, "y"] and ["inf", c], nodes list their operands after
+/// the coefficient, with the exponent of pow nodes in between
+impl Serialize for Basis {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        seq.serialize_element(tag(self))?;
+        match self {
+            Basis::BasisLeaf(basis_leaf) => {
+                seq.serialize_element(&basis_leaf.coefficient)?;
+                if let BasisElement::Var(symbol) = basis_leaf.element {
+                    seq.serialize_element(&symbol)?;
+                }
+            }
+            Basis::BasisNode(basis_node) => {
+                seq.serialize_element(&basis_node.coefficient)?;
+                if let BasisOperator::Pow(pow) = basis_node.operator {
+                    seq.serialize_element(&pow)?;
+                }
+                for op in basis_node.operands.iter() {
+                    seq.serialize_element(op)?;
+                }
+            }
+        }
+        seq.end()
+    }
+}
+
+/// reads the tagged array written by Serialize for Basis
+struct BasisVisitor;
+
+impl<'de> Visitor<'de> for BasisVisitor {
+    type Value = Basis;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "a tagged Basis array, ie. [\"x\", [1, 1]]")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Basis, A::Error> {
+        let missing = |what: &str| de::Error::custom(format!("missing {}", what));
+        let tag: String = seq.next_element()?.ok_or_else(|| missing("tag"))?;
+        let coefficient: Fraction = seq.next_element()?.ok_or_else(|| missing("coefficient"))?;
+        // rejects invalid INF and out of range coefficients
+        let leaf = |element| {
+            let basis = Basis::BasisLeaf(BasisLeaf {
+                coefficient,
+                element,
+            });
+            validate(&basis).map_err(de::Error::custom)?;
+            Ok(basis)
+        };
+        let operator = match tag.as_str() {
+            "num" => return leaf(BasisElement::Num),
+            "x" => return leaf(BasisElement::X),
+            "inf" => return leaf(BasisElement::Inf),
+            "var" => {
+                let symbol: char = seq.next_element()?.ok_or_else(|| missing("variable"))?;
+                return leaf(BasisElement::Var(symbol));
+            }
+            "pow" => BasisOperator::Pow(seq.next_element()?.ok_or_else(|| missing("exponent"))?),
+            "add" => BasisOperator::Add,
+            "minus" => BasisOperator::Minus,
+            "mult" => BasisOperator::Mult,
+            "div" => BasisOperator::Div,
+            "e" => BasisOperator::E,
+            "log" => BasisOperator::Log,
+            "cos" => BasisOperator::Cos,
+            "sin" => BasisOperator::Sin,
+            "acos" => BasisOperator::Acos,
+            "asin" => BasisOperator::Asin,
+            "inv" => BasisOperator::Inv,
+            "int" => BasisOperator::Int,
+            _ => return Err(de::Error::custom(format!("unknown tag {}", tag))),
+        };
+        let mut operands = vec![];
+        while let Some(op) = seq.next_element::<Basis>()? {
+            operands.push(op);
+        }
+        let basis_node = BasisNode {
+            coefficient,
+            operator,
+            operands: operands.clone().into(),
+        };
+        // rejects wrong operand counts and out of range coefficients before the builders see them
+        validate(&Basis::BasisNode(basis_node.clone())).map_err(de::Error::custom)?;
+        // rebuilt so operands are in canonical order and equal nodes hash the same
+        let basis = rebuild_node(&basis_node, operands);
+        validate(&basis).map_err(de::Error::custom)?;
+        Ok(basis)
+    }
+}
+
+impl<'de> Deserialize<'de> for Basis {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(BasisVisitor)
+    }
+}
+
+/// HashMap with non-string keys as a list of [key, value] pairs, as JSON object keys must be strings
+pub(crate) mod pairs {
+    use super::*;
+
+    pub fn serialize<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<(K, V)>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
@@ -0,0 +1,95 @@
+// std imports
+use std::fmt::{Display, Formatter, Result};
+// wasm-bindgen imports
+use web_sys::Storage;
+// outer crate imports
+use crate::game::structs::Game;
+use crate::serialize::{from_json, to_json, SerializeError};
+
+/// localStorage key of the game saved after every turn
+const AUTOSAVE_KEY: &str = "nabla-game:autosave";
+/// prefix of localStorage keys of named save slots
+const SLOT_PREFIX: &str = "nabla-game:slot:";
+
+/// errors returned when reading or writing saved games
+#[derive(Clone, Debug, PartialEq)]
+pub enum StorageError {
+    Unavailable,                  // localStorage is disabled or full
+    Missing(String),              // no game saved under this key
+    Incompatible(SerializeError), // save is malformed or from another format version
+}
+
+/// string representation of StorageError, used for logging
+impl Display for StorageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            StorageError::Unavailable => write!(f, "localStorage is unavailable"),
+            StorageError::Missing(key) => write!(f, "No game saved as {}", key),
+            StorageError::Incompatible(error) => write!(f, "Incompatible save: {}", error),
+        }
+    }
+}
+
+/// localStorage of the current window
+fn local_storage() -> std::result::Result<Storage, StorageError> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or(StorageError::Unavailable)
+}
+
+/// localStorage key of a named slot, or of the autosave if `slot` is None
+fn key(slot: Option<&str>) -> String {
+    match slot {
+        Some(name) => format!("{}{}", SLOT_PREFIX, name),
+        None => AUTOSAVE_KEY.to_string(),
+    }
+}
+
+/// writes game to a named slot, or to the autosave if `slot` is None
+pub fn save_game(game: &Game, slot: Option<&str>) -> std::result::Result<(), StorageError> {
+    let json = to_json(game).map_err(StorageError::Incompatible)?;
+    local_storage()?
+        .set_item(&key(slot), &json)
+        .map_err(|_| StorageError::Unavailable)
+}
+
+/// reads game from a named slot, or from the autosave if `slot` is None
+pub fn load_game(slot: Option<&str>) -> std::result::Result<Game, StorageError> {
+    let key = key(slot);
+    let json = local_storage()?
+        .get_item(&key)
+        .map_err(|_| StorageError::Unavailable)?
+        .ok_or_else(|| StorageError::Missing(key.clone()))?;
+    let mut game: Game = from_json(&json).map_err(StorageError::Incompatible)?;
+    game.restore_selection();
+    Ok(game)
+}
+
+/// removes a named slot, or the autosave if `slot` is None
+pub fn delete_game(slot: Option<&str>) {
+    if let Ok(storage) = local_storage() {
+        storage.remove_item(&key(slot)).ok();
+    }
+}
+
+/// checks if a game was saved after the last turn played
+pub fn has_autosave() -> bool {
+    local_storage()
+        .ok()
+        .and_then(|storage| storage.get_item(AUTOSAVE_KEY).ok().flatten())
+        .is_some()
+}
+
+/// names of all save slots, sorted
+pub fn save_slots() -> Vec<String> {
+    let storage = match local_storage() {
+        Ok(storage) => storage,
+        Err(_) => return vec![],
+    };
+    let mut slots = (0..storage.length().unwrap_or(0))
+        .filter_map(|i| storage.key(i).ok().flatten())
+        .filter_map(|key| key.strip_prefix(SLOT_PREFIX).map(str::to_string))
+        .collect::<Vec<String>>();
+    slots.sort();
+    slots
+}
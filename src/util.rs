@@ -1,3 +1,6 @@
+// outer crate imports
+use crate::game::flags::{DISPLAY_LN_FOR_LOG, USE_FRACTIONAL_EXPONENTS};
+
 /// util struct to store a position-based coordinate
 #[derive(Default, Clone, Debug)]
 pub struct Vector2 {
@@ -10,6 +13,59 @@ pub trait ToLatex {
     fn to_latex(&self) -> String;
 }
 
+/// display flags read by MathML representations, see game::flags
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct MathMLFlags {
+    pub ln_for_log: bool,
+    pub fractional_exponents: bool,
+}
+
+impl MathMLFlags {
+    /// current values of DISPLAY_LN_FOR_LOG and USE_FRACTIONAL_EXPONENTS
+    pub fn current() -> MathMLFlags {
+        unsafe {
+            MathMLFlags {
+                ln_for_log: DISPLAY_LN_FOR_LOG,
+                fractional_exponents: USE_FRACTIONAL_EXPONENTS,
+            }
+        }
+    }
+}
+
+/// trait denoting that struct has a MathML representation, as presentation markup without the <math> root
+pub trait ToMathML {
+    /// MathML representation using the current display flags
+    fn to_mathml(&self) -> String {
+        self.to_mathml_with(MathMLFlags::current())
+    }
+    /// MathML representation using the given display flags
+    fn to_mathml_with(&self, flags: MathMLFlags) -> String;
+}
+
+/// languages that expressions can be read aloud in
+#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum Language {
+    English,
+    Japanese,
+}
+
+/// trait denoting that struct can be read aloud, ie. by screen readers announcing card plays
+pub trait ToSpeech {
+    fn to_speech(&self, language: Language) -> String;
+}
+
+/// wraps MathML representation of item in a <math> root, rendered natively by MathML Core browsers
+pub fn math_element<T>(item: &T, display_block: bool) -> String
+where
+    T: ToMathML,
+{
+    format!(
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"{display}\">{mathml}</math>",
+        display = if display_block { "block" } else { "inline" },
+        mathml = item.to_mathml()
+    )
+}
+
 /// extracts id key and id value from id kvp
 pub fn get_key_val(id: &String) -> (String, usize) {
     let kvp = id.split("=").collect::<Vec<&str>>();
@@ -0,0 +1,148 @@
+use nabla_game;
+use nabla_game::basis::structs::*;
+use nabla_game::game::ai::*;
+use nabla_game::game::cards::*;
+use nabla_game::game::engine::*;
+use nabla_game::game::field::FieldBasis;
+use nabla_game::game::structs::*;
+use nabla_game::math::budget::*;
+use nabla_game::math::cache::clear_cache;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+pub mod util;
+
+/// game on player 2's turn, with only a constant left on player 1's half
+fn game_near_win() -> Game {
+    let mut game = Game::new();
+    game.turn.number = 3;
+    game.field[4] = FieldBasis::none();
+    game.field[5] = FieldBasis::none();
+    game.player_2 = vec![
+        Card::BasisCard(BasisCard::Cos),
+        Card::AlgebraicCard(AlgebraicCard::Sqrt),
+        Card::DerivativeCard(DerivativeCard::Derivative),
+        Card::DerivativeCard(DerivativeCard::Integral),
+    ];
+    game
+}
+
+// test every difficulty takes a winning move
+#[test]
+fn test_ai_wins() {
+    let mut rng = StdRng::seed_from_u64(48);
+    for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+        let mut game = game_near_win();
+        let action = choose_action_with(&game, difficulty, &mut rng).unwrap();
+        println!("{:?}: {:?}", difficulty, action);
+        assert_eq!(action, Action::ApplyOperator { card: 2, slot: 3 });
+
+        game.apply(action).unwrap();
+        assert_eq!(game.winner(), Some(2));
+    }
+}
+
+// test evaluation counts bases, threats and is symmetric
+#[test]
+fn test_evaluate() {
+    let mut game = game_near_win();
+    let (a, mut b);
+
+    a = evaluate(&game, 2);
+    b = evaluate(&game, 1);
+    println!("{} {}", a, b);
+    assert_eq!(a, -b);
+    assert!(a > 0);
+
+    // losing the derivative removes the threat on the last basis
+    game.player_2[2] = Card::BasisCard(BasisCard::X);
+    b = evaluate(&game, 2);
+    assert!(b < a);
+
+    game.field[3] = FieldBasis::none();
+    assert_eq!(evaluate(&game, 2), WIN_SCORE);
+    assert_eq!(evaluate(&game, 1), -WIN_SCORE);
+}
+
+// test the computer finds a move from the start of a game, and blocks a loss
+#[test]
+fn test_ai_moves() {
+    let mut rng = StdRng::seed_from_u64(49);
+    let game = Game::new();
+    for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+        let action = choose_action_with(&game, difficulty, &mut rng).unwrap();
+        println!("{:?}: {:?}", difficulty, action);
+        assert!(game.check(&action).is_ok());
+    }
+
+    // player 1 could win by differentiating 1 on slot 0, player 2 has to fill their half
+    // their hand is hidden, so every unseen card is a derivative for any sample to hold one
+    let mut game = Game::new();
+    game.turn.number = 1;
+    game.field[1] = FieldBasis::none();
+    game.field[2] = FieldBasis::none();
+    game.player_1 = vec![Card::DerivativeCard(DerivativeCard::Derivative)];
+    game.deck = vec![Card::DerivativeCard(DerivativeCard::Derivative); 20];
+    game.player_2 = vec![
+        Card::BasisCard(BasisCard::X),
+        Card::AlgebraicCard(AlgebraicCard::Sqrt),
+    ];
+    let action = choose_action_with(&game, Difficulty::Hard, &mut rng).unwrap();
+    println!("{:?}", action);
+    assert!(matches!(
+        action,
+        Action::PlayBasis {
+            card: 0,
+            slot: 1 | 2
+        }
+    ));
+    assert_eq!(game.field[0].basis, Some(Basis::from(1)));
+
+    // every move fails in the CAS, log(-x^2) is defined nowhere, so the computer passes
+    let mut game = Game::new();
+    game.turn.number = 1;
+    for i in 0..6 {
+        game.field[i] = FieldBasis::new(&((Basis::x() ^ 2) * -1));
+    }
+    game.player_2 = vec![Card::AlgebraicCard(AlgebraicCard::Log)];
+    for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+        let action = choose_action_with(&game, difficulty, &mut rng);
+        println!("{:?}: {:?}", difficulty, action);
+        assert_eq!(action, Some(Action::Pass));
+    }
+}
+
+// test the chosen action is one the game accepts under the caller's budget, not only the search's
+#[test]
+fn test_ai_applies() {
+    let mut rng = StdRng::seed_from_u64(50);
+    let game = game_near_win();
+
+    // results the search computed are cached, the rest can't be computed without any steps
+    clear_cache();
+    set_budget(Budget {
+        max_steps: 0,
+        ..Budget::default()
+    });
+    let action = choose_action_with(&game, Difficulty::Medium, &mut rng).unwrap();
+    println!("{:?}", action);
+    assert!(game.clone().apply(action).is_ok());
+    set_budget(Budget::default());
+
+    // a finished game has no action
+    let mut game = game_near_win();
+    game.field[3] = FieldBasis::none();
+    assert_eq!(
+        choose_action_with(&game, Difficulty::Medium, &mut rng),
+        None
+    );
+}
+
+// test settings values parse, unknown ones fall back to the default instead of panicking
+#[test]
+fn test_difficulty_from() {
+    assert_eq!(Difficulty::from("EASY"), Difficulty::Easy);
+    assert_eq!(Difficulty::from("HARD"), Difficulty::Hard);
+    assert_eq!(Difficulty::from("hard"), Difficulty::Medium);
+    assert_eq!(Difficulty::from(""), Difficulty::default());
+}
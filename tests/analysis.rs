@@ -0,0 +1,151 @@
+use nabla_game;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::game::cards::LimitCard;
+use nabla_game::math::analysis::*;
+use nabla_game::math::error::MathError;
+use nabla_game::math::interval::*;
+use nabla_game::math::limits::try_limit;
+
+pub mod util;
+use util::*;
+
+// test monotonic intervals, extrema and inflection points
+#[test]
+fn test_monotonicity() {
+    let (mut a, b);
+
+    // x^3 - 3x rises to (-1, 2), falls to (1, -2), then rises
+    a = (Basis::x() ^ 3) - Basis::x() * 3;
+    println!("{}:\n{}", a, analyze(&a));
+    b = vec![
+        (
+            Interval::open(f64::NEG_INFINITY, -1.0),
+            Monotonicity::Increasing,
+        ),
+        (Interval::open(-1.0, 1.0), Monotonicity::Decreasing),
+        (Interval::open(1.0, f64::INFINITY), Monotonicity::Increasing),
+    ];
+    assert_eq!(monotonicity(&a), b);
+    assert_eq!(
+        extrema(&a),
+        vec![
+            CriticalPoint {
+                x: -1.0,
+                y: 2.0,
+                kind: PointKind::Maximum
+            },
+            CriticalPoint {
+                x: 1.0,
+                y: -2.0,
+                kind: PointKind::Minimum
+            },
+        ]
+    );
+    assert_eq!(
+        inflections(&a),
+        vec![CriticalPoint {
+            x: 0.0,
+            y: 0.0,
+            kind: PointKind::Inflection
+        }]
+    );
+
+    // x^3 is increasing everywhere, with no extrema
+    a = Basis::x() ^ 3;
+    assert_eq!(
+        monotonicity(&a),
+        vec![(Interval::real(), Monotonicity::Increasing)]
+    );
+    assert!(extrema(&a).is_empty());
+
+    // √(1 - x^2) has a maximum at (0, 1) and no inflection on its domain
+    a = SqrtBasisNode(1, &(Basis::from(1) - (Basis::x() ^ 2)));
+    println!("{}:\n{}", a, analyze(&a));
+    assert_eq!(extrema(&a)[0].to_string(), "maximum at (0, 1)");
+    assert!(inflections(&a).is_empty());
+}
+
+// test vertical, horizontal and oblique asymptotes
+#[test]
+fn test_asymptotes() {
+    let (mut a, mut b);
+
+    // 1/x has x = 0 and y = 0 on both sides
+    a = Basis::x() ^ -1;
+    b = vec![
+        Asymptote::Vertical { x: 0.0 },
+        Asymptote::Horizontal {
+            y: Basis::from(0),
+            side: -1,
+        },
+        Asymptote::Horizontal {
+            y: Basis::from(0),
+            side: 1,
+        },
+    ];
+    println!("{}:\n{}", a, analyze(&a));
+    assert_eq!(asymptotes(&a), b);
+
+    // x + 1/x approaches y = x
+    a = Basis::x() + (Basis::x() ^ -1);
+    println!("{}:\n{}", a, analyze(&a));
+    assert!(asymptotes(&a).contains(&Asymptote::Oblique {
+        slope: Basis::from(1),
+        intercept: Basis::from(0),
+        side: 1,
+    }));
+
+    // log(x) has a pole at 0, e^x only approaches 0 as x → -∞
+    assert_eq!(asymptotes(&log_x()), vec![Asymptote::Vertical { x: 0.0 }]);
+    b = vec![Asymptote::Horizontal {
+        y: Basis::from(0),
+        side: -1,
+    }];
+    assert_eq!(asymptotes(&e_x()), b);
+    assert_eq!(b[0].to_string(), "y = 0 as x → -∞");
+
+    // √x ends at 0 without a pole
+    assert!(asymptotes(&SqrtBasisNode(1, &Basis::x()))
+        .iter()
+        .all(|asymptote| !matches!(asymptote, Asymptote::Vertical { .. })));
+}
+
+// test limits of inverse and integral nodes using monotonicity
+#[test]
+fn test_monotone_limits() {
+    let (mut a, mut b);
+
+    // x + e^x is increasing on the real line, so its inverse is too
+    a = InvBasisNode(&(Basis::x() + e_x()));
+    b = try_limit(&LimitCard::LimPosInf, &a).unwrap();
+    println!("lim x→INF {} = {}", a, b);
+    assert_eq!(b, Basis::inf(1));
+    b = try_limit(&LimitCard::LimNegInf, &a).unwrap();
+    assert_eq!(b, Basis::inf(-1));
+
+    // integrals from 0 grow with e^x as x → INF, but may converge as x → -INF
+    a = IntBasisNode(&e_x());
+    assert_eq!(try_limit(&LimitCard::Lim0, &a).unwrap(), Basis::from(0));
+    assert_eq!(try_limit(&LimitCard::LimPosInf, &a).unwrap(), Basis::inf(1));
+    assert!(matches!(
+        try_limit(&LimitCard::LimNegInf, &a),
+        Err(MathError::Unsupported(_))
+    ));
+    a = IntBasisNode(&(Basis::x() ^ 2)) * -1;
+    assert_eq!(try_limit(&LimitCard::LimNegInf, &a).unwrap(), Basis::inf(1));
+
+    // integrals from 0 of integrands unbounded at 0 may diverge
+    for a in [
+        IntBasisNode(&(e_x() / Basis::x())),
+        IntBasisNode(&(Basis::x() ^ -2)),
+    ] {
+        println!("lim x→0 {} = {:?}", a, try_limit(&LimitCard::Lim0, &a));
+        assert!(try_limit(&LimitCard::Lim0, &a).is_err());
+    }
+
+    // INF^-n = 0
+    assert_eq!(
+        try_limit(&LimitCard::LimPosInf, &(Basis::x() ^ -2)).unwrap(),
+        Basis::from(0)
+    );
+}
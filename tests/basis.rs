@@ -164,7 +164,6 @@ fn test_div() {
 }
 
 #[test]
-#[ignore]
 fn test_special_coefficients() {
     let (mut a, mut b);
 
@@ -215,17 +214,17 @@ fn test_special_coefficients() {
 fn test_complex_special_coefficients() {
     let (mut a, mut b);
 
-    // // test sqrt coefficient cancel
-    // a = SqrtBasisNode(1, &Basis::from(2)) * SqrtBasisNode(1, &Basis::from(2));
-    // b = Basis::from(2);
-    // println!("{} = {}", a, b);
-    // assert_eq!(a, b);
+    // test sqrt coefficient cancel
+    a = SqrtBasisNode(1, &Basis::from(2)) * SqrtBasisNode(1, &Basis::from(2));
+    b = Basis::from(2);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
 
-    // // test sqrt coefficient cancel (mult)
-    // a = SqrtBasisNode(1, &Basis::from(2)) * SqrtBasisNode(3, &Basis::from(2));
-    // b = Basis::from(4);
-    // println!("{} = {}", a, b);
-    // assert_eq!(a, b);
+    // test sqrt coefficient cancel (mult)
+    a = SqrtBasisNode(1, &Basis::from(2)) * SqrtBasisNode(3, &Basis::from(2));
+    b = Basis::from(4);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
 
     // test sqrt coefficient cancel (div)
     a = SqrtBasisNode(3, &Basis::from(2)) / SqrtBasisNode(1, &Basis::from(2));
@@ -239,3 +238,52 @@ fn test_complex_special_coefficients() {
     println!("{} = {}", a, b);
     assert_eq!(a, b);
 }
+
+#[test]
+fn test_radical_coefficients() {
+    let (mut a, mut b);
+
+    // test perfect power extraction, sqrt(8) = 2√2
+    a = SqrtBasisNode(1, &Basis::from(8));
+    b = SqrtBasisNode(1, &Basis::from(2)) * 2;
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+
+    // test perfect power extraction, 12^(1/2) = 2√3
+    a = Basis::from(12) ^ (1, 2);
+    b = SqrtBasisNode(1, &Basis::from(3)) * 2;
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+
+    // test rational base, (4/9)^(3/2) = 8/27
+    a = Basis::from((4, 9)) ^ (3, 2);
+    b = Basis::from((8, 27));
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+
+    // test denesting, √2√8 = 4
+    a = SqrtBasisNode(1, &Basis::from(2)) * SqrtBasisNode(1, &Basis::from(8));
+    b = Basis::from(4);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+
+    // test rationalised denominator, 1/√2 = √2/2
+    a = Basis::from(1) / SqrtBasisNode(1, &Basis::from(2));
+    b = SqrtBasisNode(1, &Basis::from(2)) / 2;
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+
+    // test canonical order, √3√2 = √2√3 = √6
+    a = SqrtBasisNode(1, &Basis::from(3)) * SqrtBasisNode(1, &Basis::from(2));
+    b = SqrtBasisNode(1, &Basis::from(2)) * SqrtBasisNode(1, &Basis::from(3));
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+    assert_eq!(a, SqrtBasisNode(1, &Basis::from(6)));
+    assert_eq!(SqrtBasisNode(1, &Basis::from(8)).to_string(), "2√2");
+
+    // test radicals of different degree, √2∛2 = 2^(5/6)
+    a = SqrtBasisNode(1, &Basis::from(2)) * (Basis::from(2) ^ (1, 3));
+    b = Basis::from(32) ^ (1, 6);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+}
@@ -0,0 +1,129 @@
+use nabla_game;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::game::cards::LimitCard;
+use nabla_game::math::budget::*;
+use nabla_game::math::derivative::{derivative, try_derivative};
+use nabla_game::math::domain::{domain, try_domain};
+use nabla_game::math::error::MathError;
+use nabla_game::math::integral::{integral, try_integral};
+use nabla_game::math::interval::IntervalSet;
+use nabla_game::math::inverse::{inverse, try_inverse};
+use nabla_game::math::limits::try_limit;
+use nabla_game::math::solve::{solve, try_solve};
+
+pub mod util;
+use util::*;
+
+// test integrals within budget are unaffected
+#[test]
+fn test_default_budget() {
+    let a = (Basis::x() ^ 3) * e_x();
+    assert_eq!(try_integral(&a), Ok(integral(&a)));
+    assert_eq!(try_inverse(&log_x()), Ok(e_x()));
+    assert_eq!(budget(), Budget::default());
+}
+
+// test each limit of the budget stops integration by parts
+#[test]
+fn test_integral_budget() {
+    let (a, mut b);
+    a = (Basis::x() ^ 3) * e_x();
+    let full = integral(&a);
+
+    set_budget(Budget {
+        max_depth: 1,
+        ..Budget::default()
+    });
+    b = try_integral(&a);
+    println!("{:?}", b);
+    assert!(matches!(b, Err(MathError::RecursionLimit(1))));
+    // nested integrals are left unevaluated instead of erroring
+    b = Ok(integral(&a));
+    println!("{:?}", b);
+    assert_ne!(b, Ok(full));
+
+    set_budget(Budget {
+        max_steps: 3,
+        ..Budget::default()
+    });
+    b = try_integral(&a);
+    assert!(matches!(b, Err(MathError::BudgetExceeded(_))));
+
+    set_budget(Budget {
+        max_nodes: 4,
+        ..Budget::default()
+    });
+    b = try_integral(&a);
+    assert!(matches!(b, Err(MathError::BudgetExceeded(_))));
+    assert_eq!(integral(&a), IntBasisNode(&a));
+
+    // steps are counted per operation, not per thread
+    set_budget(Budget::default());
+    for _ in 0..Budget::default().max_steps {
+        integral(&sin_x());
+    }
+    assert_eq!(try_integral(&a), Ok(integral(&a)));
+}
+
+// test inverse falls back to an unevaluated node
+#[test]
+fn test_inverse_budget() {
+    let a = e_x();
+    set_budget(Budget {
+        max_depth: 0,
+        ..Budget::default()
+    });
+    assert!(matches!(try_inverse(&a), Err(MathError::RecursionLimit(0))));
+    assert_eq!(inverse(&a), InvBasisNode(&a));
+    set_budget(Budget::default());
+    assert_eq!(inverse(&a), log_x());
+}
+
+// test derivatives, limits, roots and domains stop once over budget
+#[test]
+fn test_analysis_budget() {
+    let a = (Basis::x() ^ 3) * e_x();
+    set_budget(Budget {
+        max_nodes: 4,
+        ..Budget::default()
+    });
+    assert!(matches!(
+        try_derivative(&a),
+        Err(MathError::BudgetExceeded(_))
+    ));
+    set_budget(Budget {
+        max_steps: 2,
+        ..Budget::default()
+    });
+    assert!(matches!(
+        try_derivative(&a),
+        Err(MathError::BudgetExceeded(_))
+    ));
+    assert!(matches!(
+        try_limit(&LimitCard::LimPosInf, &a),
+        Err(MathError::BudgetExceeded(_))
+    ));
+
+    // roots fall back to the numeric scan
+    let a = log(&((Basis::x() ^ 2) - Basis::from(3)));
+    set_budget(Budget {
+        max_steps: 1,
+        ..Budget::default()
+    });
+    assert!(matches!(try_solve(&a), Err(MathError::BudgetExceeded(_))));
+    println!("{}", solve(&a));
+    assert!(!solve(&a).complete);
+
+    // domains fall back to ℝ
+    let a = log(&log_x());
+    set_budget(Budget {
+        max_depth: 1,
+        ..Budget::default()
+    });
+    assert!(matches!(try_domain(&a), Err(MathError::RecursionLimit(1))));
+    assert_eq!(domain(&a), IntervalSet::real());
+
+    set_budget(Budget::default());
+    assert_eq!(try_derivative(&a), Ok(derivative(&a)));
+    assert_eq!(try_domain(&a).unwrap().to_string(), "(1, ∞)");
+}
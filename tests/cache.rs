@@ -0,0 +1,121 @@
+use nabla_game;
+use nabla_game::basis::structs::*;
+use nabla_game::game::cards::*;
+use nabla_game::game::engine::Action;
+use nabla_game::game::field::FieldBasis;
+use nabla_game::game::structs::Game;
+use nabla_game::math::budget::*;
+use nabla_game::math::cache::*;
+use nabla_game::math::derivative::derivative;
+use nabla_game::math::error::MathError;
+use nabla_game::math::integral::integral;
+use nabla_game::math::limits::limit;
+
+pub mod util;
+use util::*;
+
+// test cached results match uncached results and record hits
+#[test]
+fn test_cache_hits() {
+    clear_cache();
+
+    let a = Basis::x() * log_x();
+    assert_eq!(cached_integral(&a), Ok(integral(&a)));
+    assert_eq!(cached_integral(&a), Ok(integral(&a)));
+    assert_eq!(cached_derivative(&a), Ok(derivative(&a)));
+
+    let stats = cache_stats();
+    println!("{:?}", stats);
+    assert_eq!(stats.hits, 1);
+    assert_eq!(stats.misses, 2);
+    assert_eq!(stats.entries, 2);
+
+    // invalid limits are cached too
+    let b = sin_x();
+    assert!(cached_limit(&LimitCard::LimPosInf, &b).is_err());
+    assert_eq!(
+        cached_limit(&LimitCard::LimPosInf, &b).ok(),
+        limit(&LimitCard::LimPosInf)(&b)
+    );
+    assert_eq!(cache_stats().hits, 2);
+
+    clear_cache();
+    assert_eq!(cache_stats(), CacheStats::default());
+}
+
+// test inverses only depend on their input and bounded capacity
+#[test]
+fn test_cache_inverse_and_eviction() {
+    clear_cache();
+
+    // f-1(f-1(x)) = f(x) is computed, not taken from the first inverse
+    let a = e_x();
+    let b = cached_inverse(&a).unwrap();
+    println!("f-1({}) = {}", a, b);
+    assert_eq!(b, log_x());
+    assert_eq!(cached_inverse(&b), Ok(a.clone()));
+    assert_eq!(cache_stats().hits, 0);
+    assert_eq!(cached_inverse(&a), Ok(b));
+    assert_eq!(cache_stats().hits, 1);
+
+    set_cache_capacity(2);
+    cached_derivative(&sin_x()).unwrap();
+    cached_derivative(&cos_x()).unwrap();
+    cached_derivative(&e_x()).unwrap();
+
+    let stats = cache_stats();
+    println!("{:?}", stats);
+    assert_eq!(stats.entries, 2);
+    assert!(stats.evictions >= 2);
+
+    set_cache_capacity(DEFAULT_CACHE_CAPACITY);
+}
+
+// test inverse cards go through the cache, and inverting back through the inverses of the field
+#[test]
+fn test_cache_inverse_card() {
+    clear_cache();
+
+    let mut game = Game::new();
+    game.field[0] = FieldBasis::new(&e_x());
+    game.player_1[0] = Card::AlgebraicCard(AlgebraicCard::Inverse);
+    game.apply(Action::ApplyOperator { card: 0, slot: 0 })
+        .unwrap();
+    assert_eq!(game.field[0].basis, Some(log_x()));
+    assert_eq!(cached_inverse(&e_x()), Ok(log_x()));
+    assert_eq!(cache_stats().hits, 1);
+
+    // the field keeps the played e^x, the cache isn't asked again
+    game.turn.number = 2;
+    game.player_1[0] = Card::AlgebraicCard(AlgebraicCard::Inverse);
+    let stats = cache_stats();
+    game.apply(Action::ApplyOperator { card: 0, slot: 0 })
+        .unwrap();
+    assert_eq!(game.field[0].basis, Some(e_x()));
+    assert_eq!(cache_stats().misses, stats.misses);
+    assert_eq!(cache_stats().hits, stats.hits);
+}
+
+// test changing the budget keeps results and only drops errors of the previous budget
+#[test]
+fn test_cache_budget() {
+    clear_cache();
+
+    let a = (Basis::x() ^ 3) * e_x();
+    let b = cached_derivative(&sin_x()).unwrap();
+    set_budget(Budget {
+        max_steps: 3,
+        ..Budget::default()
+    });
+    assert!(matches!(
+        cached_integral(&a),
+        Err(MathError::BudgetExceeded(_))
+    ));
+    assert_eq!(cache_stats().entries, 2);
+
+    set_budget(Budget::default());
+    assert_eq!(cache_stats().entries, 1);
+    assert_eq!(cached_derivative(&sin_x()), Ok(b));
+    assert_eq!(cache_stats().hits, 1);
+    assert!(cached_integral(&a).is_ok());
+}
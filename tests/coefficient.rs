@@ -0,0 +1,180 @@
+use nabla_game;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::game::cards::LimitCard;
+use nabla_game::math::coefficient::*;
+use nabla_game::math::equivalence::*;
+use nabla_game::math::fraction::Fraction;
+use nabla_game::math::integral::integral;
+use nabla_game::math::inverse::inverse;
+use nabla_game::math::limits::limit;
+
+pub mod util;
+use util::*;
+
+fn sqrt(n: i32) -> Coefficient {
+    Coefficient::from(n).pow(Fraction::from((1, 2))).unwrap()
+}
+
+fn mul(a: Coefficient, b: impl Into<Coefficient>) -> Coefficient {
+    a.checked_mul(b.into()).unwrap()
+}
+
+fn div(a: Coefficient, b: impl Into<Coefficient>) -> Coefficient {
+    a.checked_div(b.into()).unwrap()
+}
+
+// test simplification and arithmetic of symbolic coefficients
+#[test]
+fn test_coefficient_arithmetic() {
+    let (mut a, mut b);
+
+    // sqrt(8) = 2√2
+    a = sqrt(8);
+    b = mul(sqrt(2), 2);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+    assert_eq!(a.to_string(), "2√2");
+
+    // √2√2 = 2, √2√3 = √6
+    assert_eq!(mul(sqrt(2), sqrt(2)), Coefficient::from(2));
+    a = mul(sqrt(2), sqrt(3));
+    b = sqrt(6);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+
+    // 1/√2 = √2/2
+    a = div(Coefficient::from(1), sqrt(2));
+    b = div(sqrt(2), 2);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+
+    // log(8) = 3log(2), log(1) = 0
+    a = Coefficient::log(8).unwrap();
+    b = mul(Coefficient::log(2).unwrap(), 3);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+    assert_eq!(Coefficient::log(1), Some(Coefficient::from(0)));
+    assert_eq!(Coefficient::log(-1), None);
+
+    // e^(1/2)e^(1/2) = e, π/π = 1
+    a = mul(
+        Coefficient::e(Fraction::from((1, 2))),
+        Coefficient::e(Fraction::from((1, 2))),
+    );
+    b = Coefficient::e(Fraction::from(1));
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+    assert_eq!(
+        div(Coefficient::pi(), Coefficient::pi()),
+        Coefficient::from(1)
+    );
+
+    // odd roots of negatives are real, even roots are not
+    a = Coefficient::from(-8).pow(Fraction::from((1, 3))).unwrap();
+    b = Coefficient::from(-2);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+    assert_eq!(Coefficient::from(-2).pow(Fraction::from((1, 2))), None);
+
+    a = mul(
+        mul(Coefficient::log(2).unwrap(), Coefficient::pi()),
+        sqrt(3),
+    );
+    println!("{} ≃ {}", a, a.to_f64());
+    assert!((a.to_f64() - 2f64.ln() * std::f64::consts::PI * 3f64.sqrt()).abs() < 1e-12);
+}
+
+// test conversion between Coefficient and constant Bases
+#[test]
+fn test_coefficient_basis() {
+    let (mut a, mut b);
+
+    a = SqrtBasisNode(1, &Basis::from(2)) * 3;
+    b = mul(sqrt(2), 3);
+    println!("{} = {}", a, b);
+    assert_eq!(Coefficient::from_basis(&a), Some(b.clone()));
+    assert_eq!(Coefficient::from_basis(&b.to_basis()), Some(b));
+
+    a = LogBasisNode(&Basis::from(4)) * EBasisNode(&Basis::from(2));
+    b = mul(
+        mul(
+            Coefficient::log(2).unwrap(),
+            Coefficient::e(Fraction::from(2)),
+        ),
+        2,
+    );
+    println!("{} = {}", a, b);
+    assert_eq!(Coefficient::from_basis(&a), Some(b.clone()));
+    assert_eq!(Coefficient::from_basis(&b.to_basis()), Some(b));
+
+    // acos(-1) = π
+    a = ACosBasisNode(&Basis::from(-1));
+    assert_eq!(Coefficient::from_basis(&a), Some(Coefficient::pi()));
+
+    // non constant bases
+    assert_eq!(Coefficient::from_basis(&Basis::x()), None);
+    assert_eq!(Coefficient::from_basis(&sin_x()), None);
+
+    // √2xe^x = (√2, xe^x)
+    a = SqrtBasisNode(1, &Basis::from(2)) * Basis::x() * e_x();
+    let (constant, rest) = split_constant(&a);
+    println!("{} = ({}, {})", a, constant, rest);
+    assert_eq!(constant, sqrt(2));
+    assert_eq!(rest, Basis::x() * e_x());
+
+    // powers overflowing i32 are left unevaluated instead of panicking
+    a = Basis::from(2) ^ 40;
+    println!("2^40 = {}", a);
+    assert!(a.is_node(BasisOperator::Pow(Fraction::from(40))));
+    assert_eq!(Coefficient::from(2).pow(Fraction::from(40)), None);
+    a = SqrtBasisNode(1, &(Basis::from(3) ^ 21));
+    println!("√(3^21) = {}", a);
+    assert!(a.is_node(BasisOperator::Pow(Fraction::from((1, 2)))));
+
+    // radicals whose product overflows i32 are left as a product, √46349√46351
+    assert_eq!(sqrt(46349).checked_mul(sqrt(46351)), None);
+    a = SqrtBasisNode(1, &Basis::from(46349)) * SqrtBasisNode(1, &Basis::from(46351));
+    println!("√46349√46351 = {}", a);
+    assert!(a.is_node(BasisOperator::Mult));
+    let (constant, rest) = split_constant(&a);
+    println!("{} = ({}, {})", a, constant, rest);
+    assert_eq!(
+        constant.checked_mul(Coefficient::from_basis(&rest).unwrap()),
+        None
+    );
+}
+
+// test symbolic coefficients are treated as constants by the CAS
+#[test]
+fn test_coefficient_cas() {
+    let sqrt_2 = SqrtBasisNode(1, &Basis::from(2));
+    let (mut a, mut b);
+
+    // I(√2) = √2x
+    a = integral(&sqrt_2);
+    b = sqrt_2.clone() * Basis::x();
+    println!("I({}) = {}", sqrt_2, b);
+    assert_eq!(a, b);
+
+    // I(log(2)cos(x)) = log(2)sin(x)
+    a = integral(&(LogBasisNode(&Basis::from(2)) * cos_x()));
+    b = LogBasisNode(&Basis::from(2)) * sin_x();
+    println!("I(log(2)cos(x)) = {}", b);
+    assert_eq!(a, b);
+
+    // f-1(√2x) = x/√2
+    a = inverse(&(sqrt_2.clone() * Basis::x()));
+    b = Basis::x() / sqrt_2.clone();
+    println!("f-1(√2x) = {}", a);
+    assert_ne!(equivalent(&a, &b), Equivalence::Different);
+
+    // lim x→INF -√2x = -INF
+    a = limit(&LimitCard::LimPosInf)(&(-(sqrt_2.clone() * Basis::x()))).unwrap();
+    b = Basis::inf(-1);
+    println!("lim -√2x = {}", a);
+    assert_eq!(a, b);
+
+    // lim x→0 π = π
+    a = ACosBasisNode(&Basis::from(-1));
+    assert_eq!(limit(&LimitCard::Lim0)(&a), Some(a.clone()));
+}
@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use nabla_game;
-use nabla_game::basis::builders::ACosBasisNode;
+use nabla_game::basis::builders::{ACosBasisNode, ASinBasisNode};
 use nabla_game::basis::structs::*;
 use nabla_game::math::derivative::derivative;
 
@@ -69,6 +69,12 @@ fn test_mult_derivatives() {
     println!("d/dx({}) = {}", a, b);
     assert_eq!(derivative(&a), b);
 
+    // test mult coefficient is kept
+    a = 3 * Basis::x() * sin_x();
+    b = (3 * Basis::x() * cos_x()) + (3 * sin_x());
+    println!("d/dx({}) = {}", a, b);
+    assert_eq!(derivative(&a), b);
+
     // test trim 1
     a = Basis::x() * e_x();
     b = (Basis::x() * e_x()) + e_x();
@@ -135,3 +141,27 @@ fn test_log_derivatives() {
     println!("d/dx({}) = {}", a, b);
     assert_eq!(derivative(&a), b);
 }
+
+// test inverse trig derivatives apply the chain rule
+#[test]
+fn test_inverse_trig_derivatives() {
+    let (mut a, mut b);
+
+    // test acos(x) derivative
+    a = ACosBasisNode(&Basis::x());
+    b = -Basis::from(1) / ((Basis::from(1) - (Basis::x() ^ 2)) ^ (1, 2));
+    println!("d/dx({}) = {}", a, b);
+    assert_eq!(derivative(&a), b);
+
+    // test acos(x^2) derivative
+    a = ACosBasisNode(&(Basis::x() ^ 2));
+    b = (-2 * Basis::x()) / ((Basis::from(1) - (Basis::x() ^ 4)) ^ (1, 2));
+    println!("d/dx({}) = {}", a, b);
+    assert_eq!(derivative(&a), b);
+
+    // test asin(x^2) derivative
+    a = ASinBasisNode(&(Basis::x() ^ 2));
+    b = (2 * Basis::x()) / ((Basis::from(1) - (Basis::x() ^ 4)) ^ (1, 2));
+    println!("d/dx({}) = {}", a, b);
+    assert_eq!(derivative(&a), b);
+}
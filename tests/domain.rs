@@ -0,0 +1,119 @@
+use nabla_game;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::game::cards::*;
+use nabla_game::math::domain::*;
+use nabla_game::math::error::MathError;
+use nabla_game::math::interval::*;
+use nabla_game::math::limits::try_limit;
+
+pub mod util;
+use util::*;
+
+// test domains of functions with restricted operands
+#[test]
+fn test_domain() {
+    let (mut a, mut b);
+
+    // polynomials and e^x are defined everywhere
+    a = (Basis::x() ^ 2) + e_x();
+    println!("domain of {} = {}", a, domain(&a));
+    assert_eq!(domain(&a), IntervalSet::real());
+
+    // log(x) on (0, ∞)
+    a = log_x();
+    b = IntervalSet::from(Interval::open(0.0, f64::INFINITY));
+    println!("domain of {} = {}", a, domain(&a));
+    assert_eq!(domain(&a), b);
+
+    // √(1 - x^2) on [-1, 1]
+    a = SqrtBasisNode(1, &(Basis::from(1) - (Basis::x() ^ 2)));
+    b = IntervalSet::from(Interval::closed(-1.0, 1.0));
+    println!("domain of {} = {}", a, domain(&a));
+    assert_eq!(domain(&a), b);
+
+    // 1/x on (-∞, 0) ∪ (0, ∞)
+    a = Basis::x() ^ -1;
+    println!("domain of {} = {}", a, domain(&a));
+    assert_eq!(domain(&a).to_string(), "(-∞, 0) ∪ (0, ∞)");
+
+    // arccos(2x) on [-1/2, 1/2]
+    a = ACosBasisNode(&(Basis::x() * 2));
+    b = IntervalSet::from(Interval::closed(-0.5, 0.5));
+    println!("domain of {} = {}", a, domain(&a));
+    assert_eq!(domain(&a), b);
+
+    // log(-x^2) is defined nowhere
+    a = log(&-(Basis::x() ^ 2));
+    println!("domain of {} = {}", a, domain(&a));
+    assert!(domain(&a).is_empty());
+
+    // the Inf leaf is defined everywhere
+    assert_eq!(domain(&Basis::inf(1)), IntervalSet::real());
+}
+
+// test signs of functions on intervals
+#[test]
+fn test_sign_on() {
+    let a = (Basis::x() ^ 2) - Basis::from(1);
+
+    // x^2 - 1 < 0 on (-1, 1), changes sign on [0, 2]
+    assert_eq!(sign_on(&a, &Interval::open(-1.0, 1.0)), Some(-1));
+    assert_eq!(sign_on(&a, &Interval::open(1.0, f64::INFINITY)), Some(1));
+    assert_eq!(sign_on(&a, &Interval::closed(0.0, 2.0)), None);
+    assert_eq!(sign_on(&a, &Interval::closed(1.0, 1.0)), Some(0));
+
+    // log(x) is not defined on all of [-1, 1]
+    assert_eq!(sign_on(&log_x(), &Interval::closed(-1.0, 1.0)), None);
+    assert_eq!(sign_on(&sin_x(), &Interval::open(0.0, 3.0)), Some(1));
+    assert_eq!(sign_on(&Basis::inf(-1), &Interval::real()), Some(-1));
+}
+
+// test limits and cards respect domains
+#[test]
+fn test_domain_rules() {
+    // x cannot approach -INF in log(x) or 0 in log(x - 1)
+    assert!(matches!(
+        try_limit(&LimitCard::LimNegInf, &log_x()),
+        Err(MathError::DomainError(_))
+    ));
+    assert!(matches!(
+        try_limit(&LimitCard::Lim0, &log(&(Basis::x() - Basis::from(1)))),
+        Err(MathError::DomainError(_))
+    ));
+    assert_eq!(
+        try_limit(&LimitCard::Lim0, &log_x()).unwrap(),
+        Basis::inf(-1)
+    );
+
+    // log card on -x^2 is rejected
+    let a = -(Basis::x() ^ 2);
+    let result = apply_card(&Card::AlgebraicCard(AlgebraicCard::Log))(&a);
+    println!("log({}) = {:?}", a, result);
+    assert!(matches!(result, Err(MathError::DomainError(_))));
+}
+
+// test periodic restrictions are only listed within a window, never truncated silently
+#[test]
+fn test_periodic_domain() {
+    let (a, b);
+
+    // 1/sin(x) misses every kπ, which can't be listed on all of ℝ
+    a = Basis::from(1) / sin_x();
+    b = try_domain(&a);
+    println!("dom({}) = {:?}", a, b);
+    assert!(matches!(b, Err(MathError::Unsupported(_))));
+    assert!(!domain(&a).contains(0.0));
+    assert!(!domain(&a).contains(std::f64::consts::PI));
+    assert!(domain(&a).contains(1.0));
+
+    // x → INF needs the whole domain, x → 0 only needs points near 0
+    assert!(matches!(
+        try_limit(&LimitCard::LimPosInf, &a),
+        Err(MathError::Unsupported(_))
+    ));
+    assert!(try_limit(&LimitCard::Lim0, &(sin_x() / cos_x())).is_ok());
+
+    // bounded intervals only need the roots inside them
+    assert_eq!(sign_on(&a, &Interval::open(0.0, 3.0)), Some(1));
+    assert_eq!(sign_on(&a, &Interval::open(0.0, f64::INFINITY)), None);
+}
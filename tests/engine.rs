@@ -0,0 +1,351 @@
+use nabla_game;
+use nabla_game::basis::structs::*;
+use nabla_game::game::cards::*;
+use nabla_game::game::engine::*;
+use nabla_game::game::field::FieldBasis;
+use nabla_game::game::structs::*;
+use nabla_game::math::budget::*;
+
+pub mod util;
+use util::*;
+
+/// new game with player 1 holding given hand
+fn game_with_hand(hand: Vec<Card>) -> Game {
+    let mut game = Game::new();
+    game.player_1 = hand;
+    game
+}
+
+// test basis cards, single operators and refilling the hand
+#[test]
+fn test_apply_single() {
+    let mut game = game_with_hand(vec![
+        Card::BasisCard(BasisCard::Cos),
+        Card::DerivativeCard(DerivativeCard::Derivative),
+    ]);
+    let deck_size = game.deck.len();
+    game.field[0] = FieldBasis::none();
+
+    let events = game.apply(Action::PlayBasis { card: 0, slot: 0 }).unwrap();
+    println!("{:?}", events);
+    assert_eq!(game.field[0].basis, Some(Basis::from(BasisCard::Cos)));
+    assert_eq!(game.graveyard, vec![Card::BasisCard(BasisCard::Cos)]);
+    assert_eq!(game.player_1.len(), HAND_SIZE);
+    assert_eq!(
+        game.player_1[0],
+        Card::DerivativeCard(DerivativeCard::Derivative)
+    );
+    assert_eq!(game.deck.len(), deck_size - (HAND_SIZE - 1));
+    assert_eq!(game.get_current_player_num(), 2);
+    assert!(events.contains(&GameEvent::FieldChanged { slot: 0 }));
+    assert!(events.contains(&GameEvent::CardsDealt {
+        player: 1,
+        slots: (1..HAND_SIZE).collect()
+    }));
+    assert!(events.contains(&GameEvent::TurnEnded { next_player: 2 }));
+
+    // player 2 differentiates x^2
+    game.player_2[0] = Card::DerivativeCard(DerivativeCard::Derivative);
+    game.apply(Action::ApplyOperator { card: 0, slot: 5 })
+        .unwrap();
+    assert_eq!(game.field[5].basis, Some(Basis::x() * 2));
+    assert_eq!(game.field[5].index, -1);
+
+    // inverting back uses the inverses played this game
+    game.field[0] = FieldBasis::new(&e_x());
+    game.player_1[0] = Card::AlgebraicCard(AlgebraicCard::Inverse);
+    game.apply(Action::ApplyOperator { card: 0, slot: 0 })
+        .unwrap();
+    assert_eq!(game.field[0].basis, Some(log_x()));
+    assert_eq!(game.field.inverses.get(&log_x()), Some(&e_x()));
+    game.player_2[0] = Card::AlgebraicCard(AlgebraicCard::Inverse);
+    game.apply(Action::ApplyOperator { card: 0, slot: 0 })
+        .unwrap();
+    assert_eq!(game.field[0].basis, Some(e_x()));
+}
+
+// test nabla on half of the field and combining with mult
+#[test]
+fn test_apply_field() {
+    let mut game = game_with_hand(vec![
+        Card::DerivativeCard(DerivativeCard::Nabla),
+        Card::AlgebraicCard(AlgebraicCard::Mult),
+        Card::BasisCard(BasisCard::X),
+    ]);
+
+    let events = game
+        .apply(Action::FieldOperator { card: 0, half: 0 })
+        .unwrap();
+    println!("{:?}", events);
+    assert_eq!(game.field[0].basis, None);
+    assert_eq!(game.field[1].basis, Some(Basis::from(1)));
+    assert_eq!(game.field[2].basis, Some(Basis::x() * 2));
+    assert_eq!(game.field[3].basis, Some(Basis::from(1)));
+
+    // skip player 2 turn
+    game.turn.number += 1;
+    game.player_1 = vec![
+        Card::AlgebraicCard(AlgebraicCard::Mult),
+        Card::BasisCard(BasisCard::X),
+    ];
+    game.apply(Action::MultiCombine {
+        card: 0,
+        operands: vec![Operand::Field(4), Operand::Hand(1)],
+    })
+    .unwrap();
+    assert_eq!(game.field[4].basis, Some(Basis::x() ^ 2));
+    assert_eq!(
+        game.graveyard[game.graveyard.len() - 2..],
+        [
+            Card::BasisCard(BasisCard::X),
+            Card::AlgebraicCard(AlgebraicCard::Mult)
+        ]
+    );
+}
+
+// test end of turn rules are read from the game, d/dx x^2 = 2x is a multiple of x
+#[test]
+fn test_apply_rules() {
+    let hand = vec![Card::DerivativeCard(DerivativeCard::Derivative)];
+    let mut game = game_with_hand(hand.clone());
+    game.apply(Action::ApplyOperator { card: 0, slot: 2 })
+        .unwrap();
+    assert_eq!(game.field[2].basis, Some(Basis::x() * 2));
+
+    // 2x is cleared as it depends on x
+    for rules in [
+        Rules {
+            allow_linear_dependence: false,
+            ..Rules::default()
+        },
+        Rules {
+            remove_dependent_bases: true,
+            ..Rules::default()
+        },
+    ] {
+        game = game_with_hand(hand.clone());
+        game.rules = rules;
+        let events = game
+            .apply(Action::ApplyOperator { card: 0, slot: 2 })
+            .unwrap();
+        println!("{:?}: {:?}", rules, events);
+        assert_eq!(game.field[1].basis, Some(Basis::x()));
+        assert_eq!(game.field[2].basis, None);
+        assert!(events.contains(&GameEvent::FieldChanged { slot: 2 }));
+    }
+}
+
+// test rejected actions leave the game unchanged, and the end of the game
+#[test]
+fn test_apply_rejected() {
+    let mut game = game_with_hand(vec![
+        Card::BasisCard(BasisCard::Zero),
+        Card::BasisCard(BasisCard::X),
+        Card::DerivativeCard(DerivativeCard::Laplacian),
+        Card::AlgebraicCard(AlgebraicCard::Div),
+        Card::AlgebraicCard(AlgebraicCard::Log),
+    ]);
+    game.field[0] = FieldBasis::none();
+
+    let rejected = vec![
+        Action::PlayBasis { card: 0, slot: 0 },
+        Action::PlayBasis { card: 1, slot: 1 },
+        Action::PlayBasis { card: 1, slot: 6 },
+        Action::PlayBasis { card: 9, slot: 0 },
+        Action::ApplyOperator { card: 1, slot: 1 },
+        Action::ApplyOperator { card: 4, slot: 0 },
+        Action::FieldOperator { card: 2, half: 1 },
+        Action::MultiCombine {
+            card: 3,
+            operands: vec![Operand::Hand(0), Operand::Hand(1)],
+        },
+        Action::MultiCombine {
+            card: 3,
+            operands: vec![Operand::Field(1), Operand::Field(1)],
+        },
+        Action::MultiCombine {
+            card: 3,
+            operands: vec![Operand::Field(1), Operand::Field(2), Operand::Hand(0)],
+        },
+    ];
+    for action in rejected {
+        let error = game.apply(action.clone()).unwrap_err();
+        println!("{:?}: {}", action, error);
+    }
+    assert_eq!(game.turn.number, 0);
+    assert_eq!(game.player_1.len(), 5);
+    assert!(game.graveyard.is_empty());
+    assert_eq!(game.field[0].basis, None);
+    assert_eq!(game.field[1].basis, Some(Basis::x()));
+
+    // CAS errors are rejected too, log(-x^2) is defined nowhere
+    game.field[1] = FieldBasis::new(&((Basis::x() ^ 2) * -1));
+    assert!(matches!(
+        game.apply(Action::ApplyOperator { card: 4, slot: 1 }),
+        Err(RuleError::Math(_))
+    ));
+    assert_eq!(game.turn.number, 0);
+
+    // emptying player 2 half wins for player 1, 0/x clears the last basis
+    game.field[1] = FieldBasis::new(&Basis::x());
+    game.field[2] = FieldBasis::none();
+    let events = game
+        .apply(Action::MultiCombine {
+            card: 3,
+            operands: vec![Operand::Hand(0), Operand::Field(1)],
+        })
+        .unwrap();
+    println!("{:?}", events);
+    assert_eq!(game.winner(), Some(1));
+    assert!(events.contains(&GameEvent::GameOver { winner: 1 }));
+    assert_eq!(
+        game.apply(Action::PlayBasis { card: 0, slot: 0 }),
+        Err(RuleError::GameOver)
+    );
+}
+
+// test legal actions match the rules checked by apply
+#[test]
+fn test_legal_actions() {
+    let mut game = game_with_hand(vec![
+        Card::BasisCard(BasisCard::Zero),
+        Card::BasisCard(BasisCard::X),
+        Card::DerivativeCard(DerivativeCard::Laplacian),
+        Card::AlgebraicCard(AlgebraicCard::Mult),
+        Card::DerivativeCard(DerivativeCard::Derivative),
+    ]);
+    game.field[0] = FieldBasis::none();
+
+    let actions = game.legal_actions();
+    println!("{} actions", actions.len());
+    assert!(actions.iter().all(|action| game.check(action).is_ok()));
+    let basis_actions = actions
+        .iter()
+        .filter(|action| matches!(action, Action::PlayBasis { .. }))
+        .collect::<Vec<&Action>>();
+    assert_eq!(basis_actions, vec![&Action::PlayBasis { card: 1, slot: 0 }]);
+    // laplacian can't be played on the first turn
+    assert!(actions.iter().all(|action| action.card() != Some(2)));
+    assert_eq!(
+        actions
+            .iter()
+            .filter(|action| action.card() == Some(4))
+            .count(),
+        5
+    );
+    assert!(actions.contains(&Action::MultiCombine {
+        card: 3,
+        operands: vec![Operand::Field(1), Operand::Hand(0)]
+    }));
+    // 0 only combines with a single basis, every operand set needs a field basis
+    assert!(actions.iter().all(|action| match action {
+        Action::MultiCombine { operands, .. } =>
+            (operands.len() == 2 || !operands.contains(&Operand::Hand(0)))
+                && operands
+                    .iter()
+                    .any(|operand| matches!(operand, Operand::Field(_))),
+        _ => true,
+    }));
+    assert!(!actions.contains(&Action::Pass));
+    assert!(game.check(&Action::Pass).is_err());
+
+    // Div lists both orders of two operands
+    game.turn.number = 2;
+    game.player_1 = vec![Card::AlgebraicCard(AlgebraicCard::Div)];
+    for i in [0, 2, 4, 5] {
+        game.field[i] = FieldBasis::none();
+    }
+    assert_eq!(
+        game.legal_actions(),
+        vec![
+            Action::MultiCombine {
+                card: 0,
+                operands: vec![Operand::Field(1), Operand::Field(3)]
+            },
+            Action::MultiCombine {
+                card: 0,
+                operands: vec![Operand::Field(3), Operand::Field(1)]
+            },
+        ]
+    );
+
+    // no playable card leaves only passing
+    game.player_1 = vec![Card::BasisCard(BasisCard::Zero); 2];
+    assert_eq!(game.legal_actions(), vec![Action::Pass]);
+    let events = game.apply(Action::Pass).unwrap();
+    println!("{:?}", events);
+    assert_eq!(game.turn.number, 3);
+    assert_eq!(game.player_1.len(), HAND_SIZE);
+
+    // passing is allowed when every legal action fails in the CAS, log(-x^2) is defined nowhere
+    game = game_with_hand(vec![Card::AlgebraicCard(AlgebraicCard::Log)]);
+    for i in 0..6 {
+        game.field[i] = FieldBasis::new(&((Basis::x() ^ 2) * -1));
+    }
+    let actions = game.legal_actions();
+    println!("{:?}", actions);
+    assert_eq!(actions.len(), 7);
+    assert!(actions.contains(&Action::Pass));
+    assert!(game.check(&Action::Pass).is_ok());
+
+    // the cached CAS check follows the hand, -x^2 can be differentiated
+    game.player_1 = vec![Card::DerivativeCard(DerivativeCard::Derivative)];
+    assert!(!game.legal_actions().contains(&Action::Pass));
+    game.player_1 = vec![Card::AlgebraicCard(AlgebraicCard::Log)];
+    game.apply(Action::Pass).unwrap();
+    assert_eq!(game.turn.number, 1);
+
+    // and the CAS budget, an integral over budget doesn't allow passing once the budget is restored
+    game.player_2 = vec![Card::DerivativeCard(DerivativeCard::Integral)];
+    set_budget(Budget {
+        max_steps: 0,
+        ..Budget::default()
+    });
+    assert!(game.check(&Action::Pass).is_ok());
+    set_budget(Budget::default());
+    assert!(game.check(&Action::Pass).is_err());
+}
+
+// test Mult/Div targets follow the operands selected so far
+#[test]
+fn test_next_operands() {
+    let mut game = game_with_hand(vec![
+        Card::BasisCard(BasisCard::Zero),
+        Card::BasisCard(BasisCard::X),
+        Card::AlgebraicCard(AlgebraicCard::Mult),
+    ]);
+    let fields = (0..6).map(Operand::Field).collect::<Vec<Operand>>();
+
+    // anything can start a selection
+    let mut b = fields.clone();
+    b.extend([Operand::Hand(0), Operand::Hand(1)]);
+    assert_eq!(game.next_operands(2, &[]), b);
+
+    // 0 only combines with a single field basis
+    assert_eq!(game.next_operands(2, &[Operand::Hand(0)]), fields);
+    assert!(game
+        .next_operands(2, &[Operand::Field(1), Operand::Hand(0)])
+        .is_empty());
+
+    // a field basis lets any other item follow, except 0 once there are 2
+    b = fields.clone();
+    b.retain(|operand| *operand != Operand::Field(1));
+    b.extend([Operand::Hand(0), Operand::Hand(1)]);
+    assert_eq!(game.next_operands(2, &[Operand::Field(1)]), b);
+    assert!(!game
+        .next_operands(2, &[Operand::Field(1), Operand::Field(2)])
+        .contains(&Operand::Hand(0)));
+
+    // selected operands, empty slots and the card itself can't be selected, only these are clickable
+    game.field[0] = FieldBasis::none();
+    let selected = [Operand::Field(1), Operand::Hand(1)];
+    b = game.next_operands(2, &selected);
+    println!("{:?}", b);
+    assert!(!b.is_empty());
+    for operand in selected
+        .iter()
+        .chain([Operand::Field(0), Operand::Hand(2)].iter())
+    {
+        assert!(!b.contains(operand));
+    }
+}
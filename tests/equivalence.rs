@@ -0,0 +1,85 @@
+use nabla_game;
+use nabla_game::basis;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::math::equivalence::*;
+use nabla_game::math::eval::*;
+use nabla_game::math::fraction::Fraction;
+use nabla_game::math::integral::integral;
+
+pub mod util;
+use util::*;
+
+// test numeric and exact evaluation
+#[test]
+fn test_evaluate() {
+    let a = basis!(x ^ 2 + 3 * x - 1);
+    assert_eq!(evaluate(&a, 2.0), Some(9.0));
+    assert_eq!(
+        evaluate_exact(&a, Fraction::from((1, 2))),
+        Some(Fraction::from((3, 4)))
+    );
+
+    // log undefined at 0, sin irrational
+    assert_eq!(evaluate(&log_x(), 0.0), None);
+    assert_eq!(evaluate_exact(&sin_x(), Fraction::from(1)), None);
+    assert!((evaluate(&sin_x(), 1.0).unwrap() - 1f64.sin()).abs() < 1e-12);
+    assert_eq!(evaluate(&IntBasisNode(&sin_x()), 1.0), None);
+}
+
+// test equivalence of different shapes of the same function
+#[test]
+fn test_equivalent() {
+    let (mut a, mut b);
+
+    a = basis!(x);
+    assert_eq!(equivalent(&a, &a), Equivalence::Proven);
+
+    // (x + 1)^2 = x^2 + 2x + 1
+    a = basis!((x + 1) * (x + 1));
+    b = basis!(x ^ 2 + 2 * x + 1);
+    println!("{} ~ {}", a, b);
+    assert_eq!(equivalent(&a, &b), Equivalence::Proven);
+    assert_eq!(equivalent(&a, &basis!(x ^ 2 + 1)), Equivalence::Different);
+
+    // sin^2 + cos^2 = 1
+    a = basis!(sin(x) ^ 2 + cos(x) ^ 2);
+    b = basis!(1);
+    println!("{} ~ {}", a, b);
+    assert_eq!(equivalent(&a, &b), Equivalence::Likely);
+
+    // log(x^2) = 2log(x) for x > 0
+    a = basis!(log(x ^ 2));
+    b = basis!(2 * log(x));
+    assert_ne!(equivalent(&a, &b), Equivalence::Different);
+
+    a = basis!(e ^ x);
+    b = basis!(e ^ (2 * x));
+    assert_eq!(equivalent(&a, &b), Equivalence::Different);
+
+    // integrals cannot be compared numerically
+    a = IntBasisNode(&sin_x());
+    assert_eq!(equivalent(&a, &cos_x()), Equivalence::Different);
+}
+
+// test antiderivatives and linear dependence
+#[test]
+fn test_equivalent_up_to_constant_and_multiple() {
+    let (mut a, mut b);
+
+    a = integral(&(Basis::x() * log_x()));
+    b = basis!(x ^ 2 * log(x) / 2 - x ^ 2 / 4 + 7);
+    println!("{} ~ {}", a, b);
+    assert_eq!(equivalent(&a, &b), Equivalence::Different);
+    assert_ne!(equivalent_up_to_constant(&a, &b), Equivalence::Different);
+
+    a = basis!(2 * sin(x));
+    b = basis!(sin(x) / 3);
+    assert_eq!(scalar_multiple(&a, &b), Equivalence::Proven);
+
+    a = basis!(2 * x + 2);
+    b = basis!(x + 1);
+    assert_ne!(scalar_multiple(&a, &b), Equivalence::Different);
+
+    a = basis!(x + 2);
+    assert_eq!(scalar_multiple(&a, &b), Equivalence::Different);
+}
@@ -0,0 +1,144 @@
+use nabla_game;
+use nabla_game::basis::structs::*;
+use nabla_game::game::cards::*;
+use nabla_game::math::derivative::{derivative, try_derivative};
+use nabla_game::math::error::*;
+use nabla_game::math::fraction::Fraction;
+use nabla_game::math::integral::try_integral;
+use nabla_game::math::inverse::try_inverse;
+use nabla_game::math::limits::{limit, try_limit};
+
+pub mod util;
+use util::*;
+
+// test valid input matches panicking API
+#[test]
+fn test_try_valid() {
+    let a = Basis::x() * sin_x();
+    assert_eq!(try_derivative(&a), Ok(derivative(&a)));
+    assert!(try_integral(&a).is_ok());
+    assert_eq!(try_inverse(&e_x()), Ok(log_x()));
+    assert_eq!(
+        try_limit(&LimitCard::LimPosInf, &e_x()).ok(),
+        limit(&LimitCard::LimPosInf)(&e_x())
+    );
+}
+
+// test malformed Bases are rejected before reaching the CAS
+#[test]
+fn test_try_malformed() {
+    let (mut a, b);
+
+    // division with missing denominator
+    a = Basis::BasisNode(BasisNode {
+        coefficient: Fraction::from(1),
+        operator: BasisOperator::Div,
+        operands: vec![Basis::x()].into(),
+    });
+    b = try_derivative(&a);
+    println!("{:?}", b);
+    assert!(matches!(b, Err(MathError::Malformed(_))));
+
+    // INF with invalid coefficient
+    a = Basis::BasisLeaf(BasisLeaf {
+        coefficient: Fraction::from(2),
+        element: BasisElement::Inf,
+    });
+    assert!(matches!(try_integral(&a), Err(MathError::Malformed(_))));
+    assert!(matches!(Basis::try_inf(0), Err(MathError::Malformed(_))));
+    assert_eq!(Basis::try_inf(-1), Ok(Basis::inf(-1)));
+    assert!(std::panic::catch_unwind(|| Basis::inf(0)).is_err());
+
+    // oversized coefficient
+    a = Basis::x() * (MAX_COEFFICIENT + 1);
+    assert!(matches!(try_inverse(&a), Err(MathError::Overflow(_))));
+
+    // deeply nested
+    a = Basis::x();
+    for _ in 0..MAX_DEPTH + 1 {
+        a = sin(&a);
+    }
+    assert!(matches!(
+        try_derivative(&a),
+        Err(MathError::RecursionLimit(_))
+    ));
+}
+
+// test limits that do not exist or are not supported
+#[test]
+fn test_try_limit() {
+    let (mut a, mut b);
+
+    a = Basis::x() ^ -1;
+    b = try_limit(&LimitCard::Lim0, &a);
+    println!("{:?}", b);
+    assert!(matches!(b, Err(MathError::DomainError(_))));
+
+    a = sin_x();
+    b = try_limit(&LimitCard::LimNegInf, &a);
+    assert!(matches!(b, Err(MathError::DomainError(_))));
+
+    // inverse of a function that is not monotone on the real line
+    a = Basis::BasisNode(BasisNode {
+        coefficient: Fraction::from(1),
+        operator: BasisOperator::Inv,
+        operands: vec![sin_x() + (Basis::x() ^ 2)].into(),
+    });
+    b = try_limit(&LimitCard::LimPosInf, &a);
+    assert!(matches!(b, Err(MathError::Unsupported(_))));
+    assert_eq!(limit(&LimitCard::LimPosInf)(&a), None);
+
+    // 0x has no sign to tend to INF with
+    a = Basis::BasisLeaf(BasisLeaf {
+        coefficient: Fraction::from(0),
+        element: BasisElement::X,
+    });
+    b = try_limit(&LimitCard::LimPosInf, &a);
+    println!("{:?}", b);
+    assert!(b.is_err());
+}
+
+// test cards return errors instead of panicking
+#[test]
+fn test_apply_card_errors() {
+    let (mut a, mut b);
+
+    // invalid limit leaves basis unchanged
+    a = sin_x();
+    b = apply_card(&Card::LimitCard(LimitCard::LimPosInf))(&a);
+    assert_eq!(b, Ok(a));
+
+    a = Basis::BasisLeaf(BasisLeaf {
+        coefficient: Fraction::from(3),
+        element: BasisElement::Inf,
+    });
+    b = apply_card(&Card::AlgebraicCard(AlgebraicCard::Sqrt))(&a);
+    assert!(b.is_err());
+
+    b = apply_multi_card(
+        &Card::AlgebraicCard(AlgebraicCard::Div),
+        vec![Basis::x(), Basis::from(0)],
+    );
+    println!("{:?}", b);
+    assert!(matches!(b, Err(MathError::DomainError(_))));
+
+    b = apply_multi_card(
+        &Card::AlgebraicCard(AlgebraicCard::Mult),
+        vec![Basis::x(), cos_x()],
+    );
+    assert_eq!(b, Ok(Basis::x() * cos_x()));
+
+    // coefficients within bounds can still overflow when multiplied together
+    a = Basis::x() * 30000;
+    b = apply_multi_card(
+        &Card::AlgebraicCard(AlgebraicCard::Mult),
+        vec![a.clone(), a.clone(), a.clone()],
+    );
+    println!("{:?}", b);
+    assert!(matches!(b, Err(MathError::Overflow(_))));
+    b = apply_multi_card(
+        &Card::AlgebraicCard(AlgebraicCard::Div),
+        vec![a.clone(), Basis::x() / 30000, a.clone()],
+    );
+    assert!(matches!(b, Err(MathError::Overflow(_))));
+}
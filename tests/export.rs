@@ -0,0 +1,81 @@
+use nabla_game;
+use nabla_game::basis::export::*;
+use nabla_game::basis::{builders::*, structs::*};
+
+pub mod util;
+use util::*;
+
+// test SymPy srepr and Python expressions
+#[test]
+fn test_sympy_export() {
+    let (mut a, mut b);
+
+    a = (Basis::x() ^ 2) * 3;
+    b = srepr(&a);
+    println!("{}", b);
+    assert_eq!(b, "Mul(Integer(3), Pow(Symbol('x'), Integer(2)))");
+    assert_eq!(python(&a), "3*x**2");
+
+    a = Basis::x() * (1, 2) + log_x();
+    b = python(&a);
+    println!("{}", b);
+    assert_eq!(b, "Rational(1, 2)*x + log(x)");
+
+    a = Basis::x() * -2 + Basis::from(1);
+    b = python(&a);
+    println!("{}", b);
+    assert_eq!(b, "-2*x + 1");
+    a = (Basis::x() + Basis::from(1)) ^ 2;
+    b = python(&a);
+    assert_eq!(b, "(x + 1)**2");
+    a = Basis::x() ^ -2;
+    assert_eq!(python(&a), "x**(-2)");
+    assert_eq!(srepr(&Basis::inf(-1)), "-oo");
+
+    // unevaluated inverse and integral
+    a = InvBasisNode(&(Basis::x() + e_x()));
+    b = srepr(&a);
+    println!("{}", b);
+    assert!(b.starts_with("Function('InverseFunction')(Lambda(Symbol('x'), Add("));
+    assert!(b.ends_with("), Symbol('x'))"));
+    a = IntBasisNode(&cos_x());
+    assert_eq!(srepr(&a), "Integral(cos(Symbol('x')), Symbol('x'))");
+    assert_eq!(python(&a), "Integral(cos(x), x)");
+}
+
+// test Mathematica FullForm
+#[test]
+fn test_mathematica_export() {
+    let (mut a, mut b);
+
+    a = (Basis::x() ^ 2) / log_x();
+    b = full_form(&a);
+    println!("{}", b);
+    assert_eq!(b, "Times[Power[x, 2], Power[Log[x], -1]]");
+
+    a = SqrtBasisNode(1, &Basis::x());
+    assert_eq!(full_form(&a), "Power[x, Rational[1, 2]]");
+    assert_eq!(full_form(&e_x()), "Power[E, x]");
+    assert_eq!(full_form(&Basis::inf(1)), "DirectedInfinity[1]");
+
+    a = InvBasisNode(&(Basis::x() + e_x()));
+    b = full_form(&a);
+    println!("{}", b);
+    assert!(b.starts_with("InverseFunction[Function[x, Plus["));
+    assert!(b.ends_with("]][x]"));
+    assert_eq!(full_form(&IntBasisNode(&sin_x())), "Integrate[Sin[x], x]");
+}
+
+// test bulk export, one Basis per line
+#[test]
+fn test_export_all() {
+    let bases = vec![Basis::x(), sin_x(), Basis::x() ^ 2];
+    let a = export_all(&bases, ExportFormat::Python);
+    println!("{}", a);
+    assert_eq!(a, "x\nsin(x)\nx**2");
+    assert_eq!(
+        export_all(&bases, ExportFormat::Mathematica),
+        "x\nSin[x]\nPower[x, 2]"
+    );
+    assert_eq!(export(&bases[2], ExportFormat::Infix), "x^2");
+}
@@ -0,0 +1,78 @@
+use nabla_game;
+use nabla_game::basis::intern::node_table_size;
+use nabla_game::basis::structs::*;
+use nabla_game::math::integral::integral;
+
+pub mod util;
+use util::*;
+
+/// returns operands of given Basis, panics if Basis is a leaf
+fn operands(basis: &Basis) -> &[Basis] {
+    match basis {
+        Basis::BasisNode(BasisNode { operands, .. }) => operands,
+        _ => panic!("{} is not a BasisNode", basis),
+    }
+}
+
+// test structurally identical nodes share interned operands
+#[test]
+fn test_shared_subtrees() {
+    let (a, b);
+
+    // separately built nodes share the same operands, in any build order
+    a = (Basis::x() ^ 2) * sin_x();
+    b = sin_x() * (Basis::x() ^ 2);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+    if let (Basis::BasisNode(a_node), Basis::BasisNode(b_node)) = (&a, &b) {
+        assert!(a_node.operands.ptr_eq(&b_node.operands));
+        assert_eq!(a_node.operands.id(), b_node.operands.id());
+    }
+
+    // clones share the same operands
+    let c = a.clone();
+    if let (Basis::BasisNode(a_node), Basis::BasisNode(c_node)) = (&a, &c) {
+        assert!(a_node.operands.ptr_eq(&c_node.operands));
+    }
+
+    // different nodes do not share operands
+    let d = (Basis::x() ^ 2) * cos_x();
+    if let (Basis::BasisNode(a_node), Basis::BasisNode(d_node)) = (&a, &d) {
+        assert!(!a_node.operands.ptr_eq(&d_node.operands));
+    }
+    assert_ne!(a, d);
+
+    // sums share operands regardless of the order of their terms
+    let (e, f) = (
+        Basis::x() + sin_x() + Basis::from(1),
+        Basis::from(1) + sin_x() + Basis::x(),
+    );
+    if let (Basis::BasisNode(e_node), Basis::BasisNode(f_node)) = (&e, &f) {
+        assert!(e_node.operands.ptr_eq(&f_node.operands));
+    }
+}
+
+// test integration by parts reuses subtrees instead of copying them
+#[test]
+fn test_integral_shares_subtrees() {
+    let a = (Basis::x() ^ 3) * e_x();
+    let b = integral(&a);
+    println!("I({}) = {}", a, b);
+
+    // every e^x in the result points to the same interned operands
+    let e_operands = operands(&e_x()).as_ptr();
+    let e_factors = operands(&b)
+        .iter()
+        .flat_map(|term| operands(term).iter())
+        .filter(|factor| factor.is_node(BasisOperator::E))
+        .collect::<Vec<&Basis>>();
+    assert!(!e_factors.is_empty());
+    assert!(e_factors
+        .iter()
+        .all(|factor| operands(factor).as_ptr() == e_operands));
+
+    // node table does not grow when rebuilding the same expression
+    let size = node_table_size();
+    integral(&a);
+    assert_eq!(node_table_size(), size);
+}
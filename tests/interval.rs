@@ -0,0 +1,51 @@
+use nabla_game;
+use nabla_game::math::interval::*;
+use std::f64::consts::PI;
+
+// test operations on Intervals and IntervalSets
+#[test]
+fn test_intervals() {
+    let (mut a, mut b);
+
+    // [0, 2] ∩ (1, 3) = (1, 2]
+    a = Interval::closed(0.0, 2.0).intersect(&Interval::open(1.0, 3.0));
+    b = Interval::new(1.0, 2.0, false, true);
+    println!("{}", a);
+    assert_eq!(a, b);
+    assert!(a.contains(2.0) && !a.contains(1.0));
+    assert!(Interval::open(1.0, 1.0).is_empty());
+
+    // infinite endpoints are open
+    assert_eq!(Interval::closed(0.0, f64::INFINITY).to_string(), "[0, ∞)");
+    assert_eq!(Interval::closed(-PI / 2.0, PI).to_string(), "[-π/2, π]");
+    assert_eq!(Interval::open(0.5, 4.0 / 3.0).to_string(), "(1/2, 4/3)");
+
+    // decreasing maps flip endpoints
+    a = Interval::new(0.0, 1.0, true, false).map(|x, _| -x);
+    b = Interval::new(-1.0, 0.0, false, true);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_interval_sets() {
+    let positive = IntervalSet::from(Interval::open(0.0, f64::INFINITY));
+    let negative = IntervalSet::from(Interval::open(f64::NEG_INFINITY, 0.0));
+
+    // (-∞, 0) ∪ (0, ∞) stays split, adding [0, 0] merges into ℝ
+    let a = negative.union(&positive);
+    println!("{}", a);
+    assert_eq!(a.to_string(), "(-∞, 0) ∪ (0, ∞)");
+    assert!(!a.contains(0.0));
+    let b = a.union(&IntervalSet::from(Interval::closed(0.0, 0.0)));
+    assert_eq!(b, IntervalSet::real());
+
+    assert!(negative.intersect(&positive).is_empty());
+    assert_eq!(negative.intersect(&positive).to_string(), "∅");
+    assert_eq!(
+        a.intersect(&IntervalSet::from(Interval::closed(-1.0, 1.0))),
+        IntervalSet::from(vec![
+            Interval::new(-1.0, 0.0, true, false),
+            Interval::new(0.0, 1.0, false, true)
+        ])
+    );
+}
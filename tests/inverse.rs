@@ -1,7 +1,10 @@
 use nabla_game;
 use nabla_game::basis::{builders::*, structs::*};
 use nabla_game::math::derivative::derivative;
-use nabla_game::math::inverse::inverse;
+use nabla_game::math::equivalence::*;
+use nabla_game::math::error::MathError;
+use nabla_game::math::interval::IntervalSet;
+use nabla_game::math::inverse::*;
 
 pub mod util;
 use util::*;
@@ -144,3 +147,127 @@ fn test_inverse_complex_coefficient() {
     println!("f-1({}) = {}", a, b);
     assert_eq!(inverse(&a), b);
 }
+
+#[test]
+fn test_inverse_branches() {
+    let mut a;
+
+    // x^2 is inverted on x >= 0
+    a = Basis::x() ^ 2;
+    let branch = inverse_branch(&a).unwrap();
+    println!(
+        "f-1({}) = {} on {}, branch {}",
+        a, branch.inverse, branch.domain, branch.branch
+    );
+    assert_eq!(branch.inverse, Basis::x() ^ (1, 2));
+    assert_eq!(branch.domain.to_string(), "[0, ∞)");
+    assert_eq!(branch.branch.to_string(), "[0, ∞)");
+
+    // cos(x) is inverted on [0, π], acos(x) is only defined on [-1, 1]
+    a = cos_x();
+    let branch = inverse_branch(&a).unwrap();
+    println!(
+        "f-1({}) = {} on {}, branch {}",
+        a, branch.inverse, branch.domain, branch.branch
+    );
+    assert_eq!(branch.domain.to_string(), "[-1, 1]");
+    assert_eq!(branch.branch.to_string(), "[0, π]");
+
+    a = ACosBasisNode(&Basis::x());
+    let branch = inverse_branch(&a).unwrap();
+    assert_eq!(branch.inverse, cos_x());
+    assert_eq!(branch.domain.to_string(), "[0, π]");
+    assert_eq!(branch.branch.to_string(), "[-1, 1]");
+
+    // log(x - 1) maps (1, ∞) onto ℝ
+    a = log(&(Basis::x() - Basis::from(1)));
+    let branch = inverse_branch(&a).unwrap();
+    println!(
+        "f-1({}) = {} on {}, branch {}",
+        a, branch.inverse, branch.domain, branch.branch
+    );
+    assert_eq!(branch.domain, IntervalSet::real());
+    assert_eq!(branch.branch.to_string(), "(1, ∞)");
+
+    // 1/x is its own inverse away from 0
+    a = Basis::x() ^ -1;
+    let branch = inverse_branch(&a).unwrap();
+    assert_eq!(branch.inverse, a);
+    assert_eq!(branch.domain.to_string(), "(-∞, 0) ∪ (0, ∞)");
+
+    // log(acos(x) - 4) has no valid branch since acos(x) <= π
+    a = log(&(ACosBasisNode(&Basis::x()) - Basis::from(4)));
+    assert!(matches!(inverse_branch(&a), Err(MathError::DomainError(_))));
+
+    // x + sin(x) is not invertible in closed form
+    a = Basis::x() + sin_x();
+    assert_eq!(inverse(&a), InvBasisNode(&a));
+    assert!(matches!(inverse_branch(&a), Err(MathError::Unsupported(_))));
+}
+
+#[test]
+fn test_solvable_inverses() {
+    let (mut a, mut b);
+
+    // e^x + 2e^x + 1 is linear in e^x
+    a = AddBasisNode(vec![e_x(), e_x() * 2, Basis::from(1)]);
+    b = log(&((Basis::x() - Basis::from(1)) / 3));
+    println!("f-1({}) = {}", a, inverse(&a));
+    assert_ne!(equivalent(&inverse(&a), &b), Equivalence::Different);
+
+    // Möbius transform (x + 1) / (x - 1) is its own inverse
+    a = (Basis::x() + Basis::from(1)) / (Basis::x() - Basis::from(1));
+    let branch = inverse_branch(&a).unwrap();
+    println!("f-1({}) = {} on {}", a, branch.inverse, branch.domain);
+    assert_ne!(equivalent(&branch.inverse, &a), Equivalence::Different);
+    assert_eq!(branch.domain.to_string(), "(-∞, 1) ∪ (1, ∞)");
+    assert_ne!(verify_inverse(&a, &branch), Equivalence::Different);
+
+    // xy + x = x(y + 1) for another variable y
+    a = Basis::x() * Basis::var('y') + Basis::x();
+    println!("f-1({}) = {}", a, inverse(&a));
+    assert!(!inverse(&a).is_node(BasisOperator::Inv));
+    b = Basis::x() * (Basis::var('y') + Basis::from(1));
+    assert_eq!(inverse(&inverse(&a)), b);
+
+    // f-1(x + e^x) is not solvable, but inverts back to x + e^x
+    a = InvBasisNode(&(Basis::x() + e_x()));
+    b = Basis::x() + e_x();
+    println!("f-1({}) = {}", a, inverse(&a));
+    assert_eq!(inverse(&a), b);
+    assert_eq!(try_inverse(&a), Ok(b));
+}
+
+#[test]
+fn test_verify_inverse() {
+    for a in [
+        Basis::x() ^ 2,
+        cos_x(),
+        e_x() * 3 + Basis::from(2),
+        ASinBasisNode(&e_x()),
+        Basis::x() ^ (1, 3),
+    ] {
+        let branch = inverse_branch(&a).unwrap();
+        println!("f-1({}) = {} on {}", a, branch.inverse, branch.domain);
+        assert_ne!(verify_inverse(&a, &branch), Equivalence::Different);
+    }
+
+    // wrong inverse is caught
+    let a = Basis::x() ^ 2;
+    let mut branch = inverse_branch(&a).unwrap();
+    branch.inverse = Basis::x() / 2;
+    assert_eq!(verify_inverse(&a, &branch), Equivalence::Different);
+}
+
+#[test]
+fn test_inverse_zero_terms() {
+    let (a, b);
+
+    // ∜27·x^(1/4) / 3 has no constant term, its inverse is 3x^4 without a 0/3 leaf
+    a = (Basis::from(27) ^ (1, 4)) * (Basis::x() ^ (1, 4)) / 3;
+    b = inverse(&a);
+    println!("f-1({}) = {}", a, b);
+    assert!(!b.to_string().contains("0/3"));
+    assert_eq!(b, 3 * (Basis::x() ^ 4));
+    assert_ne!(equivalent(&inverse(&b), &a), Equivalence::Different);
+}
@@ -0,0 +1,73 @@
+use nabla_game;
+use nabla_game::basis;
+use nabla_game::basis::structs::*;
+use nabla_game::math::equivalence::*;
+use nabla_game::math::linear_algebra::*;
+
+pub mod util;
+use util::*;
+
+// test symbolic Wronskian of small sets
+#[test]
+fn test_wronskian() {
+    let (mut a, mut b);
+
+    // W(sin, cos) = sin * -sin - cos * cos = -1, not simplified symbolically
+    a = wronskian(&[sin_x(), cos_x()]);
+    b = Basis::from(-1);
+    println!("{} = {}", a, b);
+    assert_eq!(equivalent(&a, &b), Equivalence::Likely);
+
+    // W(x, 2x) = x * 2 - 2x * 1 = 0
+    a = wronskian(&[basis!(x), basis!(2 * x)]);
+    b = Basis::from(0);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+
+    let matrix = wronskian_matrix(&[basis!(x ^ 2), basis!(x)]);
+    assert_eq!(matrix[1], vec![basis!(2 * x), Basis::from(1)]);
+}
+
+// test linear dependence of sets of bases
+#[test]
+fn test_linear_dependence() {
+    // scalar multiples
+    assert_eq!(
+        linear_dependence(&[basis!(x ^ 2), basis!(3 * x ^ 2)]),
+        Dependence::Proven
+    );
+
+    // sin + cos is a combination of sin and cos
+    assert_ne!(
+        linear_dependence(&[sin_x(), cos_x(), basis!(sin(x) + cos(x))]),
+        Dependence::Independent
+    );
+    assert_ne!(
+        linear_dependence(&[basis!(x ^ 2), basis!(x), basis!(x ^ 2 - 4 * x)]),
+        Dependence::Independent
+    );
+
+    // independent functions
+    assert_eq!(
+        linear_dependence(&[basis!(x), basis!(x ^ 2), basis!(e ^ x)]),
+        Dependence::Independent
+    );
+    assert_eq!(
+        linear_dependence(&[sin_x(), cos_x()]),
+        Dependence::Independent
+    );
+    assert_eq!(
+        linear_dependence(&[log_x(), basis!(x)]),
+        Dependence::Independent
+    );
+}
+
+// test greedy selection of independent bases
+#[test]
+fn test_independent_subset() {
+    let bases = [sin_x(), cos_x(), basis!(sin(x) + cos(x)), basis!(x)];
+    assert_eq!(independent_subset(&bases), vec![0, 1, 3]);
+
+    let bases = [basis!(x), basis!(2 * x), basis!(-x)];
+    assert_eq!(independent_subset(&bases), vec![0]);
+}
@@ -0,0 +1,84 @@
+use nabla_game;
+use nabla_game::basis;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::math::fraction::Fraction;
+
+pub mod util;
+use util::*;
+
+// test leaves, functions and precedence
+#[test]
+fn test_basis_macro() {
+    let (mut a, mut b);
+
+    a = basis!(x);
+    assert_eq!(a, Basis::x());
+    assert_eq!(basis!(3), Basis::from(3));
+    assert_eq!(basis!(inf), Basis::inf(1));
+    assert_eq!(basis!(-inf), Basis::inf(-1));
+
+    a = basis!(2 * x ^ 2 * log(x) - x ^ 2 / 4);
+    b = 2 * (Basis::x() ^ 2) * log_x() - (Basis::x() ^ 2) / 4;
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+
+    a = basis!((2 * x ^ 2 * log(x) - x ^ 2) / 4);
+    b = (2 * (Basis::x() ^ 2) * log_x() - (Basis::x() ^ 2)) / 4;
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+
+    a = basis!(sin(x) - x * cos(x));
+    assert_eq!(a, sin_x() - Basis::x() * cos_x());
+
+    a = basis!(e ^ x + e ^ (2 * x) + ln(x));
+    assert_eq!(a, e_x() + e(&(Basis::x() * 2)) + log_x());
+
+    a = basis!(acos(x) * asin(x ^ 2) + inv(cos(x)) - int(e ^ x * sin(x)));
+    b = ACosBasisNode(&Basis::x()) * ASinBasisNode(&(Basis::x() ^ 2)) + InvBasisNode(&cos_x())
+        - IntBasisNode(&(e_x() * sin_x()));
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+}
+
+// test exponents and fractions
+#[test]
+fn test_basis_macro_exponents() {
+    let mut a;
+
+    a = basis!(x ^ -1);
+    assert_eq!(a, Basis::x() ^ -1);
+    assert_eq!(basis!(x ^ (-1)), a);
+
+    a = basis!(sqrt(x) * x ^ (1 / 2));
+    assert_eq!(a, Basis::x());
+
+    a = basis!(x ^ (-3 / 2));
+    assert_eq!(a, Basis::x() ^ (-3, 2));
+
+    a = basis!((1 / 2) * x);
+    assert_eq!(a, Basis::x() / 2);
+
+    a = basis!(sin(x) ^ 2);
+    assert_eq!(a, sin_x() ^ 2);
+
+    let n = 3;
+    a = basis!(x ^ { n } + x ^ { Fraction::from((1, 3)) });
+    assert_eq!(a, (Basis::x() ^ 3) + (Basis::x() ^ (1, 3)));
+}
+
+// test interpolation of rust values
+#[test]
+fn test_basis_macro_interpolation() {
+    let (mut a, b, c);
+
+    b = sin_x();
+    c = &b;
+    a = basis!(x * b + c);
+    assert_eq!(a, Basis::x() * sin_x() + sin_x());
+
+    a = basis!(e ^ (b) / { Basis::x() ^ 2 });
+    assert_eq!(a, e(&sin_x()) / (Basis::x() ^ 2));
+
+    a = basis!({ (1, 2) } * log(b));
+    assert_eq!(a, log(&sin_x()) / 2);
+}
@@ -0,0 +1,107 @@
+use nabla_game;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::game::cards::*;
+use nabla_game::math::fraction::Fraction;
+use nabla_game::util::{math_element, MathMLFlags, ToMathML};
+
+pub mod util;
+use util::*;
+
+// test MathML of leaves, fractions and simple nodes
+#[test]
+fn test_basis_mathml() {
+    let (mut a, mut b);
+
+    a = Basis::x() * 2;
+    b = "<mrow><mn>2</mn><mi>x</mi></mrow>";
+    println!("{}", a.to_mathml());
+    assert_eq!(a.to_mathml(), b);
+
+    a = Basis::from((-1, 2));
+    b = "<mrow><mo>-</mo><mfrac><mn>1</mn><mn>2</mn></mfrac></mrow>";
+    assert_eq!(a.to_mathml(), b);
+    assert_eq!(Fraction::from(1).to_mathml(), "");
+    assert_eq!(
+        Basis::inf(-1).to_mathml(),
+        "<mrow><mo>-</mo><mi>∞</mi></mrow>"
+    );
+
+    // e^x + sin(x)
+    a = e_x() + sin_x();
+    println!("{}", a.to_mathml());
+    assert!(a
+        .to_mathml()
+        .contains("<mrow><msup><mi>e</mi><mrow><mi>x</mi></mrow></msup></mrow>"));
+    assert!(a.to_mathml().contains(
+        "<mrow><mi>sin</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mi>x</mi><mo>)</mo></mrow></mrow>"
+    ));
+
+    // x^-1 as a fraction, x^2 as a superscript
+    a = Basis::x() ^ -1;
+    b = "<mrow><mfrac><mrow><mn>1</mn></mrow><mrow><mi>x</mi></mrow></mfrac></mrow>";
+    assert_eq!(a.to_mathml(), b);
+    a = Basis::x() ^ 2;
+    b = "<mrow><msup><mi>x</mi><mrow><mn>2</mn></mrow></msup></mrow>";
+    assert_eq!(a.to_mathml(), b);
+
+    // symbolic constants, ie. √2
+    a = SqrtBasisNode(1, &Basis::from(2));
+    assert_eq!(a.to_mathml(), "<mrow><msqrt><mn>2</mn></msqrt></mrow>");
+
+    let element = math_element(&Basis::x(), true);
+    assert!(element.starts_with("<math xmlns=\"http://www.w3.org/1998/Math/MathML\""));
+    assert!(element.ends_with("</math>"));
+}
+
+// test MathML honours display flags
+#[test]
+fn test_mathml_flags() {
+    let (mut a, mut b);
+    let flags = MathMLFlags {
+        ln_for_log: false,
+        fractional_exponents: false,
+    };
+    let ln_flags = MathMLFlags {
+        ln_for_log: true,
+        ..flags
+    };
+    let fractional_flags = MathMLFlags {
+        fractional_exponents: true,
+        ..flags
+    };
+
+    a = log_x();
+    assert!(a.to_mathml_with(ln_flags).contains("<mi>ln</mi>"));
+    assert_eq!(
+        Card::AlgebraicCard(AlgebraicCard::Log).to_mathml_with(ln_flags),
+        "<mi>ln</mi>"
+    );
+    assert!(a.to_mathml_with(flags).contains("<mi>log</mi>"));
+
+    a = SqrtBasisNode(1, &Basis::x());
+    b = "<mrow><msqrt><mi>x</mi></msqrt></mrow>";
+    assert_eq!(a.to_mathml_with(flags), b);
+    b = "<mrow><msup><mi>x</mi><mrow><mfrac><mn>1</mn><mn>2</mn></mfrac></mrow></msup></mrow>";
+    assert_eq!(a.to_mathml_with(fractional_flags), b);
+}
+
+// test MathML of every card
+#[test]
+fn test_card_mathml() {
+    assert_eq!(
+        Card::BasisCard(BasisCard::X2).to_mathml(),
+        "<msup><mi>x</mi><mn>2</mn></msup>"
+    );
+    assert_eq!(
+        Card::LimitCard(LimitCard::Lim0).to_mathml(),
+        "<munder><mo>lim</mo><mrow><mi>x</mi><mo>→</mo><mn>0</mn></mrow></munder>"
+    );
+    assert_eq!(
+        Card::DerivativeCard(DerivativeCard::Nabla).to_mathml(),
+        "<mo>∇</mo>"
+    );
+    assert_eq!(
+        Card::AlgebraicCard(AlgebraicCard::Inverse).to_mathml(),
+        "<msup><mi>f</mi><mrow><mo>-</mo><mn>1</mn></mrow></msup>"
+    );
+}
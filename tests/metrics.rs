@@ -0,0 +1,95 @@
+use nabla_game;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::math::fraction::Fraction;
+use nabla_game::math::metrics::*;
+
+pub mod util;
+use util::*;
+
+// test structural metrics
+#[test]
+fn test_size_metrics() {
+    let (mut a, b);
+
+    a = Basis::x();
+    assert_eq!(node_count(&a), 1);
+    assert_eq!(depth(&a), 1);
+    assert!(operator_histogram(&a).is_empty());
+
+    // x^2sin(x)
+    a = (Basis::x() ^ 2) * sin_x();
+    println!("{}", a);
+    assert_eq!(node_count(&a), 5);
+    assert_eq!(depth(&a), 3);
+    b = operator_histogram(&a);
+    println!("{:?}", b);
+    assert_eq!(b[&BasisOperator::Mult], 1);
+    assert_eq!(b[&BasisOperator::Sin], 1);
+    assert_eq!(b[&BasisOperator::Pow(Fraction::from(2))], 1);
+}
+
+// test polynomial degree and growth class
+#[test]
+fn test_growth_metrics() {
+    let mut a;
+
+    a = (Basis::x() ^ 3) + Basis::x() * 2 + Basis::from(1);
+    assert_eq!(polynomial_degree(&a), Some(Fraction::from(3)));
+    assert_eq!(growth_class(&a), GrowthClass::Polynomial);
+
+    a = SqrtBasisNode(1, &Basis::x()) / (Basis::x() ^ 2);
+    assert_eq!(polynomial_degree(&a), Some(Fraction::from((-3, 2))));
+    assert_eq!(growth_class(&a), GrowthClass::Constant);
+
+    a = Basis::x() * e_x();
+    assert_eq!(polynomial_degree(&a), None);
+    assert_eq!(growth_class(&a), GrowthClass::Exponential);
+
+    assert_eq!(growth_class(&log_x()), GrowthClass::Logarithmic);
+    assert_eq!(growth_class(&Basis::from(5)), GrowthClass::Constant);
+    assert_eq!(
+        growth_class(&(Basis::x() * cos_x())),
+        GrowthClass::Oscillating
+    );
+    assert_eq!(growth_class(&(e_x() + sin_x())), GrowthClass::Exponential);
+    assert!(GrowthClass::Logarithmic < GrowthClass::Polynomial);
+}
+
+// test builders never increase complexity over the unsimplified node
+#[test]
+fn test_simplify_complexity() {
+    let (mut a, mut b);
+
+    // x + x → 2x
+    a = Basis::BasisNode(BasisNode {
+        coefficient: Fraction::from(1),
+        operator: BasisOperator::Add,
+        operands: vec![Basis::x(), Basis::x()].into(),
+    });
+    b = AddBasisNode(vec![Basis::x(), Basis::x()]);
+    println!("{} → {}", a, b);
+    assert!(complexity(&b) <= complexity(&a));
+
+    // e^log(x) → x
+    a = Basis::BasisNode(BasisNode {
+        coefficient: Fraction::from(1),
+        operator: BasisOperator::E,
+        operands: vec![log_x()].into(),
+    });
+    b = EBasisNode(&log_x());
+    println!("{} → {}", a, b);
+    assert!(complexity(&b) < complexity(&a));
+
+    // x^2 * x^3 → x^5
+    a = Basis::BasisNode(BasisNode {
+        coefficient: Fraction::from(1),
+        operator: BasisOperator::Mult,
+        operands: vec![Basis::x() ^ 2, Basis::x() ^ 3].into(),
+    });
+    b = (Basis::x() ^ 2) * (Basis::x() ^ 3);
+    println!("{} → {}", a, b);
+    assert!(complexity(&b) < complexity(&a));
+
+    assert!(worth_showing(&a));
+    assert!(complexity(&IntBasisNode(&a)) > complexity(&a));
+}
@@ -0,0 +1,86 @@
+use nabla_game;
+use nabla_game::basis::pretty::*;
+use nabla_game::basis::{builders::*, structs::*};
+
+pub mod util;
+use util::*;
+
+// test -x is not shown as -1x
+#[test]
+fn test_negative_x_display() {
+    let a = Basis::x() * -1;
+    println!("{}", a);
+    assert_eq!(a.to_string(), "-x");
+    assert_eq!(infix(&a), "-x");
+    assert_eq!((Basis::x() * -2).to_string(), "-2x");
+}
+
+// test infix only adds parentheses precedence needs
+#[test]
+fn test_infix() {
+    let (mut a, mut b);
+
+    a = (Basis::x() ^ 2) / log_x();
+    b = infix(&a);
+    println!("{}", b);
+    assert_eq!(b, "x^2/log(x)");
+
+    a = (Basis::x() + Basis::from(1)) ^ 2;
+    b = infix(&a);
+    println!("{}", b);
+    assert_eq!(b, "(x + 1)^2");
+
+    a = Basis::x() - Basis::from(1);
+    b = infix(&a);
+    println!("{}", b);
+    assert_eq!(b, "x - 1");
+
+    a = e_x() * 3;
+    assert_eq!(infix(&a), "3e^x");
+    a = EBasisNode(&(Basis::x() * 2));
+    assert_eq!(infix(&a), "e^(2x)");
+    a = SqrtBasisNode(1, &Basis::x());
+    assert_eq!(infix(&a), "√x");
+    a = Basis::x() ^ -1;
+    assert_eq!(infix(&a), "1/x");
+    assert_eq!(infix(&(Basis::x() * (1, 2))), "x/2");
+    // integer coefficients fold into the numerator of a reciprocal
+    a = Basis::from(2) / (Basis::x() + Basis::from(1));
+    b = infix(&a);
+    println!("{}", b);
+    assert_eq!(b, "2/(x + 1)");
+    assert_eq!(infix(&(3 * (Basis::x() ^ -1))), "3/x");
+    assert_eq!(infix(&Basis::inf(-1)), "-∞");
+    assert_eq!(infix(&IntBasisNode(&cos_x())), "∫(cos(x))dx");
+}
+
+// test multi-line layout of fractions, powers, radicals and integrals
+#[test]
+fn test_pretty() {
+    let (mut a, mut b);
+
+    a = (Basis::x() ^ 2) / log_x();
+    b = pretty(&a);
+    println!("{}", b);
+    assert_eq!(b, "  x²\n──────\nlog(x)");
+
+    a = Basis::from(2) / (Basis::x() + Basis::from(1));
+    b = pretty(&a);
+    println!("{}", b);
+    assert_eq!(b, "  2\n─────\nx + 1");
+
+    a = SqrtBasisNode(1, &Basis::x());
+    b = pretty(&a);
+    println!("{}", b);
+    assert_eq!(b, " ─\n√x");
+
+    a = IntBasisNode(&(Basis::x() ^ -1));
+    b = pretty(&a);
+    println!("{}", b);
+    assert_eq!(b, "⌠1\n⎮─ dx\n⌡x");
+
+    // single line expressions are laid out on one line, {:#} defers to pretty
+    a = sin_x() * 2;
+    assert_eq!(pretty(&a), "2sin(x)");
+    assert_eq!(format!("{:#}", a), pretty(&a));
+}
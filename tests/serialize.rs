@@ -0,0 +1,156 @@
+#![cfg(feature = "serde")]
+
+use std::collections::HashSet;
+
+use nabla_game;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::game::cards::*;
+use nabla_game::game::structs::*;
+use nabla_game::math::fraction::Fraction;
+use nabla_game::serialize::*;
+
+pub mod util;
+use util::*;
+
+// test compact JSON of Basis and round trips through it
+#[test]
+fn test_basis_json() {
+    let (a, mut b);
+
+    a = (Basis::x() ^ 3) * 2;
+    b = serde_json::to_string(&a).unwrap();
+    println!("{}", b);
+    assert_eq!(b, "[\"pow\",[2,1],[3,1],[\"x\",[1,1]]]");
+
+    for a in vec![
+        Basis::from((-1, 2)),
+        Basis::inf(-1),
+        Basis::var('y') * 3,
+        sin_x() + log_x() * (1, 2),
+        InvBasisNode(&(Basis::x() + e_x())),
+        IntBasisNode(&cos_x()),
+    ] {
+        b = to_json(&a).unwrap();
+        println!("{}", b);
+        assert_eq!(from_json::<Basis>(&b).unwrap(), a);
+    }
+
+    assert_eq!(
+        serde_json::to_string(&Fraction::from((3, 4))).unwrap(),
+        "[3,4]"
+    );
+    assert_eq!(
+        serde_json::to_string(&Card::LimitCard(LimitCard::Lim0)).unwrap(),
+        "{\"LimitCard\":\"Lim0\"}"
+    );
+}
+
+// test rejected versions and malformed expressions
+#[test]
+fn test_invalid_json() {
+    let a = format!(
+        "{{\"version\":{},\"data\":[\"x\",[1,1]]}}",
+        FORMAT_VERSION + 1
+    );
+    assert_eq!(
+        from_json::<Basis>(&a),
+        Err(SerializeError::Version(FORMAT_VERSION + 1))
+    );
+
+    // cos takes exactly one operand
+    let a = "{\"version\":1,\"data\":[\"cos\",[1,1]]}";
+    assert!(matches!(
+        from_json::<Basis>(a),
+        Err(SerializeError::Json(_))
+    ));
+    let a = "{\"version\":1,\"data\":[\"tan\",[1,1],[\"x\",[1,1]]]}";
+    assert!(matches!(
+        from_json::<Basis>(a),
+        Err(SerializeError::Json(_))
+    ));
+    let a = "{\"version\":1,\"data\":[\"num\",[1,0]]}";
+    assert!(matches!(
+        from_json::<Basis>(a),
+        Err(SerializeError::Json(_))
+    ));
+
+    // leaves are validated too, INF is ±1 and coefficients are bounded
+    for a in [
+        "{\"version\":1,\"data\":[\"inf\",[7,1]]}",
+        "{\"version\":1,\"data\":[\"num\",[2147483647,1]]}",
+    ] {
+        println!("{} -> {:?}", a, from_json::<Basis>(a));
+        assert!(matches!(
+            from_json::<Basis>(a),
+            Err(SerializeError::Json(_))
+        ));
+    }
+}
+
+// test operands written in any order load in canonical order, so they hash like built nodes
+#[test]
+fn test_canonical_json() {
+    let a = "{\"version\":1,\"data\":[\"add\",[1,1],[\"num\",[1,1]],[\"x\",[1,1]]]}";
+    let b: Basis = from_json(a).unwrap();
+    println!("{} from {}", b, a);
+    assert_eq!(
+        to_json(&b).unwrap(),
+        to_json(&(Basis::x() + Basis::from(1))).unwrap()
+    );
+    let set = HashSet::from([Basis::x() + Basis::from(1)]);
+    assert!(set.contains(&b));
+}
+
+// test a whole game round trips, including field history and inverses
+#[test]
+fn test_game_json() {
+    let mut game = Game::new();
+    // saved from the saves menu during a game against the computer
+    game.state = GameState::SAVES;
+    game.mode = GameState::PLAYAI;
+    game.turn.number = 3;
+    game.turn.phase = TurnPhase::SELECT(Card::AlgebraicCard(AlgebraicCard::Inverse));
+    game.field.derivative(1, Some(Basis::from(1)));
+    game.field.inverse(2, Some(SqrtBasisNode(1, &Basis::x())));
+
+    let json = to_json(&game).unwrap();
+    println!("{}", json);
+    let mut loaded: Game = from_json(&json).unwrap();
+
+    assert_eq!(loaded.mode, GameState::PLAYAI);
+    assert_eq!(loaded.turn.number, 3);
+    assert!(matches!(
+        loaded.turn.phase,
+        TurnPhase::SELECT(Card::AlgebraicCard(AlgebraicCard::Inverse))
+    ));
+    assert_eq!(loaded.player_1, game.player_1);
+    assert_eq!(loaded.player_2, game.player_2);
+    assert_eq!(loaded.deck, game.deck);
+    assert_eq!(loaded.field.inverses, game.field.inverses);
+    for i in 0..6 {
+        assert_eq!(loaded.field[i].basis, game.field[i].basis);
+        assert_eq!(loaded.field[i].index, game.field[i].index);
+        assert_eq!(loaded.field[i].history, game.field[i].history);
+    }
+
+    // the selected card is found again in the hand of the current player
+    loaded.player_2 = vec![Card::BasisCard(BasisCard::X); 7];
+    loaded.player_2[4] = Card::AlgebraicCard(AlgebraicCard::Inverse);
+    loaded.restore_selection();
+    assert!(matches!(loaded.turn.phase, TurnPhase::SELECT(_)));
+    assert_eq!(loaded.active.selected.len(), 1);
+    assert_eq!(loaded.active.selected[0].key_val(), (String::from("p2"), 4));
+
+    // a card that is no longer in the hand can't be selected, so the turn starts over
+    loaded.player_2[4] = Card::BasisCard(BasisCard::X);
+    loaded.restore_selection();
+    assert!(matches!(loaded.turn.phase, TurnPhase::IDLE));
+    assert!(loaded.active.selected.is_empty());
+
+    // Mult/Div operands aren't saved, so a half-picked combination starts over
+    loaded.player_2[4] = Card::AlgebraicCard(AlgebraicCard::Mult);
+    loaded.turn.phase = TurnPhase::MULTISELECT(Card::AlgebraicCard(AlgebraicCard::Mult));
+    loaded.restore_selection();
+    assert!(matches!(loaded.turn.phase, TurnPhase::IDLE));
+    assert!(loaded.active.selected.is_empty());
+}
@@ -0,0 +1,142 @@
+use nabla_game;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::math::coefficient::Coefficient;
+use nabla_game::math::solve::*;
+
+pub mod util;
+use util::*;
+
+fn exact(roots: Vec<Basis>) -> SolutionSet {
+    SolutionSet {
+        roots: roots.into_iter().map(Root::Exact).collect(),
+        everywhere: false,
+        complete: true,
+    }
+}
+
+// test exact roots of polynomials
+#[test]
+fn test_polynomial_roots() {
+    let (mut a, mut b);
+
+    // x^2 - 4 = 0
+    a = solve(&((Basis::x() ^ 2) - Basis::from(4)));
+    b = exact(vec![Basis::from(-2), Basis::from(2)]);
+    println!("{}", a);
+    assert_eq!(a, b);
+
+    // x^2 - 2 = 0, x = ±√2
+    let sqrt_2 = SqrtBasisNode(1, &Basis::from(2));
+    a = solve(&((Basis::x() ^ 2) - Basis::from(2)));
+    b = exact(vec![-sqrt_2.clone(), sqrt_2]);
+    println!("{}", a);
+    assert_eq!(a, b);
+
+    // x^2 + 2x + 1 = 0 has a double root
+    a = solve(&((Basis::x() ^ 2) + Basis::x() * 2 + Basis::from(1)));
+    b = exact(vec![Basis::from(-1)]);
+    assert_eq!(a, b);
+
+    // x^2 + 1 = 0 has no real roots
+    a = solve(&((Basis::x() ^ 2) + Basis::from(1)));
+    b = exact(vec![]);
+    assert_eq!(a, b);
+
+    // (x - 1)(x - 2)(2x - 3)x = 0 by rational roots
+    a = solve(
+        &((Basis::x() - Basis::from(1))
+            * (Basis::x() - Basis::from(2))
+            * (Basis::x() * 2 - Basis::from(3))
+            * Basis::x()),
+    );
+    b = exact(vec![
+        Basis::from(0),
+        Basis::from(1),
+        Basis::from((3, 2)),
+        Basis::from(2),
+    ]);
+    println!("{}", a);
+    assert_eq!(a, b);
+
+    // x^3 - 2 = 0 only has an irrational root, found numerically
+    a = solve(&((Basis::x() ^ 3) - Basis::from(2)));
+    println!("{}", a);
+    assert!(!a.complete);
+    assert_eq!(a.roots.len(), 1);
+    assert!((a.roots[0].value().unwrap() - 2f64.powf(1.0 / 3.0)).abs() < 1e-9);
+
+    a = solve(&Basis::from(0));
+    assert!(a.everywhere);
+}
+
+// test exact roots of transcendental functions
+#[test]
+fn test_transcendental_roots() {
+    let (mut a, mut b);
+    let pi = Coefficient::pi().to_basis();
+
+    // e^x = 3, x = log(3)
+    a = solve(&(e_x() - Basis::from(3)));
+    b = exact(vec![LogBasisNode(&Basis::from(3))]);
+    println!("{}", a);
+    assert_eq!(a, b);
+
+    // e^x + 1 = 0 has no roots
+    assert_eq!(solve(&(e_x() + Basis::from(1))), exact(vec![]));
+
+    // log(x) = 2, x = e^2
+    a = solve(&(log_x() - Basis::from(2)));
+    b = exact(vec![EBasisNode(&Basis::from(2))]);
+    println!("{}", a);
+    assert_eq!(a, b);
+
+    // sin(x) = 0, x = kπ
+    a = solve(&sin_x());
+    b = SolutionSet {
+        roots: vec![Root::Family(Basis::from(0), pi.clone())],
+        everywhere: false,
+        complete: true,
+    };
+    println!("{}", a);
+    assert_eq!(a, b);
+
+    // cos(2x) = 0, x = π/4 + kπ/2
+    a = solve(&CosBasisNode(&(Basis::x() * 2)));
+    b = SolutionSet {
+        roots: vec![Root::Family(pi.clone() / 4, pi / 2)],
+        everywhere: false,
+        complete: true,
+    };
+    println!("{}", a);
+    assert_eq!(a, b);
+
+    // xlog(x) = 0 only at x = 1, log(0) is undefined
+    a = solve(&(Basis::x() * log_x()));
+    b = exact(vec![Basis::from(1)]);
+    println!("{}", a);
+    assert_eq!(a, b);
+
+    // (x - 1) / (x + 1) = 0, 1/x has no roots
+    a = solve(&((Basis::x() - Basis::from(1)) / (Basis::x() + Basis::from(1))));
+    assert_eq!(a, exact(vec![Basis::from(1)]));
+    assert_eq!(solve(&(Basis::x() ^ -1)), exact(vec![]));
+}
+
+// test numeric bracketing fallback
+#[test]
+fn test_numeric_roots() {
+    // cos(x) = x near 0.739
+    let a = solve(&(cos_x() - Basis::x()));
+    println!("{}", a);
+    assert!(!a.complete);
+    assert_eq!(a.roots.len(), 1);
+    assert!((a.roots[0].value().unwrap() - 0.7390851332151607).abs() < 1e-9);
+
+    // poles of tan(x) = sin(x) / cos(x) are not roots
+    let a = solve(&(sin_x() / cos_x() - Basis::from(1)));
+    println!("{}", a);
+    for root in a.roots.iter() {
+        let x = root.value().unwrap();
+        assert!((x.tan() - 1.0).abs() < 1e-6);
+    }
+}
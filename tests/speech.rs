@@ -0,0 +1,113 @@
+use nabla_game;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::game::cards::*;
+use nabla_game::util::{Language, ToSpeech};
+
+pub mod util;
+use util::*;
+
+// test English speech of expressions
+#[test]
+fn test_english_speech() {
+    let (mut a, mut b);
+
+    // x^2 log(x)
+    a = (Basis::x() ^ 2) * log_x();
+    b = a.to_speech(Language::English);
+    println!("{}", b);
+    assert_eq!(b, "x squared times the natural log of x");
+
+    a = Basis::x() * 2 - Basis::from(1);
+    b = a.to_speech(Language::English);
+    println!("{}", b);
+    assert_eq!(b, "2 x minus 1");
+
+    a = SqrtBasisNode(1, &(Basis::x() + Basis::from(1)));
+    b = a.to_speech(Language::English);
+    println!("{}", b);
+    assert!(b.starts_with("the square root of the quantity"));
+
+    // groups are closed, and powers of nodes are grouped
+    a = (Basis::x() + Basis::from(2)) * (Basis::x() + Basis::from(1));
+    b = a.to_speech(Language::English);
+    println!("{}", b);
+    assert_eq!(
+        b,
+        "the quantity x plus 2 end quantity times the quantity x plus 1 end quantity"
+    );
+    a = sin_x() / (cos_x() ^ 2);
+    b = a.to_speech(Language::English);
+    println!("{}", b);
+    assert_eq!(
+        b,
+        "sine of x over the quantity cosine of x end quantity squared"
+    );
+
+    assert_eq!((Basis::x() ^ -1).to_speech(Language::English), "1 over x");
+    assert_eq!(e_x().to_speech(Language::English), "e to the x");
+    assert_eq!(
+        CosBasisNode(&(Basis::x() * 3)).to_speech(Language::English),
+        "cosine of 3 x"
+    );
+    assert_eq!(
+        Basis::from((-1, 2)).to_speech(Language::English),
+        "minus 1 over 2"
+    );
+    assert_eq!(
+        Basis::inf(-1).to_speech(Language::English),
+        "minus infinity"
+    );
+    assert_eq!(
+        SqrtBasisNode(1, &Basis::from(2)).to_speech(Language::English),
+        "the square root of 2"
+    );
+}
+
+// test Japanese speech of expressions
+#[test]
+fn test_japanese_speech() {
+    assert_eq!(
+        (Basis::x() ^ 2).to_speech(Language::Japanese),
+        "エックスの2乗"
+    );
+    assert_eq!(log_x().to_speech(Language::Japanese), "エックスの自然対数");
+    assert_eq!(sin_x().to_speech(Language::Japanese), "サインエックス");
+    assert_eq!(
+        (Basis::x() ^ -1).to_speech(Language::Japanese),
+        "エックス分の1"
+    );
+    assert_eq!(Basis::from((1, 2)).to_speech(Language::Japanese), "2分の1");
+    let a = (Basis::x() - Basis::from(1)).to_speech(Language::Japanese);
+    println!("{}", a);
+    assert_eq!(a, "エックスマイナス1");
+
+    // e^(x^2) groups its power instead of reading 乗乗
+    let a = EBasisNode(&(Basis::x() ^ 2)).to_speech(Language::Japanese);
+    println!("{}", a);
+    assert_eq!(a, "イーのかっこエックスの2乗かっことじ乗");
+}
+
+// test speech of cards
+#[test]
+fn test_card_speech() {
+    assert_eq!(
+        Card::LimitCard(LimitCard::LimPosInf).to_speech(Language::English),
+        "limit as x approaches positive infinity"
+    );
+    assert_eq!(
+        Card::BasisCard(BasisCard::X2).to_speech(Language::English),
+        "x squared"
+    );
+    assert_eq!(
+        Card::BasisCard(BasisCard::Cos).to_speech(Language::Japanese),
+        "コサインエックス"
+    );
+    assert_eq!(
+        Card::DerivativeCard(DerivativeCard::Derivative).to_speech(Language::Japanese),
+        "エックスで微分"
+    );
+    assert_eq!(
+        Card::AlgebraicCard(AlgebraicCard::Sqrt).to_speech(Language::English),
+        "square root"
+    );
+}
@@ -0,0 +1,124 @@
+use nabla_game;
+use nabla_game::basis::{builders::*, structs::*, traversal::*};
+use nabla_game::math::util::function_composition;
+
+pub mod util;
+use util::*;
+
+/// records visited bases as strings
+#[derive(Default)]
+struct Recorder {
+    entered: Vec<String>,
+    left: Vec<String>,
+}
+impl BasisVisitor for Recorder {
+    fn enter(&mut self, basis: &Basis) -> bool {
+        self.entered.push(basis.to_string());
+        !basis.is_node(BasisOperator::Sin) // skip inside of sin
+    }
+    fn leave(&mut self, basis: &Basis) {
+        self.left.push(basis.to_string());
+    }
+}
+
+// test traversal orders
+#[test]
+fn test_traversal_order() {
+    let (a, mut b);
+
+    // log(x)/sin(x)
+    a = log_x() / sin_x();
+    println!("{}", a);
+    b = a
+        .pre_order()
+        .map(|b| b.to_string())
+        .collect::<Vec<String>>();
+    println!("{:?}", b);
+    assert_eq!(b.len(), 5);
+    assert_eq!(b[0], a.to_string());
+    assert_eq!(b[1], log_x().to_string());
+    assert_eq!(b[3], sin_x().to_string());
+
+    b = a
+        .post_order()
+        .map(|b| b.to_string())
+        .collect::<Vec<String>>();
+    println!("{:?}", b);
+    assert_eq!(b.len(), 5);
+    assert_eq!(b[1], log_x().to_string());
+    assert_eq!(b[3], sin_x().to_string());
+    assert_eq!(b[4], a.to_string());
+
+    let mut recorder = Recorder::default();
+    a.walk(&mut recorder);
+    println!("{:?} {:?}", recorder.entered, recorder.left);
+    assert_eq!(recorder.entered.len(), 4);
+    assert_eq!(recorder.left.last(), Some(&a.to_string()));
+}
+
+// test fold, contains and find_all
+#[test]
+fn test_fold_and_search() {
+    let a = (Basis::x() ^ 2) * sin_x() + e_x();
+    println!("{}", a);
+
+    let leaves = a.fold(&mut |basis: &Basis, operands: Vec<usize>| match basis {
+        Basis::BasisLeaf(_) => 1,
+        _ => operands.into_iter().sum(),
+    });
+    assert_eq!(leaves, 3);
+
+    assert!(a.contains(|b| b.is_node(BasisOperator::E)));
+    assert!(!a.contains(|b| b.is_node(BasisOperator::Log)));
+    assert_eq!(a.find_all(|b| b.is_x()).len(), 3);
+    assert_eq!(a.find_all(|b| b == &sin_x()), vec![&sin_x()]);
+}
+
+// test rewriting with map_nodes and substitute
+#[test]
+fn test_rewrite() {
+    let (a, mut b);
+
+    a = cos_x() + sin_x();
+    b = a.substitute(&sin_x(), &log_x());
+    println!("{}", b);
+    assert_eq!(b, cos_x() + log_x());
+
+    // x → x^2 inside every operand
+    b = a.substitute(&Basis::x(), &(Basis::x() ^ 2));
+    assert_eq!(b, cos(&(Basis::x() ^ 2)) + sin(&(Basis::x() ^ 2)));
+    assert_eq!(b, function_composition(&a, &(Basis::x() ^ 2)));
+
+    // swap sin and cos
+    b = a.map_nodes(&|basis| match basis {
+        Basis::BasisNode(BasisNode {
+            operator: BasisOperator::Sin,
+            operands,
+            ..
+        }) => CosBasisNode(&operands[0]),
+        Basis::BasisNode(BasisNode {
+            operator: BasisOperator::Cos,
+            operands,
+            ..
+        }) => SinBasisNode(&operands[0]),
+        _ => basis.clone(),
+    });
+    println!("{}", b);
+    assert_eq!(b, a);
+    assert_eq!(a.substitute(&a, &Basis::x()), Basis::x());
+
+    // rebuilt nodes are simplified by the builders
+    b = (Basis::x() + Basis::from(1)).substitute(&Basis::x(), &Basis::from(0));
+    assert_eq!(b, Basis::from(1));
+    b = function_composition(&(Basis::x() ^ 2), &(Basis::x() ^ (1, 2)));
+    println!("{}", b);
+    assert_eq!(b, Basis::x());
+    b = (e_x() * log_x()).map_nodes(&|basis| match basis {
+        Basis::BasisNode(BasisNode {
+            operator: BasisOperator::E,
+            ..
+        }) => Basis::x(),
+        _ => basis.clone(),
+    });
+    assert_eq!(b, Basis::x() * log_x());
+}
@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+
+use nabla_game;
+use nabla_game::basis::{builders::*, structs::*};
+use nabla_game::game::cards::LimitCard;
+use nabla_game::math::derivative::*;
+use nabla_game::math::integral::integral;
+use nabla_game::math::limits::limit;
+
+pub mod util;
+use util::*;
+
+fn y() -> Basis {
+    Basis::var('y')
+}
+
+// test construction and display of variables other than x
+#[test]
+fn test_variables() {
+    let (mut a, b);
+
+    // x is always BasisElement::X
+    a = Basis::var('x');
+    b = Basis::x();
+    assert_eq!(a, b);
+    assert!(b.is_var('x'));
+
+    a = y() * 2;
+    println!("{}", a);
+    assert_eq!(a.to_string(), "2y");
+    assert!(a.is_var('y'));
+    assert!(!a.is_var('x'));
+
+    // xy + sin(z)
+    a = Basis::x() * y() + SinBasisNode(&Basis::var('z'));
+    println!("{}", a);
+    assert_eq!(a.variables().into_iter().collect::<String>(), "xyz");
+    assert!(a.depends_on('z'));
+    assert!(!a.depends_on('w'));
+    assert!(!SinBasisNode(&y()).depends_on('x'));
+}
+
+// test partial derivatives, gradient and Laplacian
+#[test]
+fn test_partial_derivative() {
+    let (mut a, mut b);
+
+    // d/dy x^2y = x^2, d/dx x^2y = 2xy
+    a = (Basis::x() ^ 2) * y();
+    b = Basis::x() ^ 2;
+    println!("d/dy {} = {}", a, partial_derivative(&a, 'y'));
+    assert_eq!(partial_derivative(&a, 'y'), b);
+    b = Basis::x() * y() * 2;
+    println!("d/dx {} = {}", a, partial_derivative(&a, 'x'));
+    assert_eq!(partial_derivative(&a, 'x'), b);
+    assert_eq!(derivative(&a), b);
+
+    // d/dy sin(xy) = xcos(xy)
+    a = SinBasisNode(&(Basis::x() * y()));
+    b = Basis::x() * CosBasisNode(&(Basis::x() * y()));
+    println!("d/dy {} = {}", a, partial_derivative(&a, 'y'));
+    assert_eq!(partial_derivative(&a, 'y'), b);
+
+    // d/dz f(x, y) = 0
+    assert_eq!(partial_derivative(&a, 'z'), Basis::from(0));
+    assert_eq!(derivative(&e_x()), e_x());
+
+    // ∇(x^2 + y^2) = (2x, 2y), Δ(x^2 + y^2) = 4
+    a = (Basis::x() ^ 2) + (y() ^ 2);
+    b = Basis::from(4);
+    println!("∇({}) = {:?}", a, gradient(&a, &['x', 'y']));
+    assert_eq!(gradient(&a, &['x', 'y']), vec![Basis::x() * 2, y() * 2]);
+    println!("Δ({}) = {}", a, laplacian(&a, &['x', 'y']));
+    assert_eq!(laplacian(&a, &['x', 'y']), b);
+
+    // Δ(x^2 - y^2) = 2 - 2 = 0
+    a = (Basis::x() ^ 2) - (y() ^ 2);
+    println!("Δ({}) = {}", a, laplacian(&a, &['x', 'y']));
+    assert_eq!(laplacian(&a, &['x', 'y']), Basis::from(0));
+}
+
+// test other variables are constants for integrals and limits in x
+#[test]
+fn test_variable_constants() {
+    let (mut a, mut b);
+
+    // I(y) = xy
+    a = integral(&y());
+    b = Basis::x() * y();
+    println!("I(y) = {}", a);
+    assert_eq!(a, b);
+
+    // I(ycos(x)) = ysin(x)
+    a = integral(&(y() * cos_x()));
+    b = y() * sin_x();
+    println!("I(ycos(x)) = {}", a);
+    assert_eq!(a, b);
+
+    // I(sin(y)) = xsin(y)
+    a = integral(&SinBasisNode(&y()));
+    b = Basis::x() * SinBasisNode(&y());
+    println!("I(sin(y)) = {}", a);
+    assert_eq!(a, b);
+
+    // lim x→INF y = y, lim x→0 x + e^y = e^y
+    a = limit(&LimitCard::LimPosInf)(&y()).unwrap();
+    assert_eq!(a, y());
+    a = limit(&LimitCard::Lim0)(&(Basis::x() + EBasisNode(&y()))).unwrap();
+    b = EBasisNode(&y());
+    println!("lim x→0 x + e^y = {}", a);
+    assert_eq!(a, b);
+}
+
+// test variables with any symbol sort before functions, so equal sums and products hash the same
+#[test]
+fn test_variable_order() {
+    let (mut a, mut b);
+
+    // non-ASCII symbols have codes above any rank of a node
+    let e_umlaut = Basis::var('Ë');
+    a = e_umlaut.clone() + log_x();
+    b = log_x() + e_umlaut.clone();
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+    assert!(HashSet::from([a.clone()]).contains(&b));
+    assert_eq!(a.to_string(), b.to_string());
+
+    a = (e_umlaut.clone() ^ 2) * y() * Basis::from(2) * sin_x();
+    b = sin_x() * Basis::from(2) * y() * (e_umlaut ^ 2);
+    println!("{} = {}", a, b);
+    assert_eq!(a, b);
+    assert!(HashSet::from([a.clone()]).contains(&b));
+    assert_eq!(a.to_string(), b.to_string());
+}